@@ -0,0 +1,104 @@
+use crate::impls::applications;
+use crate::panelapi::types::staff_applications::StaffApplicationState;
+use crate::{config, Context, Error};
+use poise::serenity_prelude::{
+    CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage, CreateQuickModal,
+    InputTextStyle,
+};
+
+async fn autocomplete<'a>(
+    _ctx: Context<'_>,
+    partial: &str,
+) -> Vec<serenity::all::AutocompleteChoice<'a>> {
+    let mut choices = Vec::new();
+
+    for q in config::CONFIG.applications.question_sets.iter() {
+        if partial.is_empty() || q.name.to_lowercase().contains(&partial.to_lowercase()) {
+            choices.push(serenity::all::AutocompleteChoice::new(
+                q.name.clone(),
+                q.name.clone(),
+            ));
+        }
+    }
+
+    choices
+}
+
+/// Apply to join the staff team
+#[poise::command(category = "Staff", slash_command)]
+pub async fn apply(
+    ctx: Context<'_>,
+    #[description = "What you are applying for"]
+    #[autocomplete = "autocomplete"]
+    position: String,
+) -> Result<(), Error> {
+    let poise::structs::Context::Application(a) = ctx else {
+        return Err("This command can only be used as a slash command".into());
+    };
+
+    let user_id = ctx.author().id.to_string();
+
+    let question_set = applications::get_question_set(&position)?;
+
+    // Check these before showing the modal so applicants do not answer everything for nothing
+    if !applications::get_applications(
+        &ctx.data().pool,
+        Some(&user_id),
+        Some(StaffApplicationState::Pending),
+    )
+    .await?
+    .is_empty()
+    {
+        return Err("You already have an application waiting to be reviewed".into());
+    }
+
+    if let Some(reapply_at) = applications::get_reapply_cooldown(&ctx.data().pool, &user_id).await?
+    {
+        return Err(format!(
+            "Your last application was rejected, you can apply again <t:{}:R>",
+            reapply_at.timestamp()
+        )
+        .into());
+    }
+
+    let mut qm = CreateQuickModal::new(format!("{} Application", question_set.name));
+
+    // Question sets are validated to fit in a modal when the config is loaded
+    for (i, question) in question_set.questions.iter().enumerate() {
+        qm = qm.field(CreateInputText::new(
+            InputTextStyle::Paragraph,
+            question.clone(),
+            format!("q{}", i),
+        ));
+    }
+
+    let Some(resp) = a
+        .interaction
+        .quick_modal(ctx.serenity_context(), qm)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    applications::submit(
+        &botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        &ctx.data().pool,
+        &user_id,
+        &question_set.name,
+        resp.inputs,
+    )
+    .await?;
+
+    resp.interaction
+        .create_response(
+            &ctx.serenity_context().http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content("Your application has been submitted and is waiting to be reviewed")
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
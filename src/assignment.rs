@@ -0,0 +1,153 @@
+use crate::checks;
+use crate::impls::{assignment, target_types::TargetType};
+
+type Error = crate::Error;
+type Context<'a> = crate::Context<'a>;
+
+/// Bot assignment base command
+#[poise::command(
+    category = "Testing",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff",
+    subcommands(
+        "assignment_optin",
+        "assignment_optout",
+        "assignment_status",
+        "assignment_accept",
+        "assignment_decline"
+    )
+)]
+pub async fn assignment(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.say("Some available options are ``assignment optin``, ``assignment optout``, ``assignment status``, ``assignment accept`` and ``assignment decline``")
+        .await?;
+    Ok(())
+}
+
+/// Opts in to being assigned pending bots to review
+#[poise::command(
+    rename = "optin",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff",
+    check = "checks::needs_onboarding"
+)]
+pub async fn assignment_optin(ctx: Context<'_>) -> Result<(), Error> {
+    assignment::opt_in(&ctx.data().pool, &ctx.author().id.to_string()).await?;
+
+    if crate::config::CONFIG.assignment.enabled {
+        ctx.say("You will now be assigned pending bots to review")
+            .await?;
+    } else {
+        ctx.say("You have opted in, however bots are not being assigned right now")
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Opts out of being assigned pending bots to review
+#[poise::command(
+    rename = "optout",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn assignment_optout(ctx: Context<'_>) -> Result<(), Error> {
+    assignment::opt_out(&ctx.data().pool, &ctx.author().id.to_string()).await?;
+
+    ctx.say("You will no longer be assigned pending bots to review")
+        .await?;
+
+    Ok(())
+}
+
+/// Shows the bot currently assigned to you
+#[poise::command(
+    rename = "status",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn assignment_status(ctx: Context<'_>) -> Result<(), Error> {
+    match assignment::get_pending_for_user(&ctx.data().pool, &ctx.author().id.to_string()).await? {
+        Some(a) => {
+            ctx.say(format!(
+                "<@{}> is assigned to you, claim it before <t:{}:t> or it goes back to the open queue",
+                a.bot_id,
+                a.expires_at.timestamp()
+            ))
+            .await?;
+        }
+        None => {
+            ctx.say("No bot is assigned to you right now").await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Accepts the bot assigned to you, claiming it
+#[poise::command(
+    rename = "accept",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff",
+    check = "checks::needs_onboarding"
+)]
+pub async fn assignment_accept(ctx: Context<'_>) -> Result<(), Error> {
+    if !checks::testing_server(ctx).await? {
+        return Err("You are not in the testing server".into());
+    }
+
+    let data = ctx.data();
+
+    let Some(a) =
+        assignment::get_pending_for_user(&data.pool, &ctx.author().id.to_string()).await?
+    else {
+        return Err("No bot is assigned to you right now".into());
+    };
+
+    // Claiming the bot accepts the assignment
+    crate::rpc::core::RPCMethod::Claim {
+        target_id: a.bot_id.clone(),
+        force: false,
+    }
+    .handle(crate::rpc::core::RPCHandle {
+        pool: data.pool.clone(),
+        cache_http: botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        user_id: ctx.author().id.to_string(),
+        target_type: TargetType::Bot,
+    })
+    .await?;
+
+    ctx.say(format!(
+        "Claimed <@{}> successfully, the bot owner has been informed",
+        a.bot_id
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Declines the bot assigned to you, passing it on to the next reviewer
+#[poise::command(
+    rename = "decline",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn assignment_decline(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(bot_id) = assignment::decline(&ctx.data().pool, &ctx.author().id.to_string()).await?
+    else {
+        return Err("No bot is assigned to you right now".into());
+    };
+
+    ctx.say(format!(
+        "Declined <@{}>, it will be assigned to the next reviewer",
+        bot_id
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -51,14 +51,12 @@ pub async fn is_staff(ctx: Context<'_>) -> Result<bool, Error> {
 }
 
 pub async fn needs_onboarding(ctx: Context<'_>) -> Result<bool, Error> {
-    if sqlx::query!(
-        "SELECT COUNT(*) FROM staff_onboardings WHERE user_id = $1 AND void = false AND state = 'completed' AND NOW() - created_at < INTERVAL '1 month'",
+    if !crate::impls::onboarding::has_valid_onboarding(
+        &ctx.data().pool,
         &ctx.author().id.to_string(),
     )
-    .fetch_one(&ctx.data().pool)
     .await?
-    .count
-    .unwrap_or(0) == 0 {
+    {
         return Err("You need to complete onboarding to use this command".into());
     }
 
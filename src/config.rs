@@ -1,8 +1,9 @@
+use crate::panelapi::types::review_checklists::ReviewChecklistItem;
 use crate::Error;
 use once_cell::sync::Lazy;
 use poise::serenity_prelude::{ChannelId, GuildId, RoleId, UserId};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fs::File, io::Write};
+use std::{collections::HashMap, fs::File, io::Write, net::IpAddr};
 
 pub static CURRENT_ENV: Lazy<&str> = Lazy::new(|| {
     let current_env = include_bytes!("../current-env");
@@ -40,6 +41,11 @@ pub struct Servers {
     pub main: GuildId,
     pub staff: GuildId,
     pub testing: GuildId,
+    /// Additional named guilds (such as partner guilds) positions can have corresponding roles in
+    ///
+    /// `main`, `staff` and `testing` always refer to the servers above and cannot be overridden here
+    #[serde(default)]
+    pub aliases: HashMap<String, GuildId>,
 }
 
 impl Default for Servers {
@@ -48,10 +54,41 @@ impl Default for Servers {
             main: GuildId::new(758641373074423808),
             staff: GuildId::new(870950609291972618),
             testing: GuildId::new(870952645811134475),
+            aliases: HashMap::new(),
         }
     }
 }
 
+impl Servers {
+    /// Returns the guild with the given alias, if any
+    pub fn get_by_alias(&self, alias: &str) -> Option<GuildId> {
+        match alias {
+            "main" => Some(self.main),
+            "staff" => Some(self.staff),
+            "testing" => Some(self.testing),
+            _ => self.aliases.get(alias).copied(),
+        }
+    }
+
+    /// Returns all guild aliases, sorted by name
+    pub fn alias_names(&self) -> Vec<String> {
+        let mut names = vec![
+            "main".to_string(),
+            "staff".to_string(),
+            "testing".to_string(),
+        ];
+
+        for alias in self.aliases.keys() {
+            if !names.contains(alias) {
+                names.push(alias.clone());
+            }
+        }
+
+        names.sort();
+        names
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Roles {
     pub awaiting_staff: RoleId,
@@ -120,6 +157,249 @@ pub struct PanelConfig {
     pub panel_scope: String,
     /// Panel response scope, used by frontend for validation. Should be static
     pub panel_response_scope: String,
+
+    /// Whether or not to mirror audit log entries to the staff logs channel
+    #[serde(default)]
+    pub mirror_audit_log: bool,
+
+    /// Reverse proxies in front of the panel API, forwarded IP headers are only honoured from these
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OnboardingConfig {
+    /// The test bot staff members review during onboarding if none is given when starting it
+    pub test_bot: Option<UserId>,
+    /// The questions staff members must answer when submitting their onboarding
+    pub questions: Vec<String>,
+    /// How many days a completed onboarding is valid for by default
+    pub validity_days: i64,
+    /// How many days before a completed onboarding expires to remind the staff member
+    pub reminder_days: i64,
+}
+
+impl Default for OnboardingConfig {
+    fn default() -> Self {
+        Self {
+            test_bot: None,
+            questions: vec![
+                String::from("Which commands of the test bot did you test and did they work?"),
+                String::from("Would you approve or deny the test bot, and why?"),
+                String::from("What feedback would you give the owner of the test bot?"),
+            ],
+            validity_days: 30,
+            reminder_days: 5,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ActivityConfig {
+    /// The number of days activity is computed over when checking for inactivity
+    pub window_days: i32,
+    /// The minimum number of reviews (approvals and denials) in the window
+    pub min_reviews: i64,
+    /// The minimum number of claims in the window
+    pub min_claims: i64,
+    /// The maximum average time between claiming and reviewing a bot, in minutes
+    pub max_avg_claim_to_decision_minutes: Option<i64>,
+    /// The maximum number of auto-unclaims suffered in the window
+    pub max_auto_unclaims: i64,
+    /// The minimum number of panel logins in the window
+    pub min_panel_logins: i64,
+}
+
+impl Default for ActivityConfig {
+    fn default() -> Self {
+        Self {
+            window_days: 7,
+            min_reviews: 3,
+            min_claims: 3,
+            max_avg_claim_to_decision_minutes: Some(60),
+            max_auto_unclaims: 2,
+            min_panel_logins: 0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ResyncConfig {
+    /// Staff resync holds removals of positions with an index at or below this (the top of the
+    /// hierarchy) until they are confirmed on the panel, `None` applies all removals immediately
+    pub hold_removals_max_index: Option<i32>,
+}
+
+impl Default for ResyncConfig {
+    fn default() -> Self {
+        Self {
+            hold_removals_max_index: Some(3),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct QueueConfig {
+    /// How many hours a pending bot can wait without being claimed before ``testing_lounge`` is alerted
+    pub sla_hours: i64,
+    /// Priority given for every hour a bot has been waiting in the queue
+    pub wait_hour_weight: f64,
+    /// Priority given to premium bots
+    pub premium_weight: f64,
+    /// Priority given to bots resubmitted after being denied or unverified
+    pub resubmission_weight: f64,
+    /// Priority given for every other approved bot of the owner
+    pub owner_approved_weight: f64,
+    /// Priority taken for every other denied bot of the owner
+    pub owner_denied_weight: f64,
+    /// The maximum number of approved or denied bots of the owner that count towards the priority
+    pub owner_history_cap: i64,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            sla_hours: 24,
+            wait_hour_weight: 1.0,
+            premium_weight: 24.0,
+            resubmission_weight: 6.0,
+            owner_approved_weight: 2.0,
+            owner_denied_weight: 4.0,
+            owner_history_cap: 5,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AssignmentConfig {
+    /// Whether or not new pending bots are assigned to opted-in reviewers, bots are only claimable from the open queue otherwise
+    pub enabled: bool,
+    /// How many minutes a reviewer has to accept an assignment before the bot goes back to the open queue
+    pub accept_minutes: i64,
+    /// Reviewers who have claimed this many bots are skipped when assigning
+    pub max_claims: i64,
+}
+
+impl Default for AssignmentConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            accept_minutes: 30,
+            max_claims: 1,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SandboxConfig {
+    /// Whether or not claiming a bot creates a private review channel in the testing server for the reviewer and the bot
+    pub enabled: bool,
+    /// The category review channels are created in
+    pub category: Option<ChannelId>,
+    /// The maximum number of messages archived from a review channel
+    pub transcript_limit: usize,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            category: None,
+            transcript_limit: 1000,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct ReviewConfig {
+    /// The checklist reviewers must fill in when approving or denying a bot, empty (the default) to disable
+    pub checklist: Vec<ReviewChecklistItem>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct InviteConfig {
+    /// The scopes bot invites are expected to ask for, reviewers are warned about any other scope
+    pub expected_scopes: Vec<String>,
+}
+
+impl Default for InviteConfig {
+    fn default() -> Self {
+        Self {
+            expected_scopes: vec![String::from("bot"), String::from("applications.commands")],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ApplicationQuestionSet {
+    /// The name applicants choose the question set by
+    pub name: String,
+    /// The questions applicants must answer
+    pub questions: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ApplicationsConfig {
+    /// The question sets users can apply with
+    pub question_sets: Vec<ApplicationQuestionSet>,
+    /// How many days a rejected applicant must wait before applying again
+    pub reapply_cooldown_days: i64,
+}
+
+impl Default for ApplicationsConfig {
+    fn default() -> Self {
+        Self {
+            question_sets: vec![ApplicationQuestionSet {
+                name: String::from("Bot Reviewer"),
+                questions: vec![
+                    String::from("Tell us about yourself"),
+                    String::from("Why do you want to become a bot reviewer?"),
+                    String::from("Any previous staff or moderation experience?"),
+                    String::from("How many hours a week can you review bots?"),
+                    String::from("What would you check before approving a bot?"),
+                ],
+            }],
+            reapply_cooldown_days: 30,
+        }
+    }
+}
+
+impl ApplicationsConfig {
+    /// Discord modals can have at most 5 inputs
+    pub const MAX_QUESTIONS: usize = 5;
+
+    /// Discord input labels can be at most 45 characters long
+    pub const MAX_QUESTION_LENGTH: usize = 45;
+
+    /// Ensures that every question set can be shown in a single modal
+    pub fn validate(&self) -> Result<(), Error> {
+        for set in &self.question_sets {
+            if set.questions.is_empty() || set.questions.len() > Self::MAX_QUESTIONS {
+                return Err(format!(
+                    "Question set {} must have between 1 and {} questions",
+                    set.name,
+                    Self::MAX_QUESTIONS
+                )
+                .into());
+            }
+
+            if let Some(q) = set
+                .questions
+                .iter()
+                .find(|q| q.chars().count() > Self::MAX_QUESTION_LENGTH)
+            {
+                return Err(format!(
+                    "Question \"{}\" of question set {} is longer than {} characters",
+                    q,
+                    set.name,
+                    Self::MAX_QUESTION_LENGTH
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Default)]
@@ -150,6 +430,24 @@ pub struct Config {
     pub protected_bots: Vec<UserId>,
     pub panel: PanelConfig,
     pub japi_key: String,
+    #[serde(default)]
+    pub onboarding: OnboardingConfig,
+    #[serde(default)]
+    pub activity: ActivityConfig,
+    #[serde(default)]
+    pub applications: ApplicationsConfig,
+    #[serde(default)]
+    pub resync: ResyncConfig,
+    #[serde(default)]
+    pub queue: QueueConfig,
+    #[serde(default)]
+    pub review: ReviewConfig,
+    #[serde(default)]
+    pub assignment: AssignmentConfig,
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+    #[serde(default)]
+    pub invite: InviteConfig,
 }
 
 impl Default for Config {
@@ -187,6 +485,15 @@ impl Default for Config {
             ],
             panel: PanelConfig::default(),
             japi_key: String::from(""),
+            onboarding: OnboardingConfig::default(),
+            activity: ActivityConfig::default(),
+            applications: ApplicationsConfig::default(),
+            resync: ResyncConfig::default(),
+            queue: QueueConfig::default(),
+            review: ReviewConfig::default(),
+            assignment: AssignmentConfig::default(),
+            sandbox: SandboxConfig::default(),
+            invite: InviteConfig::default(),
         }
     }
 }
@@ -212,6 +519,8 @@ impl Config {
                 // Parse config.yaml
                 let cfg: Config = serde_yaml::from_reader(file)?;
 
+                cfg.applications.validate()?;
+
                 // Return config
                 Ok(cfg)
             }
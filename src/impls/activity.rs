@@ -0,0 +1,109 @@
+use sqlx::PgPool;
+
+use crate::config::ActivityConfig;
+use crate::panelapi::types::staff_activity::StaffActivity;
+
+/// Returns the activity of all staff members over the last `window_days` days
+///
+/// Members are flagged against the configured thresholds unless they were on an approved leave
+/// of absence during the window
+pub async fn get_staff_activity(
+    pool: &PgPool,
+    window_days: i32,
+) -> Result<Vec<StaffActivity>, crate::Error> {
+    // rpc_logs data is the serialized RPCMethod, e.g. {"Claim": {"target_id": ...}}. Failed RPC calls are
+    // logged too so only successful ones are counted
+    let recs = sqlx::query!(
+        "SELECT sm.user_id,
+            (SELECT COUNT(*) FROM rpc_logs r WHERE r.user_id = sm.user_id AND r.method IN ('Approve', 'Deny') AND r.state = 'success' AND r.created_at > NOW() - make_interval(days => $1)) AS \"reviews!\",
+            (SELECT COUNT(*) FROM rpc_logs r WHERE r.user_id = sm.user_id AND r.method = 'Claim' AND r.state = 'success' AND r.created_at > NOW() - make_interval(days => $1)) AS \"claims!\",
+            (
+                SELECT AVG(EXTRACT(epoch FROM r.created_at - c.created_at))::float8 FROM rpc_logs r
+                CROSS JOIN LATERAL (
+                    SELECT c.created_at FROM rpc_logs c
+                    WHERE c.user_id = r.user_id AND c.method = 'Claim' AND c.state = 'success' AND c.data -> 'Claim' ->> 'target_id' = r.data -> r.method ->> 'target_id' AND c.created_at <= r.created_at
+                    ORDER BY c.created_at DESC LIMIT 1
+                ) c
+                WHERE r.user_id = sm.user_id AND r.method IN ('Approve', 'Deny') AND r.state = 'success' AND r.created_at > NOW() - make_interval(days => $1)
+            ) AS avg_claim_to_decision_secs,
+            (SELECT COUNT(*) FROM staff_auto_unclaims a WHERE a.user_id = sm.user_id AND a.created_at > NOW() - make_interval(days => $1)) AS \"auto_unclaims!\",
+            (SELECT COUNT(*) FROM staffpanel__login_history l WHERE l.user_id = sm.user_id AND l.event = 'MfaSuccess' AND l.created_at > NOW() - make_interval(days => $1)) AS \"panel_logins!\",
+            EXISTS (SELECT 1 FROM staff_loas l WHERE l.user_id = sm.user_id AND l.state = 'approved' AND l.start_date < NOW() AND l.end_date > NOW() - make_interval(days => $1)) AS \"on_loa!\"
+        FROM staff_members sm ORDER BY sm.created_at ASC",
+        window_days
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Error while computing staff activity: {}", e))?;
+
+    Ok(recs
+        .into_iter()
+        .map(|rec| {
+            let mut activity = StaffActivity {
+                user_id: rec.user_id,
+                window_days,
+                reviews: rec.reviews,
+                claims: rec.claims,
+                avg_claim_to_decision_secs: rec.avg_claim_to_decision_secs,
+                auto_unclaims: rec.auto_unclaims,
+                panel_logins: rec.panel_logins,
+                on_loa: rec.on_loa,
+                flags: Vec::new(),
+            };
+
+            if !activity.on_loa {
+                activity.flags = flags(&activity, &crate::config::CONFIG.activity);
+            }
+
+            activity
+        })
+        .collect())
+}
+
+/// Returns the activity thresholds a staff member did not meet
+pub fn flags(activity: &StaffActivity, thresholds: &ActivityConfig) -> Vec<String> {
+    let mut flags = Vec::new();
+
+    if activity.reviews < thresholds.min_reviews {
+        flags.push(format!(
+            "Only {} reviews (minimum {})",
+            activity.reviews, thresholds.min_reviews
+        ));
+    }
+
+    if activity.claims < thresholds.min_claims {
+        flags.push(format!(
+            "Only {} claims (minimum {})",
+            activity.claims, thresholds.min_claims
+        ));
+    }
+
+    if let (Some(avg), Some(max)) = (
+        activity.avg_claim_to_decision_secs,
+        thresholds.max_avg_claim_to_decision_minutes,
+    ) {
+        if avg > (max * 60) as f64 {
+            flags.push(format!(
+                "Takes {} minutes on average to review a claimed bot (maximum {})",
+                (avg / 60.0).round(),
+                max
+            ));
+        }
+    }
+
+    if activity.auto_unclaims > thresholds.max_auto_unclaims {
+        flags.push(format!(
+            "{} auto-unclaims (maximum {})",
+            activity.auto_unclaims, thresholds.max_auto_unclaims
+        ));
+    }
+
+    if activity.panel_logins < thresholds.min_panel_logins {
+        flags.push(format!(
+            "Only {} panel logins (minimum {})",
+            activity.panel_logins, thresholds.min_panel_logins
+        ));
+    }
+
+    flags
+}
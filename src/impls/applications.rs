@@ -0,0 +1,443 @@
+use serenity::all::{CreateEmbed, UserId};
+use sqlx::PgPool;
+
+use super::utils::notify_staff;
+use crate::config::ApplicationQuestionSet;
+use crate::panelapi::types::staff_applications::{
+    StaffApplication, StaffApplicationComment, StaffApplicationResponse, StaffApplicationState,
+    StaffApplicationVote,
+};
+use crate::panelapi::types::staff_timeline::StaffTimelineEventKind;
+
+/// Returns the configured question set with the given name
+pub fn get_question_set(name: &str) -> Result<&'static ApplicationQuestionSet, crate::Error> {
+    crate::config::CONFIG
+        .applications
+        .question_sets
+        .iter()
+        .find(|q| q.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("Question set ``{}`` does not exist", name).into())
+}
+
+/// Returns the applications matching the given filters, newest first
+pub async fn get_applications(
+    pool: &PgPool,
+    user_id: Option<&str>,
+    state: Option<StaffApplicationState>,
+) -> Result<Vec<StaffApplication>, crate::Error> {
+    fetch_applications(pool, None, user_id, state).await
+}
+
+/// Returns an application by ID
+pub async fn get_application(pool: &PgPool, id: &str) -> Result<StaffApplication, crate::Error> {
+    let Ok(id) = sqlx::types::uuid::Uuid::parse_str(id) else {
+        return Err("Application does not exist".into());
+    };
+
+    fetch_applications(pool, Some(id), None, None)
+        .await?
+        .pop()
+        .ok_or_else(|| "Application does not exist".into())
+}
+
+async fn fetch_applications(
+    pool: &PgPool,
+    id: Option<sqlx::types::uuid::Uuid>,
+    user_id: Option<&str>,
+    state: Option<StaffApplicationState>,
+) -> Result<Vec<StaffApplication>, crate::Error> {
+    let recs = sqlx::query!(
+        "SELECT id, user_id, question_set, responses, state, reviewed_by, review_reason, reviewed_at, created_at FROM staff_applications
+        WHERE ($1::uuid IS NULL OR id = $1) AND ($2::text IS NULL OR user_id = $2) AND ($3::text IS NULL OR state = $3)
+        ORDER BY created_at DESC",
+        id,
+        user_id,
+        state.map(|s| s.to_string())
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut applications = Vec::new();
+
+    for rec in recs {
+        let votes = sqlx::query!(
+            "SELECT user_id, approve, created_at FROM staff_application_votes WHERE application_id = $1 ORDER BY created_at ASC",
+            rec.id
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|v| StaffApplicationVote {
+            user_id: v.user_id,
+            approve: v.approve,
+            created_at: v.created_at,
+        })
+        .collect();
+
+        let comments = sqlx::query!(
+            "SELECT id, user_id, comment, created_at FROM staff_application_comments WHERE application_id = $1 ORDER BY created_at ASC",
+            rec.id
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|c| StaffApplicationComment {
+            id: c.id.hyphenated().to_string(),
+            user_id: c.user_id,
+            comment: c.comment,
+            created_at: c.created_at,
+        })
+        .collect();
+
+        applications.push(StaffApplication {
+            id: rec.id.hyphenated().to_string(),
+            user_id: rec.user_id,
+            question_set: rec.question_set,
+            responses: serde_json::from_value::<Vec<StaffApplicationResponse>>(rec.responses)?,
+            state: rec.state,
+            votes,
+            comments,
+            reviewed_by: rec.reviewed_by,
+            review_reason: rec.review_reason,
+            reviewed_at: rec.reviewed_at,
+            created_at: rec.created_at,
+        });
+    }
+
+    Ok(applications)
+}
+
+/// Returns when a rejected applicant can apply again, if they are still on cooldown
+pub async fn get_reapply_cooldown(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, crate::Error> {
+    let rec = sqlx::query!(
+        "SELECT reviewed_at + make_interval(days => $1) AS reapply_at FROM staff_applications
+        WHERE user_id = $2 AND state = $3 AND reviewed_at IS NOT NULL
+        ORDER BY reviewed_at DESC LIMIT 1",
+        crate::config::CONFIG.applications.reapply_cooldown_days as i32,
+        user_id,
+        StaffApplicationState::Rejected.to_string()
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(rec
+        .and_then(|r| r.reapply_at)
+        .filter(|reapply_at| *reapply_at > chrono::Utc::now()))
+}
+
+/// Submits an application with the given question set
+///
+/// Responses must be in the same order as the questions of the question set
+pub async fn submit(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    user_id: &str,
+    question_set: &str,
+    responses: Vec<String>,
+) -> Result<StaffApplication, crate::Error> {
+    let question_set = get_question_set(question_set)?;
+
+    let is_staff = sqlx::query!(
+        "SELECT COUNT(*) FROM staff_members WHERE user_id = $1",
+        user_id
+    )
+    .fetch_one(pool)
+    .await?
+    .count
+    .unwrap_or(0)
+        > 0;
+
+    if is_staff {
+        return Err("You are already a staff member".into());
+    }
+
+    let pending = get_applications(pool, Some(user_id), Some(StaffApplicationState::Pending))
+        .await?
+        .into_iter()
+        .next();
+
+    if pending.is_some() {
+        return Err("You already have an application waiting to be reviewed".into());
+    }
+
+    if let Some(reapply_at) = get_reapply_cooldown(pool, user_id).await? {
+        return Err(format!(
+            "Your last application was rejected, you can apply again <t:{}:R>",
+            reapply_at.timestamp()
+        )
+        .into());
+    }
+
+    if responses.len() != question_set.questions.len() {
+        return Err(format!(
+            "Expected {} responses but got {}",
+            question_set.questions.len(),
+            responses.len()
+        )
+        .into());
+    }
+
+    if responses.iter().any(|r| r.trim().is_empty()) {
+        return Err("All questions must be answered".into());
+    }
+
+    let responses = question_set
+        .questions
+        .iter()
+        .zip(responses)
+        .map(|(question, answer)| StaffApplicationResponse {
+            question: question.clone(),
+            answer,
+        })
+        .collect::<Vec<StaffApplicationResponse>>();
+
+    let id = sqlx::query!(
+        "INSERT INTO staff_applications (user_id, question_set, responses, state) VALUES ($1, $2, $3, $4) RETURNING id",
+        user_id,
+        question_set.name,
+        serde_json::to_value(responses)?,
+        StaffApplicationState::Pending.to_string()
+    )
+    .fetch_one(pool)
+    .await?
+    .id;
+
+    notify_staff(
+        cache_http,
+        user_id,
+        CreateEmbed::new()
+            .title("Staff Application Submitted")
+            .description(format!(
+                "<@{}> applied for {}, the application is now waiting to be reviewed",
+                user_id, question_set.name
+            ))
+            .field("ID", id.hyphenated().to_string(), false),
+    )
+    .await;
+
+    get_application(pool, &id.hyphenated().to_string()).await
+}
+
+/// Votes to accept or reject a pending application, replacing any previous vote of the staff member
+pub async fn vote(
+    pool: &PgPool,
+    id: &str,
+    user_id: &str,
+    approve: bool,
+) -> Result<(), crate::Error> {
+    let application = get_application(pool, id).await?;
+
+    check_pending(&application)?;
+
+    sqlx::query!(
+        "INSERT INTO staff_application_votes (application_id, user_id, approve) VALUES ($1, $2, $3)
+        ON CONFLICT (application_id, user_id) DO UPDATE SET approve = EXCLUDED.approve, created_at = NOW()",
+        sqlx::types::uuid::Uuid::parse_str(&application.id)?,
+        user_id,
+        approve
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Leaves a comment on an application
+pub async fn comment(
+    pool: &PgPool,
+    id: &str,
+    user_id: &str,
+    comment: String,
+) -> Result<(), crate::Error> {
+    let application = get_application(pool, id).await?;
+
+    if comment.trim().is_empty() {
+        return Err("Comment cannot be empty".into());
+    }
+
+    sqlx::query!(
+        "INSERT INTO staff_application_comments (application_id, user_id, comment) VALUES ($1, $2, $3)",
+        sqlx::types::uuid::Uuid::parse_str(&application.id)?,
+        user_id,
+        comment
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Accepts a pending application
+///
+/// The applicant is added as an unaccounted staff member (so staff resync keeps them until
+/// they are given positions) and given the awaiting staff role
+pub async fn accept(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    id: &str,
+    reviewed_by: &str,
+    reason: Option<String>,
+) -> Result<(), crate::Error> {
+    let application = get_application(pool, id).await?;
+
+    check_pending(&application)?;
+
+    let mut tx = pool.begin().await?;
+
+    // The state is checked again in case the application was reviewed concurrently
+    let res = sqlx::query!(
+        "UPDATE staff_applications SET state = $1, reviewed_by = $2, review_reason = $3, reviewed_at = NOW() WHERE id = $4 AND state = $5",
+        StaffApplicationState::Accepted.to_string(),
+        reviewed_by,
+        reason,
+        sqlx::types::uuid::Uuid::parse_str(&application.id)?,
+        StaffApplicationState::Pending.to_string()
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if res.rows_affected() == 0 {
+        return Err("Application has already been reviewed".into());
+    }
+
+    let is_staff = sqlx::query!(
+        "SELECT COUNT(*) FROM staff_members WHERE user_id = $1",
+        application.user_id
+    )
+    .fetch_one(&mut *tx)
+    .await?
+    .count
+    .unwrap_or(0)
+        > 0;
+
+    if is_staff {
+        return Err("Applicant is already a staff member".into());
+    }
+
+    sqlx::query!(
+        "INSERT INTO staff_members (user_id, positions, unaccounted) VALUES ($1, '{}', true)",
+        application.user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    super::timeline::record(
+        &mut *tx,
+        &application.user_id,
+        StaffTimelineEventKind::Membership,
+        Some(reviewed_by),
+        format!(
+            "Added after their {} application was accepted",
+            application.question_set
+        ),
+        serde_json::json!({ "application_id": application.id }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    if let Err(e) = cache_http
+        .http
+        .add_member_role(
+            crate::config::CONFIG.servers.main,
+            application.user_id.parse::<UserId>()?,
+            crate::config::CONFIG.roles.awaiting_staff,
+            Some("Staff application accepted"),
+        )
+        .await
+    {
+        log::error!(
+            "Failed to give accepted applicant the awaiting staff role: {}",
+            e
+        );
+    }
+
+    let mut embed = CreateEmbed::new()
+        .title("Staff Application Accepted")
+        .description(format!(
+            "<@{}> accepted the {} application of <@{}>, welcome to the team!",
+            reviewed_by, application.question_set, application.user_id
+        ))
+        .field("ID", application.id, false);
+
+    if let Some(reason) = reason {
+        embed = embed.field("Note", reason, false);
+    }
+
+    notify_staff(cache_http, &application.user_id, embed).await;
+
+    Ok(())
+}
+
+/// Rejects a pending application, the applicant must wait the configured cooldown before applying again
+pub async fn reject(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    id: &str,
+    reviewed_by: &str,
+    reason: String,
+) -> Result<(), crate::Error> {
+    let application = get_application(pool, id).await?;
+
+    check_pending(&application)?;
+
+    if reason.trim().is_empty() {
+        return Err("Reason cannot be empty".into());
+    }
+
+    // The state is checked again in case the application was reviewed concurrently
+    let Some(rec) = sqlx::query!(
+        "UPDATE staff_applications SET state = $1, reviewed_by = $2, review_reason = $3, reviewed_at = NOW() WHERE id = $4 AND state = $6 RETURNING reviewed_at + make_interval(days => $5) AS reapply_at",
+        StaffApplicationState::Rejected.to_string(),
+        reviewed_by,
+        reason,
+        sqlx::types::uuid::Uuid::parse_str(&application.id)?,
+        crate::config::CONFIG.applications.reapply_cooldown_days as i32,
+        StaffApplicationState::Pending.to_string()
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Err("Application has already been reviewed".into());
+    };
+
+    let reapply_at = rec.reapply_at;
+
+    let mut embed = CreateEmbed::new()
+        .title("Staff Application Rejected")
+        .description(format!(
+            "<@{}> rejected the {} application of <@{}>",
+            reviewed_by, application.question_set, application.user_id
+        ))
+        .field("Reason", reason, false)
+        .field("ID", application.id, false);
+
+    if let Some(reapply_at) = reapply_at {
+        embed = embed.field(
+            "Can Reapply",
+            format!("<t:{}:R>", reapply_at.timestamp()),
+            true,
+        );
+    }
+
+    notify_staff(cache_http, &application.user_id, embed).await;
+
+    Ok(())
+}
+
+/// Ensures that an application is still waiting to be reviewed
+fn check_pending(application: &StaffApplication) -> Result<(), crate::Error> {
+    if application.state != StaffApplicationState::Pending.to_string() {
+        return Err(format!(
+            "Application must be in the {} state, but is {}",
+            StaffApplicationState::Pending,
+            application.state
+        )
+        .into());
+    }
+
+    Ok(())
+}
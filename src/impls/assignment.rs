@@ -0,0 +1,235 @@
+use serenity::all::{CreateEmbed, CreateMessage, UserId};
+use sqlx::PgPool;
+
+use super::target_types::TargetType;
+use crate::config::CONFIG;
+
+/// A pending bot assigned to a reviewer
+pub struct Assignment {
+    pub bot_id: String,
+    pub user_id: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Opts a reviewer in to being assigned pending bots
+pub async fn opt_in(pool: &PgPool, user_id: &str) -> Result<(), crate::Error> {
+    sqlx::query!(
+        "INSERT INTO bot_assignment_reviewers (user_id) VALUES ($1) ON CONFLICT DO NOTHING",
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Opts a reviewer out of being assigned pending bots, declining their pending assignment if any
+pub async fn opt_out(pool: &PgPool, user_id: &str) -> Result<(), crate::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "DELETE FROM bot_assignment_reviewers WHERE user_id = $1",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE bot_assignments SET state = 'declined' WHERE user_id = $1 AND state = 'pending'",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Returns the pending assignment of a bot, if any
+pub async fn get_pending_for_bot(
+    pool: &PgPool,
+    bot_id: &str,
+) -> Result<Option<Assignment>, crate::Error> {
+    let rec = sqlx::query!(
+        "SELECT bot_id, user_id, expires_at FROM bot_assignments WHERE bot_id = $1 AND state = 'pending' AND expires_at > NOW()",
+        bot_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(rec.map(|rec| Assignment {
+        bot_id: rec.bot_id,
+        user_id: rec.user_id,
+        expires_at: rec.expires_at,
+    }))
+}
+
+/// Returns the pending assignment of a reviewer, if any
+pub async fn get_pending_for_user(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Option<Assignment>, crate::Error> {
+    let rec = sqlx::query!(
+        "SELECT bot_id, user_id, expires_at FROM bot_assignments WHERE user_id = $1 AND state = 'pending' AND expires_at > NOW()",
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(rec.map(|rec| Assignment {
+        bot_id: rec.bot_id,
+        user_id: rec.user_id,
+        expires_at: rec.expires_at,
+    }))
+}
+
+/// Closes the pending assignment of a bot once it is claimed, it is accepted if the assignee claimed it
+pub async fn close_on_claim(
+    pool: &PgPool,
+    bot_id: &str,
+    user_id: &str,
+) -> Result<(), crate::Error> {
+    sqlx::query!(
+        "UPDATE bot_assignments SET state = CASE WHEN user_id = $2 THEN 'accepted' ELSE 'cancelled' END WHERE bot_id = $1 AND state = 'pending'",
+        bot_id,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Declines the pending assignment of a reviewer, returning the bot that was assigned
+///
+/// The bot is then assigned to the next reviewer
+pub async fn decline(pool: &PgPool, user_id: &str) -> Result<Option<String>, crate::Error> {
+    let rec = sqlx::query!(
+        "UPDATE bot_assignments SET state = 'declined' WHERE user_id = $1 AND state = 'pending' RETURNING bot_id",
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(rec.map(|rec| rec.bot_id))
+}
+
+/// Expires pending assignments that were not accepted in time, the bots go back to the open queue
+pub async fn expire(pool: &PgPool) -> Result<Vec<Assignment>, crate::Error> {
+    let recs = sqlx::query!(
+        "UPDATE bot_assignments SET state = 'expired' WHERE state = 'pending' AND expires_at <= NOW() RETURNING bot_id, user_id, expires_at",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(recs
+        .into_iter()
+        .map(|rec| Assignment {
+            bot_id: rec.bot_id,
+            user_id: rec.user_id,
+            expires_at: rec.expires_at,
+        })
+        .collect())
+}
+
+/// Picks the next reviewer for a bot in round-robin order, skipping reviewers who are on leave,
+/// have no valid onboarding, have claimed too many bots, already have an assignment or own the bot
+///
+/// Reviewers who were already assigned the bot since it entered the queue are skipped as well
+pub async fn next_reviewer(
+    pool: &PgPool,
+    bot_id: &str,
+    queued_at: chrono::DateTime<chrono::Utc>,
+) -> Result<Option<String>, crate::Error> {
+    let candidates = sqlx::query!(
+        "SELECT r.user_id FROM bot_assignment_reviewers r
+        WHERE r.user_id IN (SELECT user_id FROM staff_members)
+        AND NOT EXISTS (
+            SELECT 1 FROM bot_assignments a WHERE a.user_id = r.user_id AND a.state = 'pending'
+        )
+        AND NOT EXISTS (
+            SELECT 1 FROM bot_assignments a WHERE a.user_id = r.user_id AND a.bot_id = $1 AND a.created_at >= $2
+        )
+        AND (SELECT COUNT(*) FROM bots b WHERE b.claimed_by = r.user_id) < $3
+        ORDER BY r.last_assigned ASC NULLS FIRST",
+        bot_id,
+        queued_at,
+        CONFIG.assignment.max_claims
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let owners = super::utils::get_entity_managers(TargetType::Bot, bot_id, pool)
+        .await?
+        .all();
+
+    for candidate in candidates {
+        if owners.contains(&candidate.user_id)
+            || super::loa::is_on_loa(pool, &candidate.user_id).await?
+            || !super::onboarding::has_valid_onboarding(pool, &candidate.user_id).await?
+        {
+            continue;
+        }
+
+        return Ok(Some(candidate.user_id));
+    }
+
+    Ok(None)
+}
+
+/// Assigns a bot to a reviewer and DMs them about it
+pub async fn assign(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    bot_id: &str,
+    user_id: &str,
+) -> Result<Assignment, crate::Error> {
+    let expires_at =
+        chrono::Utc::now() + chrono::Duration::minutes(CONFIG.assignment.accept_minutes);
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "INSERT INTO bot_assignments (bot_id, user_id, expires_at) VALUES ($1, $2, $3)",
+        bot_id,
+        user_id,
+        expires_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE bot_assignment_reviewers SET last_assigned = NOW() WHERE user_id = $1",
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let embed = CreateEmbed::default()
+        .title("Bot Assigned")
+        .description(format!(
+            "<@{}> has been assigned to you for review! Use ``/assignment accept`` (or ``/claim``) before <t:{}:t> to claim it, or ``/assignment decline`` to pass it on to the next reviewer",
+            bot_id,
+            expires_at.timestamp()
+        ))
+        .color(0x00ff00);
+
+    // Reviewers may have DMs closed, they can still see their assignment with ``/assignment status``
+    if let Ok(id) = user_id.parse::<u64>() {
+        if let Err(e) = UserId::new(id)
+            .direct_message(&cache_http.http, CreateMessage::new().embed(embed))
+            .await
+        {
+            log::warn!("Failed to DM {} about their assignment: {}", user_id, e);
+        }
+    }
+
+    Ok(Assignment {
+        bot_id: bot_id.to_string(),
+        user_id: user_id.to_string(),
+        expires_at,
+    })
+}
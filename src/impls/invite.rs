@@ -0,0 +1,250 @@
+use std::fmt::Display;
+
+use serenity::all::Permissions;
+
+use crate::config::CONFIG;
+use crate::panelapi::types::invite_analysis::InviteAnalysis;
+
+/// Permissions that let a bot take over or disrupt a server, reviewers are warned about these
+const DANGEROUS_PERMISSIONS: [(Permissions, &str); 9] = [
+    (Permissions::ADMINISTRATOR, "Administrator"),
+    (Permissions::MANAGE_GUILD, "Manage Server"),
+    (Permissions::MANAGE_ROLES, "Manage Roles"),
+    (Permissions::MANAGE_CHANNELS, "Manage Channels"),
+    (Permissions::MANAGE_WEBHOOKS, "Manage Webhooks"),
+    (Permissions::BAN_MEMBERS, "Ban Members"),
+    (Permissions::KICK_MEMBERS, "Kick Members"),
+    (Permissions::MODERATE_MEMBERS, "Timeout Members"),
+    (Permissions::MENTION_EVERYONE, "Mention Everyone"),
+];
+
+/// Decodes a stored bot invite, flagging dangerous permissions and unexpected scopes
+pub fn analyze(invite: &str) -> InviteAnalysis {
+    analyze_with_scopes(invite, &CONFIG.invite.expected_scopes)
+}
+
+/// Decodes a stored bot invite against the given expected scopes
+fn analyze_with_scopes(invite: &str, expected: &[String]) -> InviteAnalysis {
+    let mut analysis = InviteAnalysis {
+        client_id: None,
+        scopes: Vec::new(),
+        permissions: None,
+        redirect_uri: None,
+        dangerous_permissions: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    let url = match reqwest::Url::parse(invite) {
+        Ok(url) => url,
+        Err(e) => {
+            analysis.warnings.push(format!("Not a valid URL: {}", e));
+            return analysis;
+        }
+    };
+
+    if !matches!(
+        url.host_str(),
+        Some("discord.com" | "discordapp.com" | "ptb.discord.com" | "canary.discord.com")
+    ) || !url.path().ends_with("/oauth2/authorize")
+    {
+        analysis
+            .warnings
+            .push("Not a Discord OAuth2 invite".to_string());
+        return analysis;
+    }
+
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "client_id" => analysis.client_id = Some(value.to_string()),
+            "scope" => analysis.scopes = value.split_whitespace().map(|s| s.to_string()).collect(),
+            "permissions" => analysis.permissions = Some(value.to_string()),
+            "redirect_uri" => analysis.redirect_uri = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    if analysis.client_id.is_none() {
+        analysis
+            .warnings
+            .push("Missing the ``client_id``".to_string());
+    }
+
+    let missing = expected
+        .iter()
+        .filter(|s| !analysis.scopes.contains(s))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if !missing.is_empty() {
+        analysis
+            .warnings
+            .push(format!("Missing scopes: {}", missing.join(", ")));
+    }
+
+    let unexpected = analysis
+        .scopes
+        .iter()
+        .filter(|s| !expected.contains(s))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if !unexpected.is_empty() {
+        analysis
+            .warnings
+            .push(format!("Unexpected scopes: {}", unexpected.join(", ")));
+    }
+
+    if let Some(ref permissions) = analysis.permissions {
+        match permissions.parse::<u64>() {
+            Ok(bits) => {
+                let permissions = Permissions::from_bits_truncate(bits);
+
+                analysis.dangerous_permissions = DANGEROUS_PERMISSIONS
+                    .iter()
+                    .filter(|(p, _)| permissions.contains(*p))
+                    .map(|(_, name)| name.to_string())
+                    .collect();
+            }
+            Err(_) => analysis.warnings.push(format!(
+                "``{}`` is not a valid permission bitfield",
+                permissions
+            )),
+        }
+    }
+
+    if let Some(ref redirect_uri) = analysis.redirect_uri {
+        analysis
+            .warnings
+            .push(format!("Redirects to {} after inviting", redirect_uri));
+    }
+
+    analysis
+}
+
+/// Returns every problem with an invite, an empty list means the invite looks sane
+pub fn problems(analysis: &InviteAnalysis) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if !analysis.dangerous_permissions.is_empty() {
+        problems.push(format!(
+            "Asks for {}",
+            analysis.dangerous_permissions.join(", ")
+        ));
+    }
+
+    problems.extend(analysis.warnings.iter().cloned());
+
+    problems
+}
+
+/// Builds an invite to the given guild that grants no permissions, whatever the stored invite asks for
+pub fn safe_invite(client_id: &str, guild_id: impl Display) -> String {
+    format!(
+        "https://discord.com/api/v10/oauth2/authorize?client_id={client_id}&permissions=0&scope=bot%20applications.commands&guild_id={guild_id}",
+        client_id = client_id,
+        guild_id = guild_id
+    )
+}
+
+/// Formats an invite analysis for embeds, noting when the invite is for another client ID
+pub fn format_analysis(analysis: &InviteAnalysis, client_id: &str) -> String {
+    let invite_client_id = match analysis.client_id {
+        Some(ref id) if id != client_id => format!("{} (does not match ``{}``)", id, client_id),
+        Some(ref id) => id.clone(),
+        None => "*None*".to_string(),
+    };
+
+    let mut lines = vec![
+        format!("**Client ID:** {}", invite_client_id),
+        format!(
+            "**Scopes:** {}",
+            if analysis.scopes.is_empty() {
+                "*None*".to_string()
+            } else {
+                analysis.scopes.join(", ")
+            }
+        ),
+        format!(
+            "**Permissions:** {}",
+            analysis.permissions.as_deref().unwrap_or("*None*")
+        ),
+    ];
+
+    lines.extend(problems(analysis).into_iter().map(|p| format!("- {}", p)));
+
+    lines
+        .join("\n")
+        // Embed fields can be at most 1024 characters
+        .chars()
+        .take(1024)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected() -> Vec<String> {
+        vec![String::from("bot"), String::from("applications.commands")]
+    }
+
+    #[test]
+    fn bad_url() {
+        let analysis = analyze_with_scopes("not a url", &expected());
+
+        assert!(analysis.client_id.is_none());
+        assert_eq!(analysis.warnings.len(), 1);
+        assert!(analysis.warnings[0].starts_with("Not a valid URL"));
+    }
+
+    #[test]
+    fn missing_scopes() {
+        let analysis = analyze_with_scopes(
+            "https://discord.com/oauth2/authorize?client_id=1&scope=bot&permissions=0",
+            &expected(),
+        );
+
+        assert_eq!(analysis.client_id.as_deref(), Some("1"));
+        assert_eq!(analysis.scopes, vec!["bot".to_string()]);
+        assert_eq!(
+            analysis.warnings,
+            vec!["Missing scopes: applications.commands".to_string()]
+        );
+        assert!(analysis.dangerous_permissions.is_empty());
+    }
+
+    #[test]
+    fn bad_bitfield() {
+        let analysis = analyze_with_scopes(
+            "https://discord.com/oauth2/authorize?client_id=1&scope=bot%20applications.commands&permissions=abc",
+            &expected(),
+        );
+
+        assert_eq!(
+            analysis.warnings,
+            vec!["``abc`` is not a valid permission bitfield".to_string()]
+        );
+        assert!(analysis.dangerous_permissions.is_empty());
+    }
+
+    #[test]
+    fn administrator() {
+        let analysis = analyze_with_scopes(
+            &format!(
+                "https://discord.com/oauth2/authorize?client_id=1&scope=bot%20applications.commands&permissions={}",
+                (Permissions::ADMINISTRATOR | Permissions::SEND_MESSAGES).bits()
+            ),
+            &expected(),
+        );
+
+        assert!(analysis.warnings.is_empty());
+        assert_eq!(
+            analysis.dangerous_permissions,
+            vec!["Administrator".to_string()]
+        );
+        assert_eq!(
+            problems(&analysis),
+            vec!["Asks for Administrator".to_string()]
+        );
+    }
+}
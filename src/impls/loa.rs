@@ -0,0 +1,301 @@
+use serenity::all::CreateEmbed;
+use sqlx::PgPool;
+
+use super::utils::notify_staff;
+use crate::panelapi::types::staff_loa::{StaffLoa, StaffLoaState};
+
+/// Returns the leaves of absence matching the given filters, newest first
+pub async fn get_loas(
+    pool: &PgPool,
+    user_id: Option<&str>,
+    state: Option<StaffLoaState>,
+) -> Result<Vec<StaffLoa>, crate::Error> {
+    fetch_loas(pool, None, user_id, state).await
+}
+
+/// Returns a leave of absence by ID
+pub async fn get_loa(pool: &PgPool, id: &str) -> Result<StaffLoa, crate::Error> {
+    let Ok(id) = sqlx::types::uuid::Uuid::parse_str(id) else {
+        return Err("Leave of absence does not exist".into());
+    };
+
+    fetch_loas(pool, Some(id), None, None)
+        .await?
+        .pop()
+        .ok_or_else(|| "Leave of absence does not exist".into())
+}
+
+async fn fetch_loas(
+    pool: &PgPool,
+    id: Option<sqlx::types::uuid::Uuid>,
+    user_id: Option<&str>,
+    state: Option<StaffLoaState>,
+) -> Result<Vec<StaffLoa>, crate::Error> {
+    let recs = sqlx::query!(
+        "SELECT id, user_id, reason, start_date, end_date, state, reviewed_by, deny_reason, created_at FROM staff_loas
+        WHERE ($1::uuid IS NULL OR id = $1) AND ($2::text IS NULL OR user_id = $2) AND ($3::text IS NULL OR state = $3)
+        ORDER BY created_at DESC",
+        id,
+        user_id,
+        state.map(|s| s.to_string())
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(recs
+        .into_iter()
+        .map(|rec| StaffLoa {
+            id: rec.id.hyphenated().to_string(),
+            user_id: rec.user_id,
+            reason: rec.reason,
+            start_date: rec.start_date,
+            end_date: rec.end_date,
+            state: rec.state,
+            reviewed_by: rec.reviewed_by,
+            deny_reason: rec.deny_reason,
+            created_at: rec.created_at,
+        })
+        .collect())
+}
+
+/// Returns the approved leave of absence a staff member is currently on, if any
+pub async fn get_current_loa(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Option<StaffLoa>, crate::Error> {
+    let now = chrono::Utc::now();
+
+    Ok(get_loas(pool, Some(user_id), Some(StaffLoaState::Approved))
+        .await?
+        .into_iter()
+        .find(|l| l.start_date <= now && l.end_date > now))
+}
+
+/// Returns whether or not a staff member is currently on an approved leave of absence
+pub async fn is_on_loa(pool: &PgPool, user_id: &str) -> Result<bool, crate::Error> {
+    Ok(get_current_loa(pool, user_id).await?.is_some())
+}
+
+/// Requests a leave of absence for a staff member, this must be approved before it takes effect
+pub async fn request(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    user_id: &str,
+    reason: String,
+    start_date: chrono::DateTime<chrono::Utc>,
+    end_date: chrono::DateTime<chrono::Utc>,
+) -> Result<StaffLoa, crate::Error> {
+    let is_staff = sqlx::query!(
+        "SELECT COUNT(*) FROM staff_members WHERE user_id = $1",
+        user_id
+    )
+    .fetch_one(pool)
+    .await?
+    .count
+    .unwrap_or(0)
+        > 0;
+
+    if !is_staff {
+        return Err("User is not a staff member".into());
+    }
+
+    if reason.trim().is_empty() {
+        return Err("Reason cannot be empty".into());
+    }
+
+    if end_date <= start_date {
+        return Err("The end date must be after the start date".into());
+    }
+
+    if end_date <= chrono::Utc::now() {
+        return Err("The end date must be in the future".into());
+    }
+
+    let overlapping = sqlx::query!(
+        "SELECT COUNT(*) FROM staff_loas WHERE user_id = $1 AND state = ANY($2) AND start_date < $4 AND end_date > $3",
+        user_id,
+        &[
+            StaffLoaState::Pending.to_string(),
+            StaffLoaState::Approved.to_string()
+        ],
+        start_date,
+        end_date
+    )
+    .fetch_one(pool)
+    .await?
+    .count
+    .unwrap_or(0)
+        > 0;
+
+    if overlapping {
+        return Err(
+            "This leave of absence overlaps with another pending or approved leave of absence"
+                .into(),
+        );
+    }
+
+    let id = sqlx::query!(
+        "INSERT INTO staff_loas (user_id, reason, start_date, end_date, state) VALUES ($1, $2, $3, $4, $5) RETURNING id",
+        user_id,
+        reason,
+        start_date,
+        end_date,
+        StaffLoaState::Pending.to_string()
+    )
+    .fetch_one(pool)
+    .await?
+    .id;
+
+    notify_staff(
+        cache_http,
+        user_id,
+        CreateEmbed::new()
+            .title("Leave of Absence Requested")
+            .description(format!(
+                "<@{}> requested a leave of absence, it is now waiting for approval",
+                user_id
+            ))
+            .field("Starts", format!("<t:{}:f>", start_date.timestamp()), true)
+            .field("Ends", format!("<t:{}:f>", end_date.timestamp()), true)
+            .field("Reason", reason, false)
+            .field("ID", id.hyphenated().to_string(), false),
+    )
+    .await;
+
+    get_loa(pool, &id.hyphenated().to_string()).await
+}
+
+/// Approves or (if a reason is given) denies a pending leave of absence
+pub async fn review(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    id: &str,
+    reviewed_by: &str,
+    deny_reason: Option<String>,
+) -> Result<(), crate::Error> {
+    let loa = get_loa(pool, id).await?;
+
+    if loa.user_id == reviewed_by {
+        return Err("You cannot approve or deny your own leave of absence".into());
+    }
+
+    if loa.state != StaffLoaState::Pending.to_string() {
+        return Err("Leave of absence is not pending approval".into());
+    }
+
+    if let Some(ref reason) = deny_reason {
+        if reason.trim().is_empty() {
+            return Err("Reason cannot be empty".into());
+        }
+    }
+
+    let new_state = if deny_reason.is_some() {
+        StaffLoaState::Denied
+    } else {
+        StaffLoaState::Approved
+    };
+
+    // The state is checked again in case the leave of absence was reviewed or cancelled concurrently
+    let res = sqlx::query!(
+        "UPDATE staff_loas SET state = $1, reviewed_by = $2, deny_reason = $3 WHERE id = $4 AND state = $5",
+        new_state.to_string(),
+        reviewed_by,
+        deny_reason,
+        sqlx::types::uuid::Uuid::parse_str(&loa.id)?,
+        StaffLoaState::Pending.to_string()
+    )
+    .execute(pool)
+    .await?;
+
+    if res.rows_affected() == 0 {
+        return Err("Leave of absence is not pending approval".into());
+    }
+
+    let mut embed = CreateEmbed::new()
+        .field(
+            "Starts",
+            format!("<t:{}:f>", loa.start_date.timestamp()),
+            true,
+        )
+        .field("Ends", format!("<t:{}:f>", loa.end_date.timestamp()), true)
+        .field("ID", loa.id, false);
+
+    if let Some(reason) = deny_reason {
+        embed = embed
+            .title("Leave of Absence Denied")
+            .description(format!(
+                "<@{}> denied the leave of absence of <@{}>",
+                reviewed_by, loa.user_id
+            ))
+            .field("Reason", reason, false);
+    } else {
+        embed = embed
+            .title("Leave of Absence Approved")
+            .description(format!(
+                "<@{}> approved the leave of absence of <@{}>",
+                reviewed_by, loa.user_id
+            ));
+    }
+
+    notify_staff(cache_http, &loa.user_id, embed).await;
+
+    Ok(())
+}
+
+/// Cancels a pending or approved leave of absence that has not ended yet
+///
+/// Only the staff member on leave can cancel it unless `can_manage` is set
+pub async fn cancel(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    id: &str,
+    cancelled_by: &str,
+    can_manage: bool,
+) -> Result<(), crate::Error> {
+    let loa = get_loa(pool, id).await?;
+
+    if loa.user_id != cancelled_by && !can_manage {
+        return Err("You can only cancel your own leave of absence".into());
+    }
+
+    if loa.state != StaffLoaState::Pending.to_string()
+        && loa.state != StaffLoaState::Approved.to_string()
+    {
+        return Err("Leave of absence has already been denied or cancelled".into());
+    }
+
+    if loa.end_date <= chrono::Utc::now() {
+        return Err("Leave of absence has already ended".into());
+    }
+
+    let res = sqlx::query!(
+        "UPDATE staff_loas SET state = $1 WHERE id = $2 AND state = ANY($3) AND end_date > NOW()",
+        StaffLoaState::Cancelled.to_string(),
+        sqlx::types::uuid::Uuid::parse_str(&loa.id)?,
+        &[
+            StaffLoaState::Pending.to_string(),
+            StaffLoaState::Approved.to_string()
+        ]
+    )
+    .execute(pool)
+    .await?;
+
+    if res.rows_affected() == 0 {
+        return Err("Leave of absence has already been denied, cancelled or ended".into());
+    }
+
+    notify_staff(
+        cache_http,
+        &loa.user_id,
+        CreateEmbed::new()
+            .title("Leave of Absence Cancelled")
+            .description(format!(
+                "<@{}> cancelled the leave of absence of <@{}>",
+                cancelled_by, loa.user_id
+            ))
+            .field("ID", loa.id, false),
+    )
+    .await;
+
+    Ok(())
+}
@@ -1,4 +1,17 @@
+pub mod activity;
+pub mod applications;
+pub mod assignment;
 pub mod dovewing;
+pub mod invite;
 pub mod link;
+pub mod loa;
+pub mod onboarding;
+pub mod permissions;
+pub mod prereview;
+pub mod queue;
+pub mod resync;
+pub mod review;
+pub mod sandbox;
 pub mod target_types;
+pub mod timeline;
 pub mod utils;
@@ -0,0 +1,490 @@
+use serenity::all::CreateEmbed;
+use sqlx::PgPool;
+
+use super::utils::notify_staff;
+use crate::panelapi::types::staff_onboardings::{
+    StaffOnboarding, StaffOnboardingResponse, StaffOnboardingState,
+};
+use crate::panelapi::types::staff_timeline::StaffTimelineEventKind;
+
+/// Returns whether or not a user has a completed, non-void onboarding that has not expired yet
+///
+/// Onboardings completed before expiries were recorded are valid for one month after they were started
+pub async fn has_valid_onboarding(pool: &PgPool, user_id: &str) -> Result<bool, crate::Error> {
+    let count = sqlx::query!(
+        "SELECT COUNT(*) FROM staff_onboardings WHERE user_id = $1 AND void = false AND state = $2 AND COALESCE(expires_at, created_at + INTERVAL '1 month') > NOW()",
+        user_id,
+        StaffOnboardingState::Completed.to_string()
+    )
+    .fetch_one(pool)
+    .await?
+    .count
+    .unwrap_or(0);
+
+    Ok(count > 0)
+}
+
+/// Returns the onboardings matching the given filters, newest first
+pub async fn get_onboardings(
+    pool: &PgPool,
+    user_id: Option<&str>,
+    state: Option<StaffOnboardingState>,
+    include_void: bool,
+) -> Result<Vec<StaffOnboarding>, crate::Error> {
+    fetch_onboardings(pool, None, user_id, state, include_void).await
+}
+
+/// Returns an onboarding by ID
+pub async fn get_onboarding(pool: &PgPool, id: &str) -> Result<StaffOnboarding, crate::Error> {
+    let Ok(id) = sqlx::types::uuid::Uuid::parse_str(id) else {
+        return Err("Onboarding does not exist".into());
+    };
+
+    fetch_onboardings(pool, Some(id), None, None, true)
+        .await?
+        .pop()
+        .ok_or_else(|| "Onboarding does not exist".into())
+}
+
+async fn fetch_onboardings(
+    pool: &PgPool,
+    id: Option<sqlx::types::uuid::Uuid>,
+    user_id: Option<&str>,
+    state: Option<StaffOnboardingState>,
+    include_void: bool,
+) -> Result<Vec<StaffOnboarding>, crate::Error> {
+    let recs = sqlx::query!(
+        "SELECT id, user_id, state, bot_id, started_by, responses, submitted_at, graded_by, passed, feedback, graded_at, completed_by, completed_at, expires_at, void, voided_by, void_reason, created_at FROM staff_onboardings
+        WHERE ($1::uuid IS NULL OR id = $1) AND ($2::text IS NULL OR user_id = $2) AND ($3::text IS NULL OR state = $3) AND ($4 OR void = false)
+        ORDER BY created_at DESC",
+        id,
+        user_id,
+        state.map(|s| s.to_string()),
+        include_void
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut onboardings = Vec::new();
+
+    for rec in recs {
+        onboardings.push(StaffOnboarding {
+            id: rec.id.hyphenated().to_string(),
+            user_id: rec.user_id,
+            state: rec.state,
+            bot_id: rec.bot_id,
+            started_by: rec.started_by,
+            responses: serde_json::from_value::<Vec<StaffOnboardingResponse>>(rec.responses)?,
+            submitted_at: rec.submitted_at,
+            graded_by: rec.graded_by,
+            passed: rec.passed,
+            feedback: rec.feedback,
+            graded_at: rec.graded_at,
+            completed_by: rec.completed_by,
+            completed_at: rec.completed_at,
+            expires_at: rec.expires_at,
+            void: rec.void,
+            voided_by: rec.voided_by,
+            void_reason: rec.void_reason,
+            created_at: rec.created_at,
+        });
+    }
+
+    Ok(onboardings)
+}
+
+/// Starts an onboarding for a staff member with the given (or configured) test bot
+pub async fn start(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    user_id: &str,
+    started_by: &str,
+    bot_id: Option<String>,
+) -> Result<StaffOnboarding, crate::Error> {
+    let is_staff = sqlx::query!(
+        "SELECT COUNT(*) FROM staff_members WHERE user_id = $1",
+        user_id
+    )
+    .fetch_one(pool)
+    .await?
+    .count
+    .unwrap_or(0)
+        > 0;
+
+    if !is_staff {
+        return Err("User is not a staff member".into());
+    }
+
+    let in_progress = get_onboardings(pool, Some(user_id), None, false)
+        .await?
+        .into_iter()
+        .any(|o| {
+            o.state == StaffOnboardingState::Pending.to_string()
+                || o.state == StaffOnboardingState::Submitted.to_string()
+                || o.state == StaffOnboardingState::Graded.to_string()
+        });
+
+    if in_progress {
+        return Err("User already has an onboarding in progress, void it first".into());
+    }
+
+    let Some(bot_id) = bot_id.or_else(|| {
+        crate::config::CONFIG
+            .onboarding
+            .test_bot
+            .map(|b| b.to_string())
+    }) else {
+        return Err("No test bot was given and no default test bot is configured".into());
+    };
+
+    let bot_exists = sqlx::query!("SELECT COUNT(*) FROM bots WHERE bot_id = $1", bot_id)
+        .fetch_one(pool)
+        .await?
+        .count
+        .unwrap_or(0)
+        > 0;
+
+    if !bot_exists {
+        return Err("Test bot does not exist".into());
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let id = sqlx::query!(
+        "INSERT INTO staff_onboardings (user_id, state, bot_id, started_by) VALUES ($1, $2, $3, $4) RETURNING id",
+        user_id,
+        StaffOnboardingState::Pending.to_string(),
+        bot_id,
+        started_by
+    )
+    .fetch_one(&mut *tx)
+    .await?
+    .id;
+
+    super::timeline::record(
+        &mut *tx,
+        user_id,
+        StaffTimelineEventKind::Onboarding,
+        Some(started_by),
+        format!("Onboarding started with the test bot <@{}>", bot_id),
+        serde_json::json!({ "onboarding_id": id.hyphenated().to_string(), "state": StaffOnboardingState::Pending }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    notify_staff(
+        cache_http,
+        user_id,
+        CreateEmbed::new()
+            .title("Onboarding Started")
+            .description(format!(
+                "<@{}> started an onboarding for <@{}>. Review the test bot <@{}> as you would any other bot, then submit your responses",
+                started_by, user_id, bot_id
+            ))
+            .field("ID", id.hyphenated().to_string(), false),
+    )
+    .await;
+
+    get_onboarding(pool, &id.hyphenated().to_string()).await
+}
+
+/// Submits the responses of a staff member to their pending onboarding
+///
+/// Responses must be in the same order as the configured questions
+pub async fn submit(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    id: &str,
+    user_id: &str,
+    responses: Vec<String>,
+) -> Result<(), crate::Error> {
+    let onboarding = get_onboarding(pool, id).await?;
+
+    if onboarding.user_id != user_id {
+        return Err("You can only submit responses to your own onboarding".into());
+    }
+
+    check_state(&onboarding, StaffOnboardingState::Pending)?;
+
+    let questions = &crate::config::CONFIG.onboarding.questions;
+
+    if responses.len() != questions.len() {
+        return Err(format!(
+            "Expected {} responses but got {}",
+            questions.len(),
+            responses.len()
+        )
+        .into());
+    }
+
+    if responses.iter().any(|r| r.trim().is_empty()) {
+        return Err("All questions must be answered".into());
+    }
+
+    let responses = questions
+        .iter()
+        .zip(responses)
+        .map(|(question, answer)| StaffOnboardingResponse {
+            question: question.clone(),
+            answer,
+        })
+        .collect::<Vec<StaffOnboardingResponse>>();
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "UPDATE staff_onboardings SET state = $1, responses = $2, submitted_at = NOW() WHERE id = $3",
+        StaffOnboardingState::Submitted.to_string(),
+        serde_json::to_value(responses)?,
+        sqlx::types::uuid::Uuid::parse_str(&onboarding.id)?
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    super::timeline::record(
+        &mut *tx,
+        user_id,
+        StaffTimelineEventKind::Onboarding,
+        Some(user_id),
+        "Onboarding responses submitted".to_string(),
+        serde_json::json!({ "onboarding_id": onboarding.id, "state": StaffOnboardingState::Submitted }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    notify_staff(
+        cache_http,
+        user_id,
+        CreateEmbed::new()
+            .title("Onboarding Submitted")
+            .description(format!(
+                "<@{}> submitted their onboarding responses, it is now waiting to be graded",
+                user_id
+            ))
+            .field("ID", onboarding.id, false),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Grades a submitted onboarding, a passed onboarding still needs to be completed
+pub async fn grade(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    id: &str,
+    graded_by: &str,
+    passed: bool,
+    feedback: String,
+) -> Result<(), crate::Error> {
+    let onboarding = get_onboarding(pool, id).await?;
+
+    if onboarding.user_id == graded_by {
+        return Err("You cannot grade your own onboarding".into());
+    }
+
+    check_state(&onboarding, StaffOnboardingState::Submitted)?;
+
+    if feedback.trim().is_empty() {
+        return Err("Feedback cannot be empty".into());
+    }
+
+    let new_state = if passed {
+        StaffOnboardingState::Graded
+    } else {
+        StaffOnboardingState::Failed
+    };
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "UPDATE staff_onboardings SET state = $1, graded_by = $2, passed = $3, feedback = $4, graded_at = NOW() WHERE id = $5",
+        new_state.to_string(),
+        graded_by,
+        passed,
+        feedback,
+        sqlx::types::uuid::Uuid::parse_str(&onboarding.id)?
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    super::timeline::record(
+        &mut *tx,
+        &onboarding.user_id,
+        StaffTimelineEventKind::Onboarding,
+        Some(graded_by),
+        format!(
+            "Onboarding {}: {}",
+            if passed { "passed" } else { "failed" },
+            feedback
+        ),
+        serde_json::json!({ "onboarding_id": onboarding.id, "state": new_state }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    notify_staff(
+        cache_http,
+        &onboarding.user_id,
+        CreateEmbed::new()
+            .title(if passed {
+                "Onboarding Passed"
+            } else {
+                "Onboarding Failed"
+            })
+            .description(format!(
+                "<@{}> graded the onboarding of <@{}>",
+                graded_by, onboarding.user_id
+            ))
+            .field("Feedback", feedback, false)
+            .field("ID", onboarding.id, false),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Completes a passed onboarding, making it valid for the given (or configured) number of days
+pub async fn complete(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    id: &str,
+    completed_by: &str,
+    validity_days: Option<i64>,
+) -> Result<(), crate::Error> {
+    let onboarding = get_onboarding(pool, id).await?;
+
+    if onboarding.user_id == completed_by {
+        return Err("You cannot complete your own onboarding".into());
+    }
+
+    check_state(&onboarding, StaffOnboardingState::Graded)?;
+
+    let validity_days = validity_days.unwrap_or(crate::config::CONFIG.onboarding.validity_days);
+
+    if validity_days <= 0 {
+        return Err("Validity must be at least 1 day".into());
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let expires_at = sqlx::query!(
+        "UPDATE staff_onboardings SET state = $1, completed_by = $2, completed_at = NOW(), expires_at = NOW() + make_interval(days => $3) WHERE id = $4 RETURNING expires_at",
+        StaffOnboardingState::Completed.to_string(),
+        completed_by,
+        validity_days as i32,
+        sqlx::types::uuid::Uuid::parse_str(&onboarding.id)?
+    )
+    .fetch_one(&mut *tx)
+    .await?
+    .expires_at;
+
+    super::timeline::record(
+        &mut *tx,
+        &onboarding.user_id,
+        StaffTimelineEventKind::Onboarding,
+        Some(completed_by),
+        format!("Onboarding completed, valid for {} days", validity_days),
+        serde_json::json!({ "onboarding_id": onboarding.id, "state": StaffOnboardingState::Completed, "expires_at": expires_at }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let mut embed = CreateEmbed::new()
+        .title("Onboarding Completed")
+        .description(format!(
+            "<@{}> completed the onboarding of <@{}>",
+            completed_by, onboarding.user_id
+        ))
+        .field("ID", onboarding.id, false);
+
+    if let Some(expires_at) = expires_at {
+        embed = embed.field("Expires", format!("<t:{}:R>", expires_at.timestamp()), true);
+    }
+
+    notify_staff(cache_http, &onboarding.user_id, embed).await;
+
+    Ok(())
+}
+
+/// Voids an onboarding, if it was completed the staff member will need to be onboarded again
+pub async fn void(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    id: &str,
+    voided_by: &str,
+    reason: String,
+) -> Result<(), crate::Error> {
+    let onboarding = get_onboarding(pool, id).await?;
+
+    if onboarding.void {
+        return Err("Onboarding has already been voided".into());
+    }
+
+    if reason.trim().is_empty() {
+        return Err("Reason cannot be empty".into());
+    }
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "UPDATE staff_onboardings SET void = true, voided_by = $1, void_reason = $2 WHERE id = $3",
+        voided_by,
+        reason,
+        sqlx::types::uuid::Uuid::parse_str(&onboarding.id)?
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    super::timeline::record(
+        &mut *tx,
+        &onboarding.user_id,
+        StaffTimelineEventKind::Onboarding,
+        Some(voided_by),
+        format!("Onboarding voided: {}", reason),
+        serde_json::json!({ "onboarding_id": onboarding.id, "void": true }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    notify_staff(
+        cache_http,
+        &onboarding.user_id,
+        CreateEmbed::new()
+            .title("Onboarding Voided")
+            .description(format!(
+                "<@{}> voided the onboarding of <@{}>",
+                voided_by, onboarding.user_id
+            ))
+            .field("Reason", reason, false)
+            .field("ID", onboarding.id, false),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Ensures that an onboarding is not void and is in the expected state
+fn check_state(
+    onboarding: &StaffOnboarding,
+    expected: StaffOnboardingState,
+) -> Result<(), crate::Error> {
+    if onboarding.void {
+        return Err("Onboarding has been voided".into());
+    }
+
+    if onboarding.state != expected.to_string() {
+        return Err(format!(
+            "Onboarding must be in the {} state, but is {}",
+            expected, onboarding.state
+        )
+        .into());
+    }
+
+    Ok(())
+}
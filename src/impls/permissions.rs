@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use kittycat::perms::{self, PartialStaffPosition, Permission, StaffPermissions};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use sqlx::PgPool;
+
+use super::utils::get_user_perms;
+use crate::panelapi::types::permission_simulation::{PermissionEffect, PermissionStep};
+
+/// How long resolved permissions are cached for
+///
+/// Changes made through Arcadia invalidate the cache immediately, this only bounds how late
+/// expiring disciplinaries (and changes made outside Arcadia) are applied
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+static PERMISSION_CACHE: Lazy<Cache<String, Vec<Permission>>> =
+    Lazy::new(|| Cache::builder().time_to_live(CACHE_TTL).build());
+
+/// The permission limits of a disciplinary action
+pub struct DisciplinaryLimits {
+    /// The ID of the disciplinary action, this is also the ID of the position it is added as
+    pub id: String,
+    /// The permission limits of the disciplinary type
+    pub perm_limits: Vec<String>,
+    /// Whether the perm limits are combined with the existing positions or replace them
+    pub additory: bool,
+}
+
+/// Returns the staff permissions of a user with their active disciplinaries applied
+pub async fn get_staff_permissions(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<StaffPermissions, crate::Error> {
+    let mut sp = get_user_perms(pool, user_id).await?;
+
+    let disciplinaries = get_active_disciplinaries(pool, user_id).await?;
+
+    apply_disciplinaries(&mut sp, &disciplinaries);
+
+    Ok(sp)
+}
+
+/// Returns the permission limits of the active disciplinaries of a user, oldest first
+pub async fn get_active_disciplinaries(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Vec<DisciplinaryLimits>, crate::Error> {
+    let disciplinaries = sqlx::query!(
+        "SELECT d.id, t.perm_limits, t.additory FROM staff_disciplinary d
+        INNER JOIN staff_disciplinary_types t ON t.id = d.type
+        WHERE d.user_id = $1 AND d.state = 'active' AND (d.expiry IS NULL OR NOW() - d.created_at < d.expiry)
+        ORDER BY d.created_at ASC",
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        format!(
+            "Error while getting disciplinaries of user {}: {}",
+            user_id, e
+        )
+    })?;
+
+    Ok(disciplinaries
+        .into_iter()
+        .map(|d| DisciplinaryLimits {
+            id: d.id.hyphenated().to_string(),
+            perm_limits: d.perm_limits,
+            additory: d.additory,
+        })
+        .collect())
+}
+
+/// Applies disciplinaries to staff permissions, returning the IDs of the positions they removed
+///
+/// Each disciplinary is added as an index 0 position with its perm limits.
+/// Non-additory disciplinaries replace all positions added before them
+pub fn apply_disciplinaries(
+    sp: &mut StaffPermissions,
+    disciplinaries: &[DisciplinaryLimits],
+) -> Vec<String> {
+    let mut added_ids = Vec::new();
+    let mut removed_ids = Vec::new();
+
+    for disc in disciplinaries {
+        sp.user_positions.push(PartialStaffPosition {
+            id: disc.id.clone(),
+            index: 0,
+            perms: disc
+                .perm_limits
+                .iter()
+                .map(|x| Permission::from_string(x))
+                .collect::<Vec<Permission>>(),
+        });
+        added_ids.push(disc.id.clone());
+
+        if !disc.additory {
+            // Remove all not in added_ids
+            sp.user_positions.retain(|p| {
+                if added_ids.contains(&p.id) {
+                    true
+                } else {
+                    removed_ids.push(p.id.clone());
+                    false
+                }
+            });
+        }
+    }
+
+    removed_ids
+}
+
+/// Returns every permission applied while resolving staff permissions, in resolution order
+///
+/// This mirrors the order kittycat resolves in: positions from the highest index to the lowest
+/// with the permission overrides applied last
+pub fn explain(sp: &StaffPermissions) -> Vec<PermissionStep> {
+    let mut positions = sp.user_positions.clone();
+    positions.push(PartialStaffPosition {
+        id: "perm_overrides".to_string(),
+        index: 0,
+        perms: sp.perm_overrides.clone(),
+    });
+
+    // Stable sort, positions with the same index stay in the order they were added
+    positions.sort_by(|a, b| b.index.cmp(&a.index));
+
+    let mut steps = Vec::new();
+
+    for pos in positions {
+        for perm in pos.perms {
+            let perm = perm.to_string();
+
+            let effect = if perm.ends_with(".@clear") {
+                PermissionEffect::Cleared
+            } else if perm.starts_with('~') {
+                PermissionEffect::Negated
+            } else {
+                PermissionEffect::Added
+            };
+
+            steps.push(PermissionStep {
+                source: pos.id.clone(),
+                index: pos.index,
+                perm,
+                effect,
+            });
+        }
+    }
+
+    steps
+}
+
+/// Returns the resolved permissions of a user, using the cache if possible
+///
+/// This is what all permission checks (RPC, panel actions and bot commands) should use
+pub async fn get_resolved_perms(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Vec<Permission>, crate::Error> {
+    if let Some(perms) = PERMISSION_CACHE.get(user_id) {
+        return Ok(perms);
+    }
+
+    let resolved = get_staff_permissions(pool, user_id).await?.resolve();
+
+    PERMISSION_CACHE
+        .insert(user_id.to_string(), resolved.clone())
+        .await;
+
+    Ok(resolved)
+}
+
+/// Returns whether or not a user has a permission
+pub async fn has_perm(pool: &PgPool, user_id: &str, perm: &str) -> Result<bool, crate::Error> {
+    let resolved = get_resolved_perms(pool, user_id).await?;
+
+    Ok(perms::has_perm(&resolved, &perm.into()))
+}
+
+/// Invalidates the cached permissions of a user
+///
+/// This must be called whenever the positions, overrides or disciplinaries of a user change
+pub async fn invalidate(user_id: &str) {
+    PERMISSION_CACHE.invalidate(user_id).await;
+}
+
+/// Invalidates the cached permissions of all users
+///
+/// This must be called whenever a change may affect many users (e.g. editing a staff position)
+pub fn invalidate_all() {
+    PERMISSION_CACHE.invalidate_all();
+}
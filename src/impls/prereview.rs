@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+
+use super::dovewing::DovewingSource;
+use super::target_types::TargetType;
+use crate::config::CONFIG;
+use crate::panelapi::types::pre_review::{PreReview, PreReviewCheck};
+
+fn to_check(id: &str, label: &str, result: Result<(), String>) -> PreReviewCheck {
+    PreReviewCheck {
+        id: id.to_string(),
+        label: label.to_string(),
+        passed: result.is_ok(),
+        details: result.err(),
+    }
+}
+
+/// Checks that the long description of a bot makes it through the HTML sanitizer
+async fn check_long_description(long: &str) -> Result<(), String> {
+    if long.trim().is_empty() {
+        return Err("The long description is empty".to_string());
+    }
+
+    let res = reqwest::Client::new()
+        .post(&CONFIG.htmlsanitize_url)
+        .body(long.to_string())
+        .send()
+        .await
+        .map_err(|e| format!("Could not reach the HTML sanitizer: {}", e))?;
+
+    let status = res.status();
+    let sanitized = res
+        .text()
+        .await
+        .map_err(|e| format!("Could not read the HTML sanitizer response: {}", e))?;
+
+    if !status.is_success() {
+        return Err(format!("Rejected by the HTML sanitizer: {}", sanitized));
+    }
+
+    if sanitized.trim().is_empty() {
+        return Err("Nothing is left of the long description once sanitized".to_string());
+    }
+
+    Ok(())
+}
+
+/// Runs the automated pre-review checks on a bot, storing the results
+pub async fn run(
+    pool: &PgPool,
+    cache_http: &botox::cache::CacheHttpImpl,
+    bot_id: &str,
+) -> Result<PreReview, crate::Error> {
+    let bot = sqlx::query!(
+        "SELECT client_id, invite, long FROM bots WHERE bot_id = $1",
+        bot_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or("Bot not found")?;
+
+    let mut checks = Vec::new();
+
+    let invite = super::invite::analyze(&bot.invite);
+    let problems = super::invite::problems(&invite);
+
+    checks.push(to_check(
+        "invite",
+        "Invite is a valid bot invite",
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems.join(", "))
+        },
+    ));
+
+    checks.push(to_check(
+        "client_id",
+        "Client ID matches the bot ID",
+        match invite.client_id {
+            Some(ref invite_client_id) if invite_client_id != &bot.client_id => Err(format!(
+                "The invite is for ``{}`` but the client ID is ``{}``",
+                invite_client_id, bot.client_id
+            )),
+            _ if bot.client_id != bot_id => Err(format!(
+                "The client ID ``{}`` differs from the bot ID",
+                bot.client_id
+            )),
+            _ => Ok(()),
+        },
+    ));
+
+    let user = super::dovewing::get_platform_user(
+        pool,
+        DovewingSource::Discord(cache_http.clone()),
+        bot_id,
+    )
+    .await;
+
+    checks.push(to_check(
+        "dovewing",
+        "Bot can be resolved",
+        match user {
+            Ok(ref user) if !user.bot => Err("The account is not a bot".to_string()),
+            Ok(_) => Ok(()),
+            Err(ref e) => Err(format!("Could not resolve the bot: {}", e)),
+        },
+    ));
+
+    let managers = super::utils::get_entity_managers(TargetType::Bot, bot_id, pool)
+        .await?
+        .all();
+
+    let banned = sqlx::query!(
+        "SELECT user_id FROM users WHERE user_id = ANY($1) AND banned = true",
+        &managers
+    )
+    .fetch_all(pool)
+    .await?;
+
+    checks.push(to_check(
+        "not_banned",
+        "Bot is not deleted and its owners are not banned",
+        match user {
+            // Best-effort heuristic: Discord has no deleted flag, deleted accounts are only recognisable
+            // by the placeholder username they get (as in the deleted_bots task). A bot that happens to
+            // be named like this is flagged too, and the cached user may be stale
+            Ok(ref user)
+                if user.username.starts_with("Deleted User")
+                    || user.username.starts_with("deleted_user") =>
+            {
+                Err("The bot has been deleted from Discord".to_string())
+            }
+            _ if !banned.is_empty() => Err(format!(
+                "Banned owners: {}",
+                banned
+                    .iter()
+                    .map(|b| format!("<@{}>", b.user_id))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            _ => Ok(()),
+        },
+    ));
+
+    checks.push(to_check(
+        "long_description",
+        "Long description passes the HTML sanitizer",
+        check_long_description(&bot.long).await,
+    ));
+
+    let rec = sqlx::query!(
+        "INSERT INTO bot_pre_reviews (bot_id, checks) VALUES ($1, $2)
+        ON CONFLICT (bot_id) DO UPDATE SET checks = $2, created_at = NOW() RETURNING created_at",
+        bot_id,
+        serde_json::to_value(&checks)?
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(PreReview {
+        bot_id: bot_id.to_string(),
+        checks,
+        created_at: rec.created_at,
+    })
+}
+
+/// Returns the latest pre-review checks of the given bots, bots that were never checked are left out
+pub async fn get_pre_reviews(
+    pool: &PgPool,
+    bot_ids: &[String],
+) -> Result<HashMap<String, PreReview>, crate::Error> {
+    let recs = sqlx::query!(
+        "SELECT bot_id, checks, created_at FROM bot_pre_reviews WHERE bot_id = ANY($1)",
+        bot_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut pre_reviews = HashMap::new();
+
+    for rec in recs {
+        pre_reviews.insert(
+            rec.bot_id.clone(),
+            PreReview {
+                bot_id: rec.bot_id,
+                checks: serde_json::from_value(rec.checks)?,
+                created_at: rec.created_at,
+            },
+        );
+    }
+
+    Ok(pre_reviews)
+}
+
+/// Formats pre-review checks as a checklist for embeds
+pub fn format_checks(checks: &[PreReviewCheck]) -> String {
+    checks
+        .iter()
+        .map(|c| match c.details {
+            Some(ref details) if !c.passed => format!("[ ] {} ({})", c.label, details),
+            _ => format!("{} {}", if c.passed { "[x]" } else { "[ ]" }, c.label),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        // Embed fields can be at most 1024 characters
+        .chars()
+        .take(1024)
+        .collect()
+}
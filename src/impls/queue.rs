@@ -0,0 +1,109 @@
+use sqlx::PgPool;
+
+use crate::config::CONFIG;
+use crate::panelapi::types::bot_queue::QueuePriority;
+
+/// Returns the pending (and, if `include_claimed` is set, claimed) bots in the order they should be
+/// reviewed in, highest priority first
+pub async fn get_queue(
+    pool: &PgPool,
+    include_claimed: bool,
+) -> Result<Vec<QueuePriority>, crate::Error> {
+    // rpc_logs data is the serialized RPCMethod, e.g. {"Deny": {"target_id": ...}}. A bot re-enters the
+    // queue after being denied or unverified, so the latest of those is when it started waiting again.
+    // Failed RPC calls are logged too and must not count
+    let recs = sqlx::query!(
+        "SELECT b.bot_id, b.premium,
+            GREATEST(b.created_at, (
+                SELECT MAX(r.created_at) FROM rpc_logs r
+                WHERE r.method IN ('Deny', 'Unverify') AND r.state = 'success' AND r.data -> r.method ->> 'target_id' = b.bot_id
+            )) AS \"queued_at!\",
+            EXISTS (
+                SELECT 1 FROM rpc_logs r
+                WHERE r.method IN ('Deny', 'Unverify') AND r.state = 'success' AND r.data -> r.method ->> 'target_id' = b.bot_id
+            ) AS \"resubmission!\",
+            (
+                SELECT COUNT(*) FROM bots o WHERE o.bot_id != b.bot_id AND (o.owner = b.owner OR o.team_owner = b.team_owner)
+                AND (o.type = 'approved' OR o.type = 'certified')
+            ) AS \"owner_approved!\",
+            (
+                SELECT COUNT(*) FROM bots o WHERE o.bot_id != b.bot_id AND (o.owner = b.owner OR o.team_owner = b.team_owner)
+                AND o.type = 'denied'
+            ) AS \"owner_denied!\"
+        FROM bots b WHERE b.type = 'pending' OR ($1 AND b.type = 'claimed')",
+        include_claimed
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let now = chrono::Utc::now();
+
+    let mut queue = recs
+        .into_iter()
+        .map(|rec| {
+            let mut priority = QueuePriority {
+                bot_id: rec.bot_id,
+                position: 0,
+                score: 0.0,
+                queued_at: rec.queued_at,
+                premium: rec.premium,
+                resubmission: rec.resubmission,
+                owner_approved: rec.owner_approved,
+                owner_denied: rec.owner_denied,
+            };
+
+            priority.score = score(&priority, now);
+            priority
+        })
+        .collect::<Vec<_>>();
+
+    // Ties (e.g. two bots added at the same time) go to whoever has waited longest
+    queue.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.queued_at.cmp(&b.queued_at))
+    });
+
+    for (i, priority) in queue.iter_mut().enumerate() {
+        priority.position = i + 1;
+    }
+
+    Ok(queue)
+}
+
+/// Scores a bot in the queue using the configured weights
+fn score(priority: &QueuePriority, now: chrono::DateTime<chrono::Utc>) -> f64 {
+    let weights = &CONFIG.queue;
+
+    let waited_hours = (now - priority.queued_at).num_minutes().max(0) as f64 / 60.0;
+
+    let mut score = waited_hours * weights.wait_hour_weight;
+
+    if priority.premium {
+        score += weights.premium_weight;
+    }
+
+    if priority.resubmission {
+        score += weights.resubmission_weight;
+    }
+
+    score += priority.owner_approved.min(weights.owner_history_cap) as f64
+        * weights.owner_approved_weight;
+    score -=
+        priority.owner_denied.min(weights.owner_history_cap) as f64 * weights.owner_denied_weight;
+
+    score
+}
+
+/// Formats how long a bot has been waiting in the queue, e.g. ``2d 4h``
+pub fn format_wait(queued_at: chrono::DateTime<chrono::Utc>) -> String {
+    let waited = chrono::Utc::now() - queued_at;
+
+    if waited.num_days() > 0 {
+        format!("{}d {}h", waited.num_days(), waited.num_hours() % 24)
+    } else if waited.num_hours() > 0 {
+        format!("{}h {}m", waited.num_hours(), waited.num_minutes() % 60)
+    } else {
+        format!("{}m", waited.num_minutes().max(0))
+    }
+}
@@ -0,0 +1,205 @@
+use serenity::all::{CreateEmbed, RoleId, UserId};
+use sqlx::PgPool;
+
+use super::link::Link;
+use super::utils::notify_staff;
+use crate::panelapi::events::{self, PanelEvent};
+use crate::panelapi::types::staff_resync::StaffResyncHold;
+use crate::panelapi::types::staff_timeline::StaffTimelineEventKind;
+
+/// Returns all held position removals, oldest first
+pub async fn get_holds(pool: &PgPool) -> Result<Vec<StaffResyncHold>, crate::Error> {
+    let recs = sqlx::query!(
+        "SELECT h.id, h.user_id, h.position_id, p.name, p.index, h.created_at FROM staff_resync_holds h
+        INNER JOIN staff_positions p ON p.id = h.position_id
+        ORDER BY h.created_at ASC"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(recs
+        .into_iter()
+        .map(|rec| StaffResyncHold {
+            id: rec.id.hyphenated().to_string(),
+            user_id: rec.user_id,
+            position_id: rec.position_id.hyphenated().to_string(),
+            position_name: rec.name,
+            position_index: rec.index,
+            created_at: rec.created_at,
+        })
+        .collect())
+}
+
+/// Confirms a held removal, removing the position (and its corresponding roles) from the staff member
+pub async fn confirm(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    id: &str,
+    confirmed_by: &str,
+) -> Result<(), crate::Error> {
+    let Ok(id) = sqlx::types::uuid::Uuid::parse_str(id) else {
+        return Err("Hold does not exist".into());
+    };
+
+    let mut tx = pool.begin().await?;
+
+    let Some(hold) = sqlx::query!(
+        "SELECT h.user_id, h.position_id, p.name, p.corresponding_roles FROM staff_resync_holds h
+        INNER JOIN staff_positions p ON p.id = h.position_id
+        WHERE h.id = $1 FOR UPDATE OF h",
+        id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Err("Hold does not exist".into());
+    };
+
+    let old_positions = sqlx::query!(
+        "SELECT positions FROM staff_members WHERE user_id = $1 FOR UPDATE",
+        hold.user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .map(|r| r.positions)
+    .unwrap_or_default();
+
+    sqlx::query!(
+        "UPDATE staff_members SET positions = array_remove(positions, $1) WHERE user_id = $2",
+        hold.position_id,
+        hold.user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!("DELETE FROM staff_resync_holds WHERE id = $1", id)
+        .execute(&mut *tx)
+        .await?;
+
+    super::timeline::record(
+        &mut *tx,
+        &hold.user_id,
+        StaffTimelineEventKind::Positions,
+        Some(confirmed_by),
+        format!("Removal of ``{}`` by staff resync was confirmed", hold.name),
+        serde_json::json!({ "removed": hold.position_id.hyphenated().to_string() }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    super::permissions::invalidate(&hold.user_id).await;
+
+    // The member may have left some of the guilds, this should not undo the confirmation
+    let corresponding_roles = serde_json::from_value::<Vec<Link>>(hold.corresponding_roles)?;
+    let user = hold.user_id.parse::<UserId>()?;
+
+    for link in corresponding_roles {
+        let Some(guild_id) = crate::config::CONFIG.servers.get_by_alias(&link.name) else {
+            continue;
+        };
+
+        let Ok(role_id) = link.value.parse::<RoleId>() else {
+            continue;
+        };
+
+        if let Err(e) = cache_http
+            .http
+            .remove_member_role(guild_id, user, role_id, Some("Removing corresponding role"))
+            .await
+        {
+            log::warn!(
+                "Failed to remove corresponding role {} from {}: {}",
+                role_id,
+                user,
+                e
+            );
+        }
+    }
+
+    events::publish(PanelEvent::StaffResync {
+        user_id: hold.user_id.clone(),
+        old_positions: old_positions
+            .iter()
+            .map(|p| p.hyphenated().to_string())
+            .collect(),
+        new_positions: old_positions
+            .iter()
+            .filter(|p| **p != hold.position_id)
+            .map(|p| p.hyphenated().to_string())
+            .collect(),
+    });
+
+    notify_staff(
+        cache_http,
+        &hold.user_id,
+        CreateEmbed::new()
+            .title("Position Removal Confirmed")
+            .description(format!(
+                "<@{}> confirmed the removal of ``{}`` from <@{}>",
+                confirmed_by, hold.name, hold.user_id
+            )),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Rejects a held removal, giving the staff member back the role of the position in the staff server
+pub async fn reject(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    id: &str,
+    rejected_by: &str,
+) -> Result<(), crate::Error> {
+    let Ok(id) = sqlx::types::uuid::Uuid::parse_str(id) else {
+        return Err("Hold does not exist".into());
+    };
+
+    let Some(hold) = sqlx::query!(
+        "SELECT h.user_id, p.name, p.role_id FROM staff_resync_holds h
+        INNER JOIN staff_positions p ON p.id = h.position_id
+        WHERE h.id = $1",
+        id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Err("Hold does not exist".into());
+    };
+
+    // Otherwise the next resync would hold the removal again
+    cache_http
+        .http
+        .add_member_role(
+            crate::config::CONFIG.servers.staff,
+            hold.user_id.parse::<UserId>()?,
+            hold.role_id.parse::<RoleId>()?,
+            Some("Position removal rejected"),
+        )
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to give the role back, if the staff member left the staff server enable no_autosync for them instead: {}",
+                e
+            )
+        })?;
+
+    sqlx::query!("DELETE FROM staff_resync_holds WHERE id = $1", id)
+        .execute(pool)
+        .await?;
+
+    notify_staff(
+        cache_http,
+        &hold.user_id,
+        CreateEmbed::new()
+            .title("Position Removal Rejected")
+            .description(format!(
+                "<@{}> rejected the removal of ``{}`` from <@{}>, the role has been given back",
+                rejected_by, hold.name, hold.user_id
+            )),
+    )
+    .await;
+
+    Ok(())
+}
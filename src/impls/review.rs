@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+
+use super::target_types::TargetType;
+use crate::config::CONFIG;
+use crate::panelapi::types::review_checklists::{ReviewChecklist, ReviewChecklistAnswer};
+use crate::panelapi::types::review_history::{ReviewHistory, ReviewHistoryEntry};
+use crate::panelapi::types::review_templates::ReviewReasonTemplate;
+
+/// Checks a submitted review checklist against the configured one, returning the answers to store
+pub fn resolve_checklist(
+    checklist: &HashMap<String, bool>,
+    approve: bool,
+) -> Result<Vec<ReviewChecklistAnswer>, crate::Error> {
+    let items = &CONFIG.review.checklist;
+
+    if let Some(unknown) = checklist
+        .keys()
+        .find(|id| !items.iter().any(|i| &i.id == *id))
+    {
+        return Err(format!("Unknown review checklist item: {}", unknown).into());
+    }
+
+    let mut answers = Vec::new();
+
+    for item in items {
+        let Some(passed) = checklist.get(&item.id) else {
+            return Err(format!(
+                "The review checklist item ``{}`` must be filled in",
+                item.label
+            )
+            .into());
+        };
+
+        if approve && item.required_for_approval && !passed {
+            return Err(format!("Bots must pass ``{}`` to be approved", item.label).into());
+        }
+
+        answers.push(ReviewChecklistAnswer {
+            id: item.id.clone(),
+            label: item.label.clone(),
+            passed: *passed,
+        });
+    }
+
+    Ok(answers)
+}
+
+/// Stores a filled in review checklist, nothing is stored if the checklist is disabled
+pub async fn record<'c, E: sqlx::PgExecutor<'c>>(
+    executor: E,
+    bot_id: &str,
+    user_id: &str,
+    decision: &str,
+    reason: &str,
+    answers: &[ReviewChecklistAnswer],
+) -> Result<(), crate::Error> {
+    if answers.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        "INSERT INTO bot_review_checklists (bot_id, user_id, decision, reason, answers) VALUES ($1, $2, $3, $4, $5)",
+        bot_id,
+        user_id,
+        decision,
+        reason,
+        serde_json::to_value(answers)?
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Formats the answers of a review checklist for the mod-logs embed
+pub fn format_answers(answers: &[ReviewChecklistAnswer]) -> String {
+    answers
+        .iter()
+        .map(|a| format!("{} {}", if a.passed { "[x]" } else { "[ ]" }, a.label))
+        .collect::<Vec<_>>()
+        .join("\n")
+        // Embed fields can be at most 1024 characters
+        .chars()
+        .take(1024)
+        .collect()
+}
+
+/// Returns review checklists, newest first
+pub async fn get_checklists(
+    pool: &PgPool,
+    bot_id: Option<&str>,
+    user_id: Option<&str>,
+    failed_item: Option<&str>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<ReviewChecklist>, crate::Error> {
+    let recs = sqlx::query!(
+        "SELECT id, bot_id, user_id, decision, reason, answers, created_at FROM bot_review_checklists
+        WHERE ($1::text IS NULL OR bot_id = $1)
+        AND ($2::text IS NULL OR user_id = $2)
+        AND ($3::text IS NULL OR answers @> jsonb_build_array(jsonb_build_object('id', $3::text, 'passed', false)))
+        ORDER BY created_at DESC LIMIT $4 OFFSET $5",
+        bot_id,
+        user_id,
+        failed_item,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut checklists = Vec::new();
+
+    for rec in recs {
+        checklists.push(ReviewChecklist {
+            id: rec.id.hyphenated().to_string(),
+            bot_id: rec.bot_id,
+            user_id: rec.user_id,
+            decision: rec.decision,
+            reason: rec.reason,
+            answers: serde_json::from_value(rec.answers)?,
+            created_at: rec.created_at,
+        });
+    }
+
+    Ok(checklists)
+}
+
+/// The RPC methods reason templates can be used with
+pub const TEMPLATE_METHODS: [&str; 2] = ["Deny", "Unverify"];
+
+/// Returns the reason templates, optionally only those usable with the given RPC method
+pub async fn get_reason_templates(
+    pool: &PgPool,
+    method: Option<&str>,
+) -> Result<Vec<ReviewReasonTemplate>, crate::Error> {
+    let recs = sqlx::query!(
+        "SELECT id, name, content, methods, created_by, updated_by, created_at, last_updated FROM review_reason_templates
+        WHERE ($1::text IS NULL OR $1 = ANY(methods))
+        ORDER BY name ASC",
+        method
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(recs
+        .into_iter()
+        .map(|rec| ReviewReasonTemplate {
+            id: rec.id.hyphenated().to_string(),
+            name: rec.name,
+            content: rec.content,
+            methods: rec.methods,
+            created_by: rec.created_by,
+            updated_by: rec.updated_by,
+            created_at: rec.created_at,
+            last_updated: rec.last_updated,
+        })
+        .collect())
+}
+
+/// Checks the name, content and methods of a reason template before it is saved
+pub fn validate_reason_template(
+    name: &str,
+    content: &str,
+    methods: &[String],
+) -> Result<(), crate::Error> {
+    // Commands combine templates by separating their names with commas
+    if name.trim().is_empty() || name.len() > 100 || name.contains(',') {
+        return Err("Name must be between 1 and 100 characters and cannot contain commas".into());
+    }
+
+    if content.trim().is_empty() || content.len() > 2000 {
+        return Err("Content must be between 1 and 2000 characters".into());
+    }
+
+    if methods.is_empty() {
+        return Err("Templates must be usable with at least one RPC method".into());
+    }
+
+    if let Some(method) = methods
+        .iter()
+        .find(|m| !TEMPLATE_METHODS.contains(&m.as_str()))
+    {
+        return Err(format!(
+            "Templates cannot be used with {}, must be one of: {}",
+            method,
+            TEMPLATE_METHODS.join(", ")
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Combines reason templates and an optional custom note into a reason, filling in the placeholders
+pub async fn render_reason(
+    pool: &PgPool,
+    cache_http: &botox::cache::CacheHttpImpl,
+    templates: &[ReviewReasonTemplate],
+    bot_id: &str,
+    note: Option<&str>,
+) -> Result<String, crate::Error> {
+    let bot = super::dovewing::get_platform_user(
+        pool,
+        super::dovewing::DovewingSource::Discord(cache_http.clone()),
+        bot_id,
+    )
+    .await?;
+
+    let owners = super::utils::get_entity_managers(TargetType::Bot, bot_id, pool).await?;
+
+    let frontend_url = CONFIG.frontend_url.get();
+
+    let mut parts = templates
+        .iter()
+        .map(|t| {
+            t.content
+                .replace("{bot_name}", &bot.display_name)
+                .replace("{bot_id}", bot_id)
+                .replace("{owner}", &owners.mention_users())
+                .replace("{guide}", &format!("{}/staff/guide", frontend_url))
+                .replace("{frontend_url}", &frontend_url)
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(note) = note.filter(|n| !n.trim().is_empty()) {
+        parts.push(note.to_string());
+    }
+
+    if parts.is_empty() {
+        return Err("Either a reason template or a reason must be given".into());
+    }
+
+    let reason = parts.join("\n\n");
+
+    if reason.len() > 2000 {
+        return Err("The combined reason must be lower than/equal to 2000 characters".into());
+    }
+
+    Ok(reason)
+}
+
+/// The maximum number of review history entries returned per bot
+const MAX_HISTORY_ENTRIES: usize = 10;
+
+/// Returns the review history of each of the given bots
+///
+/// Claims come from ``staff_general_logs`` (which also has claims made before RPC logging), decisions
+/// come from successful ``rpc_logs`` entries
+pub async fn get_review_history(
+    pool: &PgPool,
+    bot_ids: &[String],
+) -> Result<HashMap<String, ReviewHistory>, crate::Error> {
+    let recs = sqlx::query!(
+        "SELECT bot_id AS \"bot_id!\", action AS \"action!\", user_id AS \"user_id!\", reason, created_at AS \"created_at!\" FROM (
+            SELECT r.data -> r.method ->> 'target_id' AS bot_id, r.method AS action, r.user_id, r.data -> r.method ->> 'reason' AS reason, r.created_at
+            FROM rpc_logs r
+            WHERE r.method IN ('Approve', 'Deny', 'Unverify') AND r.state = 'success' AND r.data -> r.method ->> 'target_id' = ANY($1)
+            UNION ALL
+            SELECT g.data ->> 'target_id' AS bot_id, CASE WHEN g.action = 'claimed' THEN 'Claim' ELSE 'Unclaim' END AS action, g.user_id, NULL AS reason, g.created_at
+            FROM staff_general_logs g
+            WHERE g.action IN ('claimed', 'unclaimed') AND g.data ->> 'target_id' = ANY($1)
+        ) h ORDER BY created_at DESC",
+        bot_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut history: HashMap<String, ReviewHistory> = HashMap::new();
+
+    for rec in recs {
+        let h = history.entry(rec.bot_id).or_default();
+
+        if rec.action == "Deny" {
+            h.denials += 1;
+        }
+
+        // Entries are newest first, so the first decision seen is the latest one
+        if h.previous_reviewer.is_none() && rec.action != "Claim" && rec.action != "Unclaim" {
+            h.previous_reviewer = Some(rec.user_id.clone());
+        }
+
+        if h.entries.len() < MAX_HISTORY_ENTRIES {
+            h.entries.push(ReviewHistoryEntry {
+                action: rec.action,
+                user_id: rec.user_id,
+                reason: rec.reason,
+                created_at: rec.created_at,
+            });
+        }
+    }
+
+    Ok(history)
+}
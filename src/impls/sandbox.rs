@@ -0,0 +1,228 @@
+use serenity::all::{
+    ChannelId, ChannelType, CreateChannel, CreateMessage, GetMessages, MessageId,
+    PermissionOverwrite, PermissionOverwriteType, Permissions, RoleId, UserId,
+};
+use sqlx::PgPool;
+
+use crate::config::CONFIG;
+use crate::panelapi::types::review_transcripts::{ReviewTranscript, ReviewTranscriptMessage};
+
+/// Creates a private review channel in the testing server for a reviewer and the bot they claimed
+///
+/// Does nothing if review channels are disabled. An existing review channel of the bot (from a force claim) is archived first
+pub async fn create(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    bot_id: &str,
+    user_id: &str,
+) -> Result<Option<ChannelId>, crate::Error> {
+    if !CONFIG.sandbox.enabled {
+        return Ok(None);
+    }
+
+    archive(cache_http, pool, bot_id, "Claim").await?;
+
+    let testing = CONFIG.servers.testing;
+
+    let allow = Permissions::VIEW_CHANNEL
+        | Permissions::SEND_MESSAGES
+        | Permissions::READ_MESSAGE_HISTORY
+        | Permissions::EMBED_LINKS
+        | Permissions::ATTACH_FILES
+        | Permissions::USE_APPLICATION_COMMANDS;
+
+    let permissions = vec![
+        // The everyone role shares its ID with the guild
+        PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(RoleId::new(testing.get())),
+        },
+        PermissionOverwrite {
+            allow,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(user_id.parse::<UserId>()?),
+        },
+        PermissionOverwrite {
+            allow,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(bot_id.parse::<UserId>()?),
+        },
+    ];
+
+    let mut builder = CreateChannel::new(format!("review-{}", bot_id))
+        .kind(ChannelType::Text)
+        .topic(format!("Private review of <@{}> by <@{}>", bot_id, user_id))
+        .permissions(permissions);
+
+    if let Some(category) = CONFIG.sandbox.category {
+        builder = builder.category(category);
+    }
+
+    let channel = testing.create_channel(&cache_http.http, builder).await?;
+
+    sqlx::query!(
+        "INSERT INTO bot_review_channels (bot_id, channel_id, user_id) VALUES ($1, $2, $3)",
+        bot_id,
+        channel.id.to_string(),
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    channel
+        .id
+        .send_message(
+            &cache_http.http,
+            CreateMessage::new().content(format!(
+                "<@{}>, this is your private channel for reviewing <@{}>. A transcript will be archived once you approve, deny or unclaim the bot",
+                user_id, bot_id
+            )),
+        )
+        .await?;
+
+    Ok(Some(channel.id))
+}
+
+/// Fetches the messages of a review channel, oldest first
+async fn fetch_messages(
+    cache_http: &botox::cache::CacheHttpImpl,
+    channel_id: ChannelId,
+) -> Result<Vec<ReviewTranscriptMessage>, crate::Error> {
+    let mut messages = Vec::new();
+    let mut before: Option<MessageId> = None;
+
+    while messages.len() < CONFIG.sandbox.transcript_limit {
+        let mut request = GetMessages::new().limit(100);
+
+        if let Some(before) = before {
+            request = request.before(before);
+        }
+
+        let page = channel_id.messages(&cache_http.http, request).await?;
+
+        let Some(last) = page.last() else {
+            break;
+        };
+
+        before = Some(last.id);
+
+        for msg in page {
+            messages.push(ReviewTranscriptMessage {
+                author_id: msg.author.id.to_string(),
+                author_name: msg.author.name.to_string(),
+                content: msg.content.to_string(),
+                attachments: msg.attachments.iter().map(|a| a.url.to_string()).collect(),
+                created_at: chrono::DateTime::from_timestamp(msg.timestamp.unix_timestamp(), 0)
+                    .unwrap_or_default(),
+            });
+        }
+    }
+
+    messages.truncate(CONFIG.sandbox.transcript_limit);
+
+    // Discord returns the newest messages first
+    messages.reverse();
+
+    Ok(messages)
+}
+
+/// Archives the transcript of the review channel of a bot and deletes the channel, if the bot has one
+pub async fn archive(
+    cache_http: &botox::cache::CacheHttpImpl,
+    pool: &PgPool,
+    bot_id: &str,
+    action: &str,
+) -> Result<(), crate::Error> {
+    let Some(rec) = sqlx::query!(
+        "SELECT channel_id, user_id FROM bot_review_channels WHERE bot_id = $1",
+        bot_id
+    )
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(());
+    };
+
+    let channel_id = rec.channel_id.parse::<ChannelId>()?;
+
+    // The channel may have been deleted by hand, an empty transcript is still recorded
+    let messages = match fetch_messages(cache_http, channel_id).await {
+        Ok(messages) => messages,
+        Err(e) => {
+            log::error!(
+                "Failed to fetch the review channel of {} for its transcript: {}",
+                bot_id,
+                e
+            );
+            Vec::new()
+        }
+    };
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        "INSERT INTO bot_review_transcripts (bot_id, channel_id, user_id, action, messages) VALUES ($1, $2, $3, $4, $5)",
+        bot_id,
+        rec.channel_id,
+        rec.user_id,
+        action,
+        serde_json::to_value(&messages)?
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!("DELETE FROM bot_review_channels WHERE bot_id = $1", bot_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    if let Err(e) = cache_http
+        .http
+        .delete_channel(channel_id, Some("Review finished, transcript archived"))
+        .await
+    {
+        log::error!("Failed to delete the review channel of {}: {}", bot_id, e);
+    }
+
+    Ok(())
+}
+
+/// Returns the archived review channel transcripts, newest first
+pub async fn get_transcripts(
+    pool: &PgPool,
+    bot_id: Option<&str>,
+    user_id: Option<&str>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<ReviewTranscript>, crate::Error> {
+    let recs = sqlx::query!(
+        "SELECT id, bot_id, channel_id, user_id, action, messages, created_at FROM bot_review_transcripts
+        WHERE ($1::text IS NULL OR bot_id = $1)
+        AND ($2::text IS NULL OR user_id = $2)
+        ORDER BY created_at DESC LIMIT $3 OFFSET $4",
+        bot_id,
+        user_id,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut transcripts = Vec::new();
+
+    for rec in recs {
+        transcripts.push(ReviewTranscript {
+            id: rec.id.hyphenated().to_string(),
+            bot_id: rec.bot_id,
+            channel_id: rec.channel_id,
+            user_id: rec.user_id,
+            action: rec.action,
+            messages: serde_json::from_value(rec.messages)?,
+            created_at: rec.created_at,
+        });
+    }
+
+    Ok(transcripts)
+}
@@ -0,0 +1,55 @@
+use sqlx::PgPool;
+
+use crate::panelapi::types::staff_timeline::{StaffTimelineEvent, StaffTimelineEventKind};
+
+/// Records a change to a staff member in their timeline
+///
+/// Pass the transaction making the change (if any) so the event is only kept if the change is
+pub async fn record<'c, E: sqlx::PgExecutor<'c>>(
+    executor: E,
+    user_id: &str,
+    kind: StaffTimelineEventKind,
+    actor_id: Option<&str>,
+    summary: String,
+    data: serde_json::Value,
+) -> Result<(), crate::Error> {
+    sqlx::query!(
+        "INSERT INTO staff_timeline_events (user_id, kind, actor_id, summary, data) VALUES ($1, $2, $3, $4, $5)",
+        user_id,
+        kind.to_string(),
+        actor_id,
+        summary,
+        data
+    )
+    .execute(executor)
+    .await
+    .map_err(|e| format!("Error while recording staff timeline event: {}", e))?;
+
+    Ok(())
+}
+
+/// Returns the timeline of a staff member, oldest first
+pub async fn get_timeline(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Vec<StaffTimelineEvent>, crate::Error> {
+    let recs = sqlx::query!(
+        "SELECT id, user_id, kind, actor_id, summary, data, created_at FROM staff_timeline_events WHERE user_id = $1 ORDER BY created_at ASC",
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(recs
+        .into_iter()
+        .map(|rec| StaffTimelineEvent {
+            id: rec.id.hyphenated().to_string(),
+            user_id: rec.user_id,
+            kind: rec.kind,
+            actor_id: rec.actor_id,
+            summary: rec.summary,
+            data: rec.data,
+            created_at: rec.created_at,
+        })
+        .collect())
+}
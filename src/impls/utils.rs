@@ -1,4 +1,5 @@
 use kittycat::perms::{PartialStaffPosition, Permission, StaffPermissions};
+use serenity::all::{CreateEmbed, CreateMessage, UserId};
 
 use sqlx::PgPool;
 
@@ -277,3 +278,38 @@ pub async fn get_user_perms(
             .collect::<Vec<Permission>>(),
     })
 }
+
+/// Notifies a staff member (through DMs) and staff_logs of a change affecting them
+///
+/// Errors are logged, not returned, as the change itself has already been made
+pub async fn notify_staff(
+    cache_http: &botox::cache::CacheHttpImpl,
+    user_id: &str,
+    embed: CreateEmbed,
+) {
+    // Staff members may have DMs closed, this should not stop the change from reaching staff_logs
+    if let Ok(id) = user_id.parse::<u64>() {
+        if let Err(e) = UserId::new(id)
+            .direct_message(
+                &cache_http.http,
+                CreateMessage::new().embeds(vec![embed.clone()]),
+            )
+            .await
+        {
+            log::warn!(
+                "Failed to DM {} about a change affecting them: {}",
+                user_id,
+                e
+            );
+        }
+    }
+
+    if let Err(e) = crate::config::CONFIG
+        .channels
+        .staff_logs
+        .send_message(&cache_http.http, CreateMessage::new().embeds(vec![embed]))
+        .await
+    {
+        log::error!("Failed to send change to staff_logs: {}", e);
+    }
+}
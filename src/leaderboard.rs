@@ -1,6 +1,5 @@
 use crate::config;
-use crate::{checks, impls::utils::get_user_perms};
-use kittycat::perms;
+use crate::{checks, impls::permissions};
 use poise::serenity_prelude::{Color, CreateEmbed, CreateMessage};
 use poise::CreateReply;
 
@@ -70,11 +69,13 @@ pub async fn leaderboard(
     check = "checks::staff_server"
 )]
 pub async fn refresh(ctx: Context<'_>) -> Result<(), Error> {
-    let user_perms = get_user_perms(&ctx.data().pool, &ctx.author().id.to_string())
-        .await?
-        .resolve();
-
-    if !perms::has_perm(&user_perms, &"arcadia.force_refresh_top".into()) {
+    if !permissions::has_perm(
+        &ctx.data().pool,
+        &ctx.author().id.to_string(),
+        "arcadia.force_refresh_top",
+    )
+    .await?
+    {
         return Err("You do not have permission to use this command".into());
     }
 
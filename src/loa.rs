@@ -0,0 +1,294 @@
+use crate::checks;
+use crate::impls::{loa, permissions};
+use crate::panelapi::types::staff_loa::{StaffLoa, StaffLoaState};
+use poise::serenity_prelude::{CreateEmbed, User};
+use poise::CreateReply;
+
+type Error = crate::Error;
+type Context<'a> = crate::Context<'a>;
+
+/// Parses a date in the format YYYY-MM-DD as the start of that day (UTC)
+fn parse_date(date: &str) -> Result<chrono::DateTime<chrono::Utc>, Error> {
+    let date = chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(|_| {
+        format!(
+            "Invalid date ``{}``, dates must be in the format YYYY-MM-DD",
+            date
+        )
+    })?;
+
+    Ok(date.and_hms_opt(0, 0, 0).ok_or("Invalid date")?.and_utc())
+}
+
+/// Adds a leave of absence to an embed as a field
+fn loa_field(embed: CreateEmbed, l: &StaffLoa) -> CreateEmbed {
+    let mut value = format!(
+        "<@{}>\n**State:** {}\n**From:** <t:{}:D>\n**To:** <t:{}:D>\n**Reason:** {}",
+        l.user_id,
+        l.state,
+        l.start_date.timestamp(),
+        l.end_date.timestamp(),
+        l.reason
+    );
+
+    if let Some(deny_reason) = &l.deny_reason {
+        value.push_str(&format!("\n**Deny Reason:** {}", deny_reason));
+    }
+
+    embed.field(&l.id, value, false)
+}
+
+/// Leave of absence base command
+#[poise::command(
+    category = "Staff",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff",
+    subcommands(
+        "loa_request",
+        "loa_status",
+        "loa_pending",
+        "loa_approve",
+        "loa_deny",
+        "loa_cancel"
+    )
+)]
+pub async fn loa(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.say("Some available options are ``loa request``, ``loa status``, ``loa pending``, ``loa approve``, ``loa deny`` and ``loa cancel``")
+        .await?;
+    Ok(())
+}
+
+/// Requests a leave of absence
+#[poise::command(
+    rename = "request",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn loa_request(
+    ctx: Context<'_>,
+    #[description = "When your leave starts (YYYY-MM-DD)"] start: String,
+    #[description = "When your leave ends (YYYY-MM-DD)"] end: String,
+    #[description = "Why you are going on leave"] reason: String,
+) -> Result<(), Error> {
+    let requested = loa::request(
+        &botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        &ctx.data().pool,
+        &ctx.author().id.to_string(),
+        reason,
+        parse_date(&start)?,
+        parse_date(&end)?,
+    )
+    .await?;
+
+    ctx.say(format!(
+        "Requested leave of absence ``{}``, it must be approved before it takes effect",
+        requested.id
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Shows the leaves of absence of a staff member
+#[poise::command(
+    rename = "status",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn loa_status(
+    ctx: Context<'_>,
+    #[description = "The staff member to view, defaults to yourself"] user: Option<User>,
+) -> Result<(), Error> {
+    let user_id = user
+        .map(|u| u.id)
+        .unwrap_or_else(|| ctx.author().id)
+        .to_string();
+
+    if user_id != ctx.author().id.to_string()
+        && !permissions::has_perm(
+            &ctx.data().pool,
+            &ctx.author().id.to_string(),
+            "staff_members.loa",
+        )
+        .await?
+    {
+        return Err("You do not have permission to view the leaves of absence of other staff members (staff_members.loa)".into());
+    }
+
+    let loas = loa::get_loas(&ctx.data().pool, Some(&user_id), None).await?;
+    let on_loa = loa::is_on_loa(&ctx.data().pool, &user_id).await?;
+
+    let mut embed = CreateEmbed::new()
+        .title("Leave of Absence Status")
+        .description(format!(
+            "<@{}> is {}on leave",
+            user_id,
+            if on_loa { "" } else { "not " }
+        ));
+
+    // Show the most recent leaves of absence only, older ones are available on the panel
+    for l in loas.iter().take(5) {
+        embed = loa_field(embed, l);
+    }
+
+    ctx.send(CreateReply::new().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Lists the leaves of absence waiting for approval
+#[poise::command(
+    rename = "pending",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn loa_pending(ctx: Context<'_>) -> Result<(), Error> {
+    if !permissions::has_perm(
+        &ctx.data().pool,
+        &ctx.author().id.to_string(),
+        "staff_members.loa",
+    )
+    .await?
+    {
+        return Err(
+            "You do not have permission to approve leaves of absence (staff_members.loa)".into(),
+        );
+    }
+
+    let loas = loa::get_loas(&ctx.data().pool, None, Some(StaffLoaState::Pending)).await?;
+
+    if loas.is_empty() {
+        ctx.say("There are no leaves of absence waiting for approval")
+            .await?;
+        return Ok(());
+    }
+
+    let mut embed = CreateEmbed::new()
+        .title("Pending Leaves of Absence")
+        .description(format!(
+            "There are {} leaves of absence waiting for approval",
+            loas.len()
+        ));
+
+    // Embeds can have at most 25 fields
+    for l in loas.iter().take(25) {
+        embed = loa_field(embed, l);
+    }
+
+    ctx.send(CreateReply::new().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Approves a leave of absence
+#[poise::command(
+    rename = "approve",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn loa_approve(
+    ctx: Context<'_>,
+    #[description = "The ID of the leave of absence"] id: String,
+) -> Result<(), Error> {
+    if !permissions::has_perm(
+        &ctx.data().pool,
+        &ctx.author().id.to_string(),
+        "staff_members.loa",
+    )
+    .await?
+    {
+        return Err(
+            "You do not have permission to approve leaves of absence (staff_members.loa)".into(),
+        );
+    }
+
+    loa::review(
+        &botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        &ctx.data().pool,
+        &id,
+        &ctx.author().id.to_string(),
+        None,
+    )
+    .await?;
+
+    ctx.say(format!("Leave of absence ``{}`` has been approved", id))
+        .await?;
+
+    Ok(())
+}
+
+/// Denies a leave of absence
+#[poise::command(
+    rename = "deny",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn loa_deny(
+    ctx: Context<'_>,
+    #[description = "The ID of the leave of absence"] id: String,
+    #[description = "The reason for denying the leave of absence"] reason: String,
+) -> Result<(), Error> {
+    if !permissions::has_perm(
+        &ctx.data().pool,
+        &ctx.author().id.to_string(),
+        "staff_members.loa",
+    )
+    .await?
+    {
+        return Err(
+            "You do not have permission to deny leaves of absence (staff_members.loa)".into(),
+        );
+    }
+
+    loa::review(
+        &botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        &ctx.data().pool,
+        &id,
+        &ctx.author().id.to_string(),
+        Some(reason),
+    )
+    .await?;
+
+    ctx.say(format!("Leave of absence ``{}`` has been denied", id))
+        .await?;
+
+    Ok(())
+}
+
+/// Cancels a leave of absence
+#[poise::command(
+    rename = "cancel",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn loa_cancel(
+    ctx: Context<'_>,
+    #[description = "The ID of the leave of absence"] id: String,
+) -> Result<(), Error> {
+    let can_manage = permissions::has_perm(
+        &ctx.data().pool,
+        &ctx.author().id.to_string(),
+        "staff_members.loa",
+    )
+    .await?;
+
+    loa::cancel(
+        &botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        &ctx.data().pool,
+        &id,
+        &ctx.author().id.to_string(),
+        can_manage,
+    )
+    .await?;
+
+    ctx.say(format!("Leave of absence ``{}`` has been cancelled", id))
+        .await?;
+
+    Ok(())
+}
@@ -5,6 +5,8 @@ use sqlx::postgres::PgPoolOptions;
 use botox::cache::CacheHttpImpl;
 use std::sync::Arc;
 
+mod apply;
+mod assignment;
 mod botowners;
 mod checks;
 mod config;
@@ -12,6 +14,8 @@ mod explain;
 mod help;
 mod impls;
 mod leaderboard;
+mod loa;
+mod onboarding;
 mod panelapi;
 mod rpc;
 mod rpc_command;
@@ -231,6 +235,8 @@ async fn main() {
             .expect("Could not initialize connection"),
     };
 
+    panelapi::server::setup_schema(&data.pool).await;
+
     let prefix = crate::config::CONFIG.prefix.get();
 
     let framework = poise::Framework::new(poise::FrameworkOptions {
@@ -245,14 +251,20 @@ async fn main() {
             help::help(),
             explain::explainme(),
             staff::staff(),
+            onboarding::onboarding(),
+            loa::loa(),
+            apply::apply(),
             testing::invite_db(),
             testing::invite(),
             testing::claim(),
             testing::unclaim(),
             testing::queue(),
+            testing::prereview(),
             testing::approve(),
             testing::deny(),
+            testing::unverify(),
             testing::staffguide(),
+            assignment::assignment(),
             stats::analytics(),
             stats::info(),
             leaderboard::leaderboard(),
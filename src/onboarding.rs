@@ -0,0 +1,350 @@
+use crate::impls::{onboarding, permissions};
+use crate::panelapi::types::staff_onboardings::StaffOnboardingState;
+use crate::{checks, config};
+use poise::serenity_prelude::{
+    CreateEmbed, CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage,
+    CreateQuickModal, InputTextStyle, User,
+};
+use poise::CreateReply;
+
+type Error = crate::Error;
+type Context<'a> = crate::Context<'a>;
+
+/// Discord modals can have at most 5 inputs
+const MAX_MODAL_INPUTS: usize = 5;
+
+/// Onboarding base command
+#[poise::command(
+    category = "Onboarding",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff",
+    subcommands(
+        "onboarding_status",
+        "onboarding_start",
+        "onboarding_submit",
+        "onboarding_grade",
+        "onboarding_complete",
+        "onboarding_void"
+    )
+)]
+pub async fn onboarding(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.say("Some available options are ``onboarding status``, ``onboarding start``, ``onboarding submit``, ``onboarding grade``, ``onboarding complete`` and ``onboarding void``")
+        .await?;
+    Ok(())
+}
+
+/// Shows the onboardings of a staff member
+#[poise::command(
+    rename = "status",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn onboarding_status(
+    ctx: Context<'_>,
+    #[description = "The staff member to view, defaults to yourself"] user: Option<User>,
+) -> Result<(), Error> {
+    let user_id = user
+        .map(|u| u.id)
+        .unwrap_or_else(|| ctx.author().id)
+        .to_string();
+
+    if user_id != ctx.author().id.to_string()
+        && !permissions::has_perm(
+            &ctx.data().pool,
+            &ctx.author().id.to_string(),
+            "staff_onboardings.view",
+        )
+        .await?
+    {
+        return Err("You do not have permission to view the onboardings of other staff members (staff_onboardings.view)".into());
+    }
+
+    let onboardings =
+        onboarding::get_onboardings(&ctx.data().pool, Some(&user_id), None, true).await?;
+
+    let valid = onboarding::has_valid_onboarding(&ctx.data().pool, &user_id).await?;
+
+    let mut embed = CreateEmbed::new()
+        .title("Onboarding Status")
+        .description(format!(
+            "<@{}> {} a valid onboarding",
+            user_id,
+            if valid { "has" } else { "does not have" }
+        ));
+
+    // Show the most recent onboardings only, older ones are available on the panel
+    for o in onboardings.iter().take(5) {
+        let mut value = format!(
+            "**State:** {}{}\n**Started:** <t:{}:R>",
+            o.state,
+            if o.void { " (void)" } else { "" },
+            o.created_at.timestamp()
+        );
+
+        if let Some(bot_id) = &o.bot_id {
+            value.push_str(&format!("\n**Test Bot:** <@{}>", bot_id));
+        }
+
+        if let Some(feedback) = &o.feedback {
+            value.push_str(&format!("\n**Feedback:** {}", feedback));
+        }
+
+        if let Some(expires_at) = o.expires_at {
+            value.push_str(&format!("\n**Expires:** <t:{}:R>", expires_at.timestamp()));
+        }
+
+        embed = embed.field(&o.id, value, false);
+    }
+
+    ctx.send(CreateReply::new().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Starts an onboarding
+#[poise::command(
+    rename = "start",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn onboarding_start(
+    ctx: Context<'_>,
+    #[description = "The staff member to onboard, defaults to yourself"] user: Option<User>,
+    #[description = "The test bot to review, defaults to the configured test bot"] bot: Option<
+        User,
+    >,
+) -> Result<(), Error> {
+    let user_id = user
+        .map(|u| u.id)
+        .unwrap_or_else(|| ctx.author().id)
+        .to_string();
+
+    if user_id != ctx.author().id.to_string()
+        && !permissions::has_perm(
+            &ctx.data().pool,
+            &ctx.author().id.to_string(),
+            "staff_onboardings.manage",
+        )
+        .await?
+    {
+        return Err("You do not have permission to start the onboarding of other staff members (staff_onboardings.manage)".into());
+    }
+
+    let started = onboarding::start(
+        &botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        &ctx.data().pool,
+        &user_id,
+        &ctx.author().id.to_string(),
+        bot.map(|b| b.id.to_string()),
+    )
+    .await?;
+
+    ctx.say(format!(
+        "Started onboarding ``{}`` for <@{}>. Review the test bot and then use ``/onboarding submit`` to submit your responses",
+        started.id, user_id
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Submits the responses to your onboarding
+#[poise::command(rename = "submit", slash_command, check = "checks::is_staff")]
+pub async fn onboarding_submit(ctx: Context<'_>) -> Result<(), Error> {
+    let poise::structs::Context::Application(a) = ctx else {
+        return Err("This command can only be used as a slash command".into());
+    };
+
+    let user_id = ctx.author().id.to_string();
+
+    let Some(pending) = onboarding::get_onboardings(
+        &ctx.data().pool,
+        Some(&user_id),
+        Some(StaffOnboardingState::Pending),
+        false,
+    )
+    .await?
+    .into_iter()
+    .next() else {
+        return Err("You do not have a pending onboarding, use ``/onboarding start`` first".into());
+    };
+
+    let questions = &config::CONFIG.onboarding.questions;
+
+    if questions.len() > MAX_MODAL_INPUTS {
+        return Err("There are too many onboarding questions to answer here, please submit your responses on the panel instead".into());
+    }
+
+    let mut qm = CreateQuickModal::new("Onboarding Responses");
+
+    for (i, question) in questions.iter().enumerate() {
+        qm = qm.field(
+            CreateInputText::new(
+                InputTextStyle::Paragraph,
+                // Labels are limited to 45 characters by Discord
+                format!("Question {}", i + 1),
+                format!("q{}", i),
+            )
+            .placeholder(question.chars().take(100).collect::<String>()),
+        );
+    }
+
+    let Some(resp) = a
+        .interaction
+        .quick_modal(ctx.serenity_context(), qm)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    onboarding::submit(
+        &botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        &ctx.data().pool,
+        &pending.id,
+        &user_id,
+        resp.inputs,
+    )
+    .await?;
+
+    resp.interaction
+        .create_response(
+            &ctx.serenity_context().http,
+            CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
+                "Your onboarding responses have been submitted and are waiting to be graded",
+            )),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Grades a submitted onboarding
+#[poise::command(
+    rename = "grade",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn onboarding_grade(
+    ctx: Context<'_>,
+    #[description = "The ID of the onboarding"] id: String,
+    #[description = "Whether or not the onboarding passed"] passed: bool,
+    #[description = "Feedback for the staff member"] feedback: String,
+) -> Result<(), Error> {
+    if !permissions::has_perm(
+        &ctx.data().pool,
+        &ctx.author().id.to_string(),
+        "staff_onboardings.grade",
+    )
+    .await?
+    {
+        return Err(
+            "You do not have permission to grade onboardings (staff_onboardings.grade)".into(),
+        );
+    }
+
+    onboarding::grade(
+        &botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        &ctx.data().pool,
+        &id,
+        &ctx.author().id.to_string(),
+        passed,
+        feedback,
+    )
+    .await?;
+
+    ctx.say(format!(
+        "Onboarding ``{}`` has been {}",
+        id,
+        if passed {
+            "passed, it can now be completed"
+        } else {
+            "failed"
+        }
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Completes a passed onboarding
+#[poise::command(
+    rename = "complete",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn onboarding_complete(
+    ctx: Context<'_>,
+    #[description = "The ID of the onboarding"] id: String,
+    #[description = "How many days the onboarding is valid for"] validity_days: Option<i64>,
+) -> Result<(), Error> {
+    if !permissions::has_perm(
+        &ctx.data().pool,
+        &ctx.author().id.to_string(),
+        "staff_onboardings.complete",
+    )
+    .await?
+    {
+        return Err(
+            "You do not have permission to complete onboardings (staff_onboardings.complete)"
+                .into(),
+        );
+    }
+
+    onboarding::complete(
+        &botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        &ctx.data().pool,
+        &id,
+        &ctx.author().id.to_string(),
+        validity_days,
+    )
+    .await?;
+
+    ctx.say(format!("Onboarding ``{}`` has been completed", id))
+        .await?;
+
+    Ok(())
+}
+
+/// Voids an onboarding
+#[poise::command(
+    rename = "void",
+    prefix_command,
+    slash_command,
+    check = "checks::is_staff"
+)]
+pub async fn onboarding_void(
+    ctx: Context<'_>,
+    #[description = "The ID of the onboarding"] id: String,
+    #[description = "The reason for voiding the onboarding"] reason: String,
+) -> Result<(), Error> {
+    if !permissions::has_perm(
+        &ctx.data().pool,
+        &ctx.author().id.to_string(),
+        "staff_onboardings.void",
+    )
+    .await?
+    {
+        return Err(
+            "You do not have permission to void onboardings (staff_onboardings.void)".into(),
+        );
+    }
+
+    onboarding::void(
+        &botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        &ctx.data().pool,
+        &id,
+        &ctx.author().id.to_string(),
+        reason,
+    )
+    .await?;
+
+    ctx.say(format!("Onboarding ``{}`` has been voided", id))
+        .await?;
+
+    Ok(())
+}
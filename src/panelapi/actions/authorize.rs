@@ -1,9 +1,12 @@
-use crate::panelapi::auth::{check_auth, check_auth_insecure};
-use crate::panelapi::core::{AppState, Error};
+use crate::panelapi::auth::record_login_event;
+use crate::panelapi::core::{AppState, Error, RequestMeta};
+use crate::panelapi::registry::{ActionContext, AuthLevel, PanelAction};
 use crate::panelapi::types::auth::{AuthorizeAction, MfaLogin, MfaLoginSecret};
+use crate::panelapi::types::login_history::LoginEvent;
 use crate::panelapi::types::webcore::StartAuth;
 use axum::response::Response;
 use axum::{http::StatusCode, response::IntoResponse, Json};
+use futures_util::future::BoxFuture;
 use rand::Rng;
 use serde::Deserialize;
 use serenity::all::User;
@@ -11,13 +14,58 @@ use std::time::Duration;
 
 const AUTH_VERSION: u16 = 5;
 
-pub async fn authorize(
-    state: &AppState,
+/// Records a login event, logging (but otherwise ignoring) any errors
+///
+/// Login history should never prevent a staff member from logging in
+async fn log_login_event(state: &AppState, user_id: &str, event: LoginEvent, meta: &RequestMeta) {
+    if let Err(e) = record_login_event(state, user_id, event, meta).await {
+        log::error!("Failed to record login event for {}: {}", user_id, e);
+    }
+}
+
+pub struct AuthorizeQuery {
+    /// Authorize protocol version, should be `AUTH_VERSION`
+    pub version: u16,
+    /// Action to take
+    pub action: AuthorizeAction,
+}
+
+impl PanelAction for AuthorizeQuery {
+    fn name(&self) -> String {
+        format!("Authorize.{}", self.action)
+    }
+
+    fn auth_level(&self) -> AuthLevel {
+        match self.action {
+            // Logout deletes the session itself, no point in erroring if it doesn't exist
+            AuthorizeAction::Begin { .. }
+            | AuthorizeAction::CreateSession { .. }
+            | AuthorizeAction::Logout { .. } => AuthLevel::None,
+            AuthorizeAction::CheckMfaState { .. } | AuthorizeAction::ActivateSession { .. } => {
+                AuthLevel::Insecure
+            }
+            AuthorizeAction::ResetMfaTotp { .. } => AuthLevel::Active,
+        }
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(authorize(ctx, self.version, self.action))
+    }
+}
+
+async fn authorize(
+    ctx: &ActionContext<'_>,
     // Authorize protocol version, should be `AUTH_VERSION`
     version: u16,
     // Action to take
     action: AuthorizeAction,
 ) -> Result<Response, Error> {
+    let state = ctx.state;
+    let meta = ctx.meta;
+
     if version != AUTH_VERSION {
         return Ok((StatusCode::BAD_REQUEST, "Invalid version".to_string()).into_response());
     }
@@ -160,12 +208,12 @@ pub async fn authorize(
 
             tx.commit().await.map_err(Error::new)?;
 
+            log_login_event(state, &user.id.to_string(), LoginEvent::CreateSession, meta).await;
+
             Ok((StatusCode::OK, token).into_response())
         }
-        AuthorizeAction::CheckMfaState { login_token } => {
-            let auth_data = check_auth_insecure(&state.pool, &login_token)
-                .await
-                .map_err(Error::new)?;
+        AuthorizeAction::CheckMfaState { .. } => {
+            let auth_data = ctx.auth_data()?;
 
             if auth_data.state != "pending" && auth_data.state != "active" {
                 return Err(Error {
@@ -257,10 +305,8 @@ pub async fn authorize(
                 Ok((StatusCode::OK, Json(MfaLogin { info: None })).into_response())
             }
         }
-        AuthorizeAction::ResetMfaTotp { login_token, otp } => {
-            let auth_data = check_auth(&state.pool, &login_token)
-                .await
-                .map_err(Error::new)?;
+        AuthorizeAction::ResetMfaTotp { otp, .. } => {
+            let auth_data = ctx.auth_data()?;
 
             let mut tx = state.pool.begin().await.map_err(Error::new)?;
 
@@ -286,6 +332,8 @@ pub async fn authorize(
             let (result, _discrepancy) = thotp::verify_totp(&otp, &secret, 0).unwrap();
 
             if !result {
+                log_login_event(state, &auth_data.user_id, LoginEvent::MfaFailure, meta).await;
+
                 return Err(Error {
                     status: StatusCode::BAD_REQUEST,
                     message: "Invalid OTP Entered".to_string(),
@@ -314,9 +362,7 @@ pub async fn authorize(
             Ok((StatusCode::NO_CONTENT, "").into_response())
         }
         AuthorizeAction::ActivateSession { login_token, otp } => {
-            let auth_data = check_auth_insecure(&state.pool, &login_token)
-                .await
-                .map_err(Error::new)?;
+            let auth_data = ctx.auth_data()?;
 
             if auth_data.state != "pending" {
                 return Err(Error {
@@ -348,6 +394,8 @@ pub async fn authorize(
             let (result, _discrepancy) = thotp::verify_totp(&otp, &secret, 0).unwrap();
 
             if !result {
+                log_login_event(state, &auth_data.user_id, LoginEvent::MfaFailure, meta).await;
+
                 return Err(Error {
                     status: StatusCode::BAD_REQUEST,
                     message: "Invalid OTP entered".to_string(),
@@ -372,19 +420,25 @@ pub async fn authorize(
 
             tx.commit().await.map_err(Error::new)?;
 
+            log_login_event(state, &auth_data.user_id, LoginEvent::MfaSuccess, meta).await;
+
             Ok((StatusCode::NO_CONTENT, "").into_response())
         }
         AuthorizeAction::Logout { login_token } => {
             // Just delete the auth, no point in even erroring if it doesn't exist
-            let row = sqlx::query!(
-                "DELETE FROM staffpanel__authchain WHERE token = $1",
+            let rows = sqlx::query!(
+                "DELETE FROM staffpanel__authchain WHERE token = $1 RETURNING user_id",
                 login_token
             )
-            .execute(&state.pool)
+            .fetch_all(&state.pool)
             .await
             .map_err(Error::new)?;
 
-            Ok((StatusCode::OK, row.rows_affected().to_string()).into_response())
+            for row in &rows {
+                log_login_event(state, &row.user_id, LoginEvent::Logout, meta).await;
+            }
+
+            Ok((StatusCode::OK, rows.len().to_string()).into_response())
         }
     }
 }
@@ -1,16 +1,30 @@
-use crate::panelapi::auth::check_auth;
-use crate::panelapi::core::{AppState, Error};
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction};
 use crate::panelapi::types::analytics::BaseAnalytics;
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use futures_util::future::BoxFuture;
 
-pub async fn base_analytics(state: &AppState, login_token: String) -> Result<Response, Error> {
-    check_auth(&state.pool, &login_token)
-        .await
-        .map_err(Error::new)?;
+pub struct BaseAnalyticsQuery;
+
+impl PanelAction for BaseAnalyticsQuery {
+    fn name(&self) -> String {
+        "BaseAnalytics".to_string()
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(base_analytics(ctx))
+    }
+}
+
+async fn base_analytics(ctx: &ActionContext<'_>) -> Result<Response, Error> {
+    let state = ctx.state;
 
     let bot_counts = sqlx::query!("SELECT type, COUNT(*) FROM bots GROUP BY type")
         .fetch_all(&state.pool)
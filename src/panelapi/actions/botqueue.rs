@@ -0,0 +1,115 @@
+use crate::impls::dovewing::DovewingSource;
+use crate::impls::target_types::TargetType;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction};
+use crate::panelapi::types::bot_queue::QueuedBot;
+use crate::panelapi::types::entity::PartialBot;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+use std::collections::HashMap;
+
+pub struct BotQueueQuery;
+
+impl PanelAction for BotQueueQuery {
+    fn name(&self) -> String {
+        "BotQueue".to_string()
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(bot_queue(ctx))
+    }
+}
+
+async fn bot_queue(ctx: &ActionContext<'_>) -> Result<Response, Error> {
+    let state = ctx.state;
+
+    let priorities = crate::impls::queue::get_queue(&state.pool, true)
+        .await
+        .map_err(Error::new)?;
+
+    let bot_ids = priorities
+        .iter()
+        .map(|p| p.bot_id.clone())
+        .collect::<Vec<_>>();
+
+    let mut queue = sqlx::query!(
+        "SELECT bot_id, client_id, last_claimed, claimed_by, type, approval_note, short,
+        invite, approximate_votes, shards, library, invite_clicks, clicks, servers
+        FROM bots WHERE bot_id = ANY($1)",
+        &bot_ids
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(Error::new)?
+    .into_iter()
+    .map(|bot| (bot.bot_id.clone(), bot))
+    .collect::<HashMap<_, _>>();
+
+    let mut history = crate::impls::review::get_review_history(&state.pool, &bot_ids)
+        .await
+        .map_err(Error::new)?;
+
+    let mut pre_reviews = crate::impls::prereview::get_pre_reviews(&state.pool, &bot_ids)
+        .await
+        .map_err(Error::new)?;
+
+    let mut bots = Vec::new();
+
+    for priority in priorities {
+        // The bot may have been deleted between the two queries
+        let Some(bot) = queue.remove(&priority.bot_id) else {
+            continue;
+        };
+
+        let owners =
+            crate::impls::utils::get_entity_managers(TargetType::Bot, &bot.bot_id, &state.pool)
+                .await
+                .map_err(Error::new)?;
+
+        let user = crate::impls::dovewing::get_platform_user(
+            &state.pool,
+            DovewingSource::Discord(state.cache_http.clone()),
+            &bot.bot_id,
+        )
+        .await
+        .map_err(Error::new)?;
+
+        let review_history = history.remove(&bot.bot_id).unwrap_or_default();
+        let pre_review = pre_reviews.remove(&bot.bot_id);
+        let invite_analysis = crate::impls::invite::analyze(&bot.invite);
+
+        bots.push(QueuedBot {
+            bot: PartialBot {
+                bot_id: bot.bot_id,
+                client_id: bot.client_id,
+                user,
+                claimed_by: bot.claimed_by,
+                last_claimed: bot.last_claimed,
+                approval_note: bot.approval_note,
+                short: bot.short,
+                r#type: bot.r#type,
+                votes: bot.approximate_votes,
+                shards: bot.shards,
+                library: bot.library,
+                invite_clicks: bot.invite_clicks,
+                clicks: bot.clicks,
+                servers: bot.servers,
+                mentionable: owners.mentionables(),
+                invite: bot.invite,
+                review_history,
+            },
+            priority,
+            pre_review,
+            invite_analysis,
+        });
+    }
+
+    Ok((StatusCode::OK, Json(bots)).into_response())
+}
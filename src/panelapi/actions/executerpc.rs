@@ -0,0 +1,58 @@
+use crate::impls::target_types::TargetType;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction};
+use crate::rpc::core::{RPCHandle, RPCMethod, RPCSuccess};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use futures_util::future::BoxFuture;
+
+pub struct ExecuteRpcQuery {
+    /// Target Type
+    pub target_type: TargetType,
+    /// RPC Method
+    pub method: RPCMethod,
+}
+
+impl PanelAction for ExecuteRpcQuery {
+    fn name(&self) -> String {
+        format!("ExecuteRpc.{}", self.method)
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(execute_rpc(ctx, self.target_type, self.method))
+    }
+}
+
+/// Executes an RPC method
+///
+/// Permission checks are performed by the RPC method itself
+async fn execute_rpc(
+    ctx: &ActionContext<'_>,
+    target_type: TargetType,
+    method: RPCMethod,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    let resp = method
+        .handle(RPCHandle {
+            pool: state.pool.clone(),
+            cache_http: state.cache_http.clone(),
+            user_id: auth_data.user_id.clone(),
+            target_type,
+        })
+        .await;
+
+    match resp {
+        Ok(r) => match r {
+            RPCSuccess::NoContent => Ok((StatusCode::NO_CONTENT, "").into_response()),
+            RPCSuccess::Content(c) => Ok((StatusCode::OK, c).into_response()),
+        },
+        Err(e) => Ok((StatusCode::BAD_REQUEST, e.to_string()).into_response()),
+    }
+}
@@ -0,0 +1,96 @@
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::audit_log::AuditLogEntry;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+/// The maximum number of audit log entries to return at once
+const MAX_ENTRIES: i64 = 100;
+
+pub struct GetAuditLogQuery {
+    /// Only return entries created by this user
+    pub user_id: Option<String>,
+    /// Only return entries whose action starts with this
+    pub action: Option<String>,
+    /// Only return entries for this entity type
+    pub entity_type: Option<String>,
+    /// Only return entries affecting this entity ID
+    pub entity_id: Option<String>,
+    /// The number of entries to skip
+    pub offset: Option<i64>,
+}
+
+impl PanelAction for GetAuditLogQuery {
+    fn name(&self) -> String {
+        "GetAuditLog".to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        Some(RequiredPerm::new("audit_log.view", "view the audit log"))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(get_audit_log(ctx, *self))
+    }
+}
+
+async fn get_audit_log(
+    ctx: &ActionContext<'_>,
+    query: GetAuditLogQuery,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+
+    let offset = query.offset.unwrap_or(0);
+
+    if offset < 0 {
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            "Offset cannot be negative".to_string(),
+        )
+            .into_response());
+    }
+
+    let rows = sqlx::query!(
+        "SELECT id, user_id, action, entity_type, entity_ids, data, before, after, diff, created_at FROM staffpanel__audit_log
+        WHERE ($1::text IS NULL OR user_id = $1)
+        AND ($2::text IS NULL OR starts_with(action, $2))
+        AND ($3::text IS NULL OR entity_type = $3)
+        AND ($4::text IS NULL OR $4 = ANY(entity_ids))
+        ORDER BY created_at DESC LIMIT $5 OFFSET $6",
+        query.user_id,
+        query.action,
+        query.entity_type,
+        query.entity_id,
+        MAX_ENTRIES,
+        offset
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(Error::new)?;
+
+    let mut entries = Vec::new();
+
+    for row in rows {
+        entries.push(AuditLogEntry {
+            id: row.id.to_string(),
+            user_id: row.user_id,
+            action: row.action,
+            entity_type: row.entity_type,
+            entity_ids: row.entity_ids,
+            data: row.data,
+            before: row.before,
+            after: row.after,
+            diff: row.diff,
+            created_at: row.created_at,
+        });
+    }
+
+    Ok((StatusCode::OK, Json(entries)).into_response())
+}
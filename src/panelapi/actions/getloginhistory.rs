@@ -0,0 +1,74 @@
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction};
+use crate::panelapi::types::login_history::LoginHistoryEntry;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+/// The maximum number of login history entries to return
+const MAX_ENTRIES: i64 = 250;
+
+pub struct GetLoginHistoryQuery {
+    /// User ID to fetch the login history of, defaults to the current user
+    pub user_id: Option<String>,
+}
+
+impl PanelAction for GetLoginHistoryQuery {
+    fn name(&self) -> String {
+        "GetLoginHistory".to_string()
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(get_login_history(ctx, self.user_id))
+    }
+}
+
+async fn get_login_history(
+    ctx: &ActionContext<'_>,
+    user_id: Option<String>,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    let user_id = user_id.unwrap_or_else(|| auth_data.user_id.clone());
+
+    // Staff members can always view their own login history
+    if user_id != auth_data.user_id && !ctx.has_perm("login_history.view") {
+        return Ok((
+            StatusCode::FORBIDDEN,
+            "You do not have permission to view the login history of other staff members [login_history.view]"
+                .to_string(),
+        )
+            .into_response());
+    }
+
+    let rows = sqlx::query!(
+        "SELECT id, user_id, event, ip, user_agent, created_at FROM staffpanel__login_history WHERE user_id = $1 ORDER BY created_at DESC LIMIT $2",
+        user_id,
+        MAX_ENTRIES
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(Error::new)?;
+
+    let mut entries = Vec::new();
+
+    for row in rows {
+        entries.push(LoginHistoryEntry {
+            id: row.id.to_string(),
+            user_id: row.user_id,
+            event: row.event,
+            ip: row.ip,
+            user_agent: row.user_agent,
+            created_at: row.created_at,
+        });
+    }
+
+    Ok((StatusCode::OK, Json(entries)).into_response())
+}
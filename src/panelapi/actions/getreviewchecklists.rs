@@ -0,0 +1,72 @@
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+/// The maximum number of review checklists to return at once
+const MAX_ENTRIES: i64 = 100;
+
+pub struct GetReviewChecklistsQuery {
+    /// Only return checklists of this bot
+    pub bot_id: Option<String>,
+    /// Only return checklists filled in by this reviewer
+    pub user_id: Option<String>,
+    /// Only return checklists where this item was not passed
+    pub failed_item: Option<String>,
+    /// The number of checklists to skip
+    pub offset: Option<i64>,
+}
+
+impl PanelAction for GetReviewChecklistsQuery {
+    fn name(&self) -> String {
+        "GetReviewChecklists".to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        Some(RequiredPerm::new(
+            "review_checklists.view",
+            "view review checklists",
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(get_review_checklists(ctx, *self))
+    }
+}
+
+async fn get_review_checklists(
+    ctx: &ActionContext<'_>,
+    query: GetReviewChecklistsQuery,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+
+    let offset = query.offset.unwrap_or(0);
+
+    if offset < 0 {
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            "Offset cannot be negative".to_string(),
+        )
+            .into_response());
+    }
+
+    let checklists = crate::impls::review::get_checklists(
+        &state.pool,
+        query.bot_id.as_deref(),
+        query.user_id.as_deref(),
+        query.failed_item.as_deref(),
+        MAX_ENTRIES,
+        offset,
+    )
+    .await
+    .map_err(Error::new)?;
+
+    Ok((StatusCode::OK, Json(checklists)).into_response())
+}
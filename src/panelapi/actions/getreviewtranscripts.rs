@@ -0,0 +1,69 @@
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+/// The maximum number of review transcripts to return at once
+const MAX_ENTRIES: i64 = 100;
+
+pub struct GetReviewTranscriptsQuery {
+    /// Only return transcripts of this bot
+    pub bot_id: Option<String>,
+    /// Only return transcripts of this reviewer
+    pub user_id: Option<String>,
+    /// The number of transcripts to skip
+    pub offset: Option<i64>,
+}
+
+impl PanelAction for GetReviewTranscriptsQuery {
+    fn name(&self) -> String {
+        "GetReviewTranscripts".to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        Some(RequiredPerm::new(
+            "review_transcripts.view",
+            "view review transcripts",
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(get_review_transcripts(ctx, *self))
+    }
+}
+
+async fn get_review_transcripts(
+    ctx: &ActionContext<'_>,
+    query: GetReviewTranscriptsQuery,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+
+    let offset = query.offset.unwrap_or(0);
+
+    if offset < 0 {
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            "Offset cannot be negative".to_string(),
+        )
+            .into_response());
+    }
+
+    let transcripts = crate::impls::sandbox::get_transcripts(
+        &state.pool,
+        query.bot_id.as_deref(),
+        query.user_id.as_deref(),
+        MAX_ENTRIES,
+        offset,
+    )
+    .await
+    .map_err(Error::new)?;
+
+    Ok((StatusCode::OK, Json(transcripts)).into_response())
+}
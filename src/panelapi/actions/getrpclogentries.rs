@@ -0,0 +1,54 @@
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::rpclogs::RPCLogEntry;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+pub struct GetRpcLogEntriesQuery;
+
+impl PanelAction for GetRpcLogEntriesQuery {
+    fn name(&self) -> String {
+        "GetRpcLogEntries".to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        Some(RequiredPerm::new("rpc_logs.view", "view rpc logs"))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(get_rpc_log_entries(ctx))
+    }
+}
+
+async fn get_rpc_log_entries(ctx: &ActionContext<'_>) -> Result<Response, Error> {
+    let state = ctx.state;
+
+    let entries = sqlx::query!(
+        "SELECT id, user_id, method, data, state, created_at FROM rpc_logs ORDER BY created_at DESC"
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(Error::new)?;
+
+    let mut rpc_log = vec![];
+
+    for entry in entries {
+        rpc_log.push(RPCLogEntry {
+            id: entry.id.to_string(),
+            user_id: entry.user_id,
+            method: entry.method,
+            data: entry.data,
+            state: entry.state,
+            created_at: entry.created_at,
+        });
+    }
+
+    Ok((StatusCode::OK, Json(rpc_log)).into_response())
+}
@@ -0,0 +1,55 @@
+use std::str::FromStr;
+
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction};
+use crate::panelapi::types::rpc::RPCWebAction;
+use crate::rpc::core::RPCMethod;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+use strum::VariantNames;
+
+pub struct GetRpcMethodsQuery {
+    /// Whether or not to filter RPC methods to those the user has access to
+    pub filtered: bool,
+}
+
+impl PanelAction for GetRpcMethodsQuery {
+    fn name(&self) -> String {
+        "GetRpcMethods".to_string()
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(get_rpc_methods(ctx, self.filtered))
+    }
+}
+
+async fn get_rpc_methods(ctx: &ActionContext<'_>, filtered: bool) -> Result<Response, Error> {
+    let mut rpc_methods = Vec::new();
+
+    for method in RPCMethod::VARIANTS {
+        let variant = RPCMethod::from_str(method).map_err(Error::new)?;
+
+        if filtered && !ctx.has_perm(&format!("rpc.{}", variant)) {
+            continue;
+        }
+
+        let action = RPCWebAction {
+            id: method.to_string(),
+            label: variant.label(),
+            description: variant.description(),
+            supported_target_types: variant.supported_target_types(),
+            fields: variant.method_fields(),
+        };
+
+        rpc_methods.push(action);
+    }
+
+    Ok((StatusCode::OK, Json(rpc_methods)).into_response())
+}
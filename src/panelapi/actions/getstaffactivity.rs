@@ -0,0 +1,71 @@
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+/// The maximum window activity can be computed over, in days
+const MAX_WINDOW_DAYS: i32 = 365;
+
+pub struct GetStaffActivityQuery {
+    /// Only return the activity of this staff member
+    pub user_id: Option<String>,
+    /// The number of days to compute activity over, defaults to the configured window
+    pub window_days: Option<i32>,
+}
+
+impl PanelAction for GetStaffActivityQuery {
+    fn name(&self) -> String {
+        "GetStaffActivity".to_string()
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(get_staff_activity(ctx, *self))
+    }
+}
+
+async fn get_staff_activity(
+    ctx: &ActionContext<'_>,
+    query: GetStaffActivityQuery,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    // Staff members can always view their own activity
+    if query.user_id.as_ref() != Some(&auth_data.user_id) && !ctx.has_perm("staff_activity.view") {
+        return Ok((
+            StatusCode::FORBIDDEN,
+            "You do not have permission to view the activity of other staff members [staff_activity.view]"
+                .to_string(),
+        )
+            .into_response());
+    }
+
+    let window_days = query
+        .window_days
+        .unwrap_or(crate::config::CONFIG.activity.window_days);
+
+    if window_days <= 0 || window_days > MAX_WINDOW_DAYS {
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            format!("Window must be between 1 and {} days", MAX_WINDOW_DAYS),
+        )
+            .into_response());
+    }
+
+    let mut activity = crate::impls::activity::get_staff_activity(&state.pool, window_days)
+        .await
+        .map_err(Error::new)?;
+
+    if let Some(user_id) = query.user_id {
+        activity.retain(|a| a.user_id == user_id);
+    }
+
+    Ok((StatusCode::OK, Json(activity)).into_response())
+}
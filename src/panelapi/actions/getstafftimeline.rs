@@ -0,0 +1,52 @@
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+pub struct GetStaffTimelineQuery {
+    /// The staff member to return the timeline of, defaults to the current user
+    pub user_id: Option<String>,
+}
+
+impl PanelAction for GetStaffTimelineQuery {
+    fn name(&self) -> String {
+        "GetStaffTimeline".to_string()
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(get_staff_timeline(ctx, *self))
+    }
+}
+
+async fn get_staff_timeline(
+    ctx: &ActionContext<'_>,
+    query: GetStaffTimelineQuery,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    let user_id = query.user_id.unwrap_or_else(|| auth_data.user_id.clone());
+
+    // Staff members can always view their own timeline
+    if user_id != auth_data.user_id && !ctx.has_perm("staff_members.history") {
+        return Ok((
+            StatusCode::FORBIDDEN,
+            "You do not have permission to view the timeline of other staff members [staff_members.history]"
+                .to_string(),
+        )
+            .into_response());
+    }
+
+    let timeline = crate::impls::timeline::get_timeline(&state.pool, &user_id)
+        .await
+        .map_err(Error::new)?;
+
+    Ok((StatusCode::OK, Json(timeline)).into_response())
+}
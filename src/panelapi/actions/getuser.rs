@@ -1,20 +1,33 @@
 use crate::impls::dovewing::{get_platform_user, DovewingSource};
-use crate::panelapi::auth::check_auth;
-use crate::panelapi::core::{AppState, Error};
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction};
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use futures_util::future::BoxFuture;
 
-pub async fn get_user(
-    state: &AppState,
-    login_token: String,
-    user_id: String,
-) -> Result<Response, Error> {
-    check_auth(&state.pool, &login_token)
-        .await
-        .map_err(Error::new)?;
+pub struct GetUserQuery {
+    /// User ID to fetch details for
+    pub user_id: String,
+}
+
+impl PanelAction for GetUserQuery {
+    fn name(&self) -> String {
+        "GetUser".to_string()
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(get_user(ctx, self.user_id))
+    }
+}
+
+async fn get_user(ctx: &ActionContext<'_>, user_id: String) -> Result<Response, Error> {
+    let state = ctx.state;
 
     let user = get_platform_user(
         &state.pool,
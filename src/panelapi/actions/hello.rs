@@ -1,28 +1,45 @@
 use std::str::FromStr;
 
 use crate::impls::target_types::TargetType;
-use crate::panelapi::auth::{check_auth, get_staff_member};
-use crate::panelapi::core::{AppState, Error};
+use crate::panelapi::auth::get_staff_member;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction};
 use crate::panelapi::types::webcore::{CoreConstants, Hello, InstanceConfig, PanelServers};
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use futures_util::future::BoxFuture;
 use strum::VariantNames;
 
 const HELLO_VERSION: u16 = 5;
 
-pub async fn hello(
-    state: &AppState,
-    // Login token
-    login_token: String,
-    // Authorize protocol version, should be `AUTH_VERSION`
+pub struct HelloQuery {
+    /// Hello protocol version, should be `HELLO_VERSION`
+    pub version: u16,
+}
+
+impl PanelAction for HelloQuery {
+    fn name(&self) -> String {
+        "Hello".to_string()
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(hello(ctx, self.version))
+    }
+}
+
+async fn hello(
+    ctx: &ActionContext<'_>,
+    // Hello protocol version, should be `HELLO_VERSION`
     version: u16,
 ) -> Result<Response, Error> {
-    let auth_data = check_auth(&state.pool, &login_token)
-        .await
-        .map_err(Error::new)?;
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?.clone();
 
     if version != HELLO_VERSION {
         return Ok((StatusCode::BAD_REQUEST, "Invalid version".to_string()).into_response());
@@ -69,7 +86,19 @@ pub async fn hello(
                     main: crate::config::CONFIG.servers.main.to_string(),
                     staff: crate::config::CONFIG.servers.staff.to_string(),
                     testing: crate::config::CONFIG.servers.testing.to_string(),
+                    aliases: crate::config::CONFIG
+                        .servers
+                        .alias_names()
+                        .into_iter()
+                        .filter_map(|alias| {
+                            crate::config::CONFIG
+                                .servers
+                                .get_by_alias(&alias)
+                                .map(|id| (alias, id.to_string()))
+                        })
+                        .collect(),
                 },
+                review_checklist: crate::config::CONFIG.review.checklist.clone(),
             },
             target_types,
         }
@@ -1,10 +1,33 @@
 pub mod authorize;
 pub mod baseanalytics;
+pub mod botqueue;
+pub mod executerpc;
+pub mod getauditlog;
+pub mod getloginhistory;
+pub mod getreviewchecklists;
+pub mod getreviewtranscripts;
+pub mod getrpclogentries;
+pub mod getrpcmethods;
+pub mod getstaffactivity;
+pub mod getstafftimeline;
 pub mod getuser;
 pub mod hello;
 pub mod searchentitys;
+pub mod simulatepermissions;
+pub mod updateblog;
+pub mod updatebotwhitelist;
 pub mod updatepartners;
+pub mod updatereviewtemplates;
+pub mod updateshopcoupons;
 pub mod updateshopholds;
+pub mod updateshopitembenefits;
+pub mod updateshopitems;
+pub mod updatestaffapplications;
+pub mod updatestaffdisciplinaries;
+pub mod updatestaffdisciplinarytype;
+pub mod updatestaffloas;
 pub mod updatestaffmembers;
+pub mod updatestaffonboardings;
 pub mod updatestaffposition;
+pub mod updatestaffresyncholds;
 pub mod updatevotecredittiers;
@@ -1,24 +1,42 @@
 use crate::impls::dovewing::{get_platform_user, DovewingSource};
 use crate::impls::target_types::TargetType;
 use crate::impls::utils::get_entity_managers;
-use crate::panelapi::auth::check_auth;
-use crate::panelapi::core::{AppState, Error};
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction};
 use crate::panelapi::types::entity::{PartialBot, PartialEntity, PartialServer};
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use futures_util::future::BoxFuture;
 
-pub async fn search_entitys(
-    state: &AppState,
-    login_token: String,
+pub struct SearchEntitysQuery {
+    /// Target type
+    pub target_type: TargetType,
+    /// Query
+    pub query: String,
+}
+
+impl PanelAction for SearchEntitysQuery {
+    fn name(&self) -> String {
+        "SearchEntitys".to_string()
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(search_entitys(ctx, self.target_type, self.query))
+    }
+}
+
+async fn search_entitys(
+    ctx: &ActionContext<'_>,
     target_type: TargetType,
     query: String,
 ) -> Result<Response, Error> {
-    check_auth(&state.pool, &login_token)
-        .await
-        .map_err(Error::new)?;
+    let state = ctx.state;
 
     match target_type {
         TargetType::Bot => {
@@ -36,6 +54,13 @@ pub async fn search_entitys(
         .await
         .map_err(Error::new)?;
 
+            let mut history = crate::impls::review::get_review_history(
+                &state.pool,
+                &queue.iter().map(|b| b.bot_id.clone()).collect::<Vec<_>>(),
+            )
+            .await
+            .map_err(Error::new)?;
+
             let mut bots = Vec::new();
 
             for bot in queue {
@@ -51,6 +76,8 @@ pub async fn search_entitys(
                 .await
                 .map_err(Error::new)?;
 
+                let review_history = history.remove(&bot.bot_id).unwrap_or_default();
+
                 bots.push(PartialEntity::Bot(PartialBot {
                     bot_id: bot.bot_id,
                     client_id: bot.client_id,
@@ -68,6 +95,7 @@ pub async fn search_entitys(
                     short: bot.short,
                     mentionable: owners.mentionables(),
                     invite: bot.invite,
+                    review_history,
                 }));
             }
 
@@ -0,0 +1,202 @@
+use crate::impls::permissions::{self, DisciplinaryLimits};
+use crate::impls::utils::get_user_perms;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::permission_simulation::{
+    PermissionSimulation, PermissionSource, SimulatedDisciplinary, SimulatedPosition,
+};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+use kittycat::perms::{PartialStaffPosition, Permission, StaffPermissions};
+
+pub struct SimulatePermissionsQuery {
+    /// The staff member to simulate, anything not given below is taken from them
+    pub user_id: Option<String>,
+    /// The positions to simulate
+    pub positions: Option<Vec<SimulatedPosition>>,
+    /// The permission overrides to simulate
+    pub perm_overrides: Option<Vec<String>>,
+    /// The active disciplinary actions to simulate
+    pub disciplinaries: Option<Vec<SimulatedDisciplinary>>,
+}
+
+impl PanelAction for SimulatePermissionsQuery {
+    fn name(&self) -> String {
+        "SimulatePermissions".to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        Some(RequiredPerm::new(
+            "arcadia.simulate_perms",
+            "simulate staff permissions",
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(simulate_permissions(ctx, *self))
+    }
+}
+
+async fn simulate_permissions(
+    ctx: &ActionContext<'_>,
+    query: SimulatePermissionsQuery,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+
+    if query.user_id.is_none()
+        && query.positions.is_none()
+        && query.perm_overrides.is_none()
+        && query.disciplinaries.is_none()
+    {
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            "Either a user ID or positions, permission overrides or disciplinaries must be given"
+                .to_string(),
+        )
+            .into_response());
+    }
+
+    let (mut sp, mut disciplinaries, current_perms) = match query.user_id {
+        Some(ref user_id) => {
+            let is_staff = sqlx::query!(
+                "SELECT COUNT(*) FROM staff_members WHERE user_id = $1",
+                user_id
+            )
+            .fetch_one(&state.pool)
+            .await
+            .map_err(Error::new)?
+            .count
+            .unwrap_or(0)
+                > 0;
+
+            if !is_staff {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "User is not a staff member".to_string(),
+                )
+                    .into_response());
+            }
+
+            let sp = get_user_perms(&state.pool, user_id)
+                .await
+                .map_err(Error::new)?;
+
+            let disciplinaries = permissions::get_active_disciplinaries(&state.pool, user_id)
+                .await
+                .map_err(Error::new)?;
+
+            // Not cached, the simulation should compare against what the member has right now
+            let current_perms = permissions::get_staff_permissions(&state.pool, user_id)
+                .await
+                .map_err(Error::new)?
+                .resolve()
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<String>>();
+
+            (sp, disciplinaries, Some(current_perms))
+        }
+        None => (
+            StaffPermissions {
+                user_positions: Vec::new(),
+                perm_overrides: Vec::new(),
+            },
+            Vec::new(),
+            None,
+        ),
+    };
+
+    if let Some(positions) = query.positions {
+        sp.user_positions = positions
+            .into_iter()
+            .map(|p| PartialStaffPosition {
+                id: p.id,
+                index: p.index,
+                perms: p
+                    .perms
+                    .iter()
+                    .map(|x| Permission::from_string(x))
+                    .collect::<Vec<Permission>>(),
+            })
+            .collect();
+    }
+
+    if let Some(perm_overrides) = query.perm_overrides {
+        sp.perm_overrides = perm_overrides
+            .iter()
+            .map(|x| Permission::from_string(x))
+            .collect::<Vec<Permission>>();
+    }
+
+    if let Some(simulated) = query.disciplinaries {
+        disciplinaries = simulated
+            .into_iter()
+            .map(|d| DisciplinaryLimits {
+                id: d.id,
+                perm_limits: d.perm_limits,
+                additory: d.additory,
+            })
+            .collect();
+    }
+
+    let removed_positions = permissions::apply_disciplinaries(&mut sp, &disciplinaries);
+
+    let steps = permissions::explain(&sp);
+
+    let resolved_perms = sp
+        .resolve()
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<String>>();
+
+    // The last step applying a resolved permission is the one it came from
+    let sources = resolved_perms
+        .iter()
+        .map(|perm| PermissionSource {
+            perm: perm.clone(),
+            source: steps
+                .iter()
+                .rev()
+                .find(|s| &s.perm == perm)
+                .map(|s| s.source.clone())
+                .unwrap_or_default(),
+        })
+        .collect::<Vec<PermissionSource>>();
+
+    let (added, removed) = match current_perms {
+        Some(ref current) => (
+            resolved_perms
+                .iter()
+                .filter(|p| !current.contains(p))
+                .cloned()
+                .collect(),
+            current
+                .iter()
+                .filter(|p| !resolved_perms.contains(p))
+                .cloned()
+                .collect(),
+        ),
+        None => (Vec::new(), Vec::new()),
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(PermissionSimulation {
+            resolved_perms,
+            sources,
+            steps,
+            removed_positions,
+            current_perms,
+            added,
+            removed,
+        }),
+    )
+        .into_response())
+}
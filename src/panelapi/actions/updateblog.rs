@@ -0,0 +1,183 @@
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::blog::{BlogAction, BlogPost};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+impl PanelAction for BlogAction {
+    fn name(&self) -> String {
+        match self {
+            BlogAction::ListEntries => "UpdateBlog.ListEntries",
+            BlogAction::CreateEntry { .. } => "UpdateBlog.CreateEntry",
+            BlogAction::UpdateEntry { .. } => "UpdateBlog.UpdateEntry",
+            BlogAction::DeleteEntry { .. } => "UpdateBlog.DeleteEntry",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            BlogAction::CreateEntry { .. } => Some(RequiredPerm::new(
+                "blog.create_entry",
+                "create blog entries",
+            )),
+            BlogAction::UpdateEntry { .. } => Some(RequiredPerm::new(
+                "blog.update_entry",
+                "update blog entries",
+            )),
+            BlogAction::DeleteEntry { .. } => Some(RequiredPerm::new(
+                "blog.delete_entry",
+                "delete blog entries",
+            )),
+            _ => None,
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let (id_column, entity_ids) = match self {
+            BlogAction::CreateEntry { slug, .. } => ("slug", vec![slug.clone()]),
+            BlogAction::UpdateEntry { itag, .. } => ("itag", vec![itag.clone()]),
+            BlogAction::DeleteEntry { itag } => ("itag", vec![itag.clone()]),
+            _ => return None,
+        };
+
+        Some(AuditTarget::new("blogs", id_column, entity_ids, self))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_blog(ctx, *self))
+    }
+}
+
+async fn update_blog(ctx: &ActionContext<'_>, action: BlogAction) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    match action {
+        BlogAction::ListEntries => {
+            let rows = sqlx::query!(
+                "SELECT itag, slug, title, description, user_id, content, created_at, draft, tags FROM blogs ORDER BY created_at DESC"
+            )
+            .fetch_all(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            let mut entries = Vec::new();
+
+            for row in rows {
+                entries.push(BlogPost {
+                    itag: row.itag.hyphenated().to_string(),
+                    slug: row.slug,
+                    title: row.title,
+                    description: row.description,
+                    user_id: row.user_id,
+                    tags: row.tags,
+                    content: row.content,
+                    created_at: row.created_at,
+                    draft: row.draft,
+                });
+            }
+
+            Ok((StatusCode::OK, Json(entries)).into_response())
+        }
+        BlogAction::CreateEntry {
+            slug,
+            title,
+            description,
+            content,
+            tags,
+        } => {
+            // Insert entry
+            sqlx::query!(
+                "INSERT INTO blogs (slug, title, description, content, tags, user_id) VALUES ($1, $2, $3, $4, $5, $6)",
+                slug,
+                title,
+                description,
+                content,
+                &tags,
+                &auth_data.user_id,
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        BlogAction::UpdateEntry {
+            itag,
+            slug,
+            title,
+            description,
+            content,
+            tags,
+            draft,
+        } => {
+            let uuid = sqlx::types::uuid::Uuid::parse_str(&itag).map_err(Error::new)?;
+
+            // Check if entry already exists with same vesion
+            if sqlx::query!("SELECT COUNT(*) FROM blogs WHERE itag = $1", uuid)
+                .fetch_one(&state.pool)
+                .await
+                .map_err(Error::new)?
+                .count
+                .unwrap_or(0)
+                == 0
+            {
+                return Ok(
+                    (StatusCode::BAD_REQUEST, "Entry does not exist".to_string()).into_response(),
+                );
+            }
+
+            // Update entry
+            sqlx::query!(
+                "UPDATE blogs SET slug = $2, title = $3, description = $4, content = $5, tags = $6, draft = $7 WHERE itag = $1",
+                uuid,
+                slug,
+                title,
+                description,
+                content,
+                &tags,
+                draft
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        BlogAction::DeleteEntry { itag } => {
+            // Check if entry already exists with same vesion
+            let uuid = sqlx::types::uuid::Uuid::parse_str(&itag).map_err(Error::new)?;
+            if sqlx::query!("SELECT COUNT(*) FROM blogs WHERE itag = $1", uuid)
+                .fetch_one(&state.pool)
+                .await
+                .map_err(Error::new)?
+                .count
+                .unwrap_or(0)
+                == 0
+            {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Entry with same id does not already exist".to_string(),
+                )
+                    .into_response());
+            }
+
+            // Delete entry
+            sqlx::query!("DELETE FROM blogs WHERE itag = $1", uuid)
+                .execute(&state.pool)
+                .await
+                .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+    }
+}
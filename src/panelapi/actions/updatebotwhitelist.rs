@@ -0,0 +1,169 @@
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::bot_whitelist::{BotWhitelist, BotWhitelistAction};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+impl PanelAction for BotWhitelistAction {
+    fn name(&self) -> String {
+        match self {
+            BotWhitelistAction::List => "UpdateBotWhitelist.List",
+            BotWhitelistAction::Add { .. } => "UpdateBotWhitelist.Add",
+            BotWhitelistAction::Edit { .. } => "UpdateBotWhitelist.Edit",
+            BotWhitelistAction::Delete { .. } => "UpdateBotWhitelist.Delete",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            BotWhitelistAction::Add { .. } => Some(RequiredPerm::new(
+                "bot_whitelist.create",
+                "add to the bot whitelist",
+            )),
+            BotWhitelistAction::Edit { .. } => Some(RequiredPerm::new(
+                "bot_whitelist.update",
+                "update bot whitelist",
+            )),
+            BotWhitelistAction::Delete { .. } => Some(RequiredPerm::new(
+                "bot_whitelist.delete",
+                "delete bot whitelist entries",
+            )),
+            _ => None,
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let (id_column, entity_ids) = match self {
+            BotWhitelistAction::Add { bot_id, .. } => ("bot_id", vec![bot_id.clone()]),
+            BotWhitelistAction::Edit { bot_id, .. } => ("bot_id", vec![bot_id.clone()]),
+            BotWhitelistAction::Delete { bot_id } => ("bot_id", vec![bot_id.clone()]),
+            _ => return None,
+        };
+
+        Some(AuditTarget::new(
+            "bot_whitelist",
+            id_column,
+            entity_ids,
+            self,
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_bot_whitelist(ctx, *self))
+    }
+}
+
+async fn update_bot_whitelist(
+    ctx: &ActionContext<'_>,
+    action: BotWhitelistAction,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    match action {
+        BotWhitelistAction::List => {
+            let rows = sqlx::query!(
+                "SELECT bot_id, user_id, reason, created_at FROM bot_whitelist ORDER BY created_at DESC"
+            )
+            .fetch_all(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            let mut entries = Vec::new();
+
+            for row in rows {
+                entries.push(BotWhitelist {
+                    bot_id: row.bot_id,
+                    user_id: row.user_id,
+                    reason: row.reason,
+                    created_at: row.created_at,
+                });
+            }
+
+            Ok((StatusCode::OK, Json(entries)).into_response())
+        }
+        BotWhitelistAction::Add { bot_id, reason } => {
+            // Insert entry
+            sqlx::query!(
+                "INSERT INTO bot_whitelist (user_id, bot_id, reason) VALUES ($1, $2, $3)",
+                &auth_data.user_id,
+                bot_id,
+                reason,
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        BotWhitelistAction::Edit { bot_id, reason } => {
+            // Check if entry already exists with same vesion
+            if sqlx::query!(
+                "SELECT COUNT(*) FROM bot_whitelist WHERE bot_id = $1",
+                bot_id
+            )
+            .fetch_one(&state.pool)
+            .await
+            .map_err(Error::new)?
+            .count
+            .unwrap_or(0)
+                == 0
+            {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Entry with same id does not already exist".to_string(),
+                )
+                    .into_response());
+            }
+
+            // Update entry
+            sqlx::query!(
+                "UPDATE bot_whitelist SET reason = $1 WHERE bot_id = $2",
+                reason,
+                bot_id,
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        BotWhitelistAction::Delete { bot_id } => {
+            // Check if entry already exists with same vesion
+            if sqlx::query!(
+                "SELECT COUNT(*) FROM bot_whitelist WHERE bot_id = $1",
+                bot_id
+            )
+            .fetch_one(&state.pool)
+            .await
+            .map_err(Error::new)?
+            .count
+            .unwrap_or(0)
+                == 0
+            {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Entry with same id does not already exist".to_string(),
+                )
+                    .into_response());
+            }
+
+            // Delete entry
+            sqlx::query!("DELETE FROM bot_whitelist WHERE bot_id = $1", bot_id)
+                .execute(&state.pool)
+                .await
+                .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+    }
+}
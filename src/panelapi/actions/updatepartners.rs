@@ -1,6 +1,6 @@
-use crate::impls::utils::get_user_perms;
-use crate::panelapi::auth::check_auth;
-use crate::panelapi::core::{AppState, Error};
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
 use crate::panelapi::types::partners::{
     CreatePartner, Partner, PartnerAction, PartnerType, Partners,
 };
@@ -9,22 +9,59 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use kittycat::perms;
+use futures_util::future::BoxFuture;
 use sqlx::PgPool;
 
-pub async fn update_partners(
-    state: &AppState,
-    login_token: String,
+impl PanelAction for PartnerAction {
+    fn name(&self) -> String {
+        match self {
+            PartnerAction::List => "UpdatePartners.List",
+            PartnerAction::Create { .. } => "UpdatePartners.Create",
+            PartnerAction::Update { .. } => "UpdatePartners.Update",
+            PartnerAction::Delete { .. } => "UpdatePartners.Delete",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            PartnerAction::Create { .. } => {
+                Some(RequiredPerm::new("partners.create", "create partners"))
+            }
+            PartnerAction::Update { .. } => {
+                Some(RequiredPerm::new("partners.update", "update partners"))
+            }
+            PartnerAction::Delete { .. } => {
+                Some(RequiredPerm::new("partners.delete", "delete partners"))
+            }
+            _ => None,
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let (id_column, entity_ids) = match self {
+            PartnerAction::Create { partner } => ("id", vec![partner.id.clone()]),
+            PartnerAction::Update { partner } => ("id", vec![partner.id.clone()]),
+            PartnerAction::Delete { id } => ("id", vec![id.clone()]),
+            _ => return None,
+        };
+
+        Some(AuditTarget::new("partners", id_column, entity_ids, self))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_partners(ctx, *self))
+    }
+}
+
+async fn update_partners(
+    ctx: &ActionContext<'_>,
     action: PartnerAction,
 ) -> Result<Response, Error> {
-    let auth_data = check_auth(&state.pool, &login_token)
-        .await
-        .map_err(Error::new)?;
-
-    let user_perms = get_user_perms(&state.pool, &auth_data.user_id)
-        .await
-        .map_err(Error::new)?
-        .resolve();
+    let state = ctx.state;
 
     async fn parse_partner(pool: &PgPool, partner: &CreatePartner) -> Result<(), crate::Error> {
         // Check if partner type exists
@@ -154,14 +191,6 @@ pub async fn update_partners(
                 .into_response())
         }
         PartnerAction::Create { partner } => {
-            if !perms::has_perm(&user_perms, &"partners.create".into()) {
-                return Ok((
-                    StatusCode::FORBIDDEN,
-                    "You do not have permission to create partners [partners.create]".to_string(),
-                )
-                    .into_response());
-            }
-
             // Check if partner already exists
             let partner_exists = sqlx::query!("SELECT id FROM partners WHERE id = $1", partner.id)
                 .fetch_optional(&state.pool)
@@ -199,14 +228,6 @@ pub async fn update_partners(
             Ok((StatusCode::NO_CONTENT, "").into_response())
         }
         PartnerAction::Update { partner } => {
-            if !perms::has_perm(&user_perms, &"partners.update".into()) {
-                return Ok((
-                    StatusCode::FORBIDDEN,
-                    "You do not have permission to update partners [partners.update]".to_string(),
-                )
-                    .into_response());
-            }
-
             // Check if partner already exists
             let partner_exists = sqlx::query!("SELECT id FROM partners WHERE id = $1", partner.id)
                 .fetch_optional(&state.pool)
@@ -244,14 +265,6 @@ pub async fn update_partners(
             Ok((StatusCode::NO_CONTENT, "").into_response())
         }
         PartnerAction::Delete { id } => {
-            if !perms::has_perm(&user_perms, &"partners.delete".into()) {
-                return Ok((
-                    StatusCode::FORBIDDEN,
-                    "You do not have permission to delete partners [partners.delete]".to_string(),
-                )
-                    .into_response());
-            }
-
             // Check if partner exists
             let partner_exists = sqlx::query!("SELECT id FROM partners WHERE id = $1", id)
                 .fetch_optional(&state.pool)
@@ -0,0 +1,243 @@
+use crate::impls::review;
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::review_templates::ReviewReasonTemplateAction;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+impl PanelAction for ReviewReasonTemplateAction {
+    fn name(&self) -> String {
+        match self {
+            ReviewReasonTemplateAction::List => "UpdateReviewReasonTemplates.List",
+            ReviewReasonTemplateAction::Create { .. } => "UpdateReviewReasonTemplates.Create",
+            ReviewReasonTemplateAction::Edit { .. } => "UpdateReviewReasonTemplates.Edit",
+            ReviewReasonTemplateAction::Delete { .. } => "UpdateReviewReasonTemplates.Delete",
+            ReviewReasonTemplateAction::Render { .. } => "UpdateReviewReasonTemplates.Render",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            ReviewReasonTemplateAction::Create { .. } => Some(RequiredPerm::new(
+                "review_reason_templates.create",
+                "create reason templates",
+            )),
+            ReviewReasonTemplateAction::Edit { .. } => Some(RequiredPerm::new(
+                "review_reason_templates.update",
+                "update reason templates",
+            )),
+            ReviewReasonTemplateAction::Delete { .. } => Some(RequiredPerm::new(
+                "review_reason_templates.delete",
+                "delete reason templates",
+            )),
+            _ => None,
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let entity_ids = match self {
+            // The ID of a new template is generated by the database
+            ReviewReasonTemplateAction::Create { .. } => Vec::new(),
+            ReviewReasonTemplateAction::Edit { id, .. } => vec![id.clone()],
+            ReviewReasonTemplateAction::Delete { id } => vec![id.clone()],
+            _ => return None,
+        };
+
+        Some(AuditTarget::new(
+            "review_reason_templates",
+            "id",
+            entity_ids,
+            self,
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_review_reason_templates(ctx, *self))
+    }
+}
+
+async fn update_review_reason_templates(
+    ctx: &ActionContext<'_>,
+    action: ReviewReasonTemplateAction,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    match action {
+        ReviewReasonTemplateAction::List => {
+            let templates = review::get_reason_templates(&state.pool, None)
+                .await
+                .map_err(Error::new)?;
+
+            Ok((StatusCode::OK, Json(templates)).into_response())
+        }
+        ReviewReasonTemplateAction::Create {
+            name,
+            content,
+            methods,
+        } => {
+            if let Err(e) = review::validate_reason_template(&name, &content, &methods) {
+                return Ok((StatusCode::BAD_REQUEST, e.to_string()).into_response());
+            }
+
+            if sqlx::query!(
+                "SELECT COUNT(*) FROM review_reason_templates WHERE LOWER(name) = LOWER($1)",
+                name
+            )
+            .fetch_one(&state.pool)
+            .await
+            .map_err(Error::new)?
+            .count
+            .unwrap_or(0)
+                > 0
+            {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "A template with this name already exists".to_string(),
+                )
+                    .into_response());
+            }
+
+            let rec = sqlx::query!(
+                "INSERT INTO review_reason_templates (name, content, methods, created_by, updated_by) VALUES ($1, $2, $3, $4, $4) RETURNING id",
+                name,
+                content,
+                &methods,
+                &auth_data.user_id
+            )
+            .fetch_one(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            ctx.created(rec.id);
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        ReviewReasonTemplateAction::Edit {
+            id,
+            name,
+            content,
+            methods,
+        } => {
+            let Ok(uuid) = sqlx::types::uuid::Uuid::parse_str(&id) else {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Template does not exist".to_string(),
+                )
+                    .into_response());
+            };
+
+            if let Err(e) = review::validate_reason_template(&name, &content, &methods) {
+                return Ok((StatusCode::BAD_REQUEST, e.to_string()).into_response());
+            }
+
+            if sqlx::query!(
+                "SELECT COUNT(*) FROM review_reason_templates WHERE LOWER(name) = LOWER($1) AND id != $2",
+                name,
+                uuid
+            )
+            .fetch_one(&state.pool)
+            .await
+            .map_err(Error::new)?
+            .count
+            .unwrap_or(0)
+                > 0
+            {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "A template with this name already exists".to_string(),
+                )
+                    .into_response());
+            }
+
+            let res = sqlx::query!(
+                "UPDATE review_reason_templates SET name = $1, content = $2, methods = $3, updated_by = $4, last_updated = NOW() WHERE id = $5",
+                name,
+                content,
+                &methods,
+                &auth_data.user_id,
+                uuid
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            if res.rows_affected() == 0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Template does not exist".to_string(),
+                )
+                    .into_response());
+            }
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        ReviewReasonTemplateAction::Delete { id } => {
+            let Ok(uuid) = sqlx::types::uuid::Uuid::parse_str(&id) else {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Template does not exist".to_string(),
+                )
+                    .into_response());
+            };
+
+            let res = sqlx::query!("DELETE FROM review_reason_templates WHERE id = $1", uuid)
+                .execute(&state.pool)
+                .await
+                .map_err(Error::new)?;
+
+            if res.rows_affected() == 0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Template does not exist".to_string(),
+                )
+                    .into_response());
+            }
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        ReviewReasonTemplateAction::Render { ids, bot_id, note } => {
+            let all = review::get_reason_templates(&state.pool, None)
+                .await
+                .map_err(Error::new)?;
+
+            let mut templates = Vec::new();
+
+            for id in ids {
+                let Some(template) = all.iter().find(|t| t.id == id) else {
+                    return Ok((
+                        StatusCode::BAD_REQUEST,
+                        format!("Template {} does not exist", id),
+                    )
+                        .into_response());
+                };
+
+                templates.push(template.clone());
+            }
+
+            let reason = match review::render_reason(
+                &state.pool,
+                &state.cache_http,
+                &templates,
+                &bot_id,
+                note.as_deref(),
+            )
+            .await
+            {
+                Ok(reason) => reason,
+                Err(e) => return Ok((StatusCode::BAD_REQUEST, e.to_string()).into_response()),
+            };
+
+            Ok((StatusCode::OK, Json(reason)).into_response())
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::shop_items::{ShopCoupon, ShopCouponAction};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+impl PanelAction for ShopCouponAction {
+    fn name(&self) -> String {
+        match self {
+            ShopCouponAction::List => "UpdateShopCoupons.List",
+            ShopCouponAction::Create { .. } => "UpdateShopCoupons.Create",
+            ShopCouponAction::Edit { .. } => "UpdateShopCoupons.Edit",
+            ShopCouponAction::Delete { .. } => "UpdateShopCoupons.Delete",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            ShopCouponAction::List => {
+                Some(RequiredPerm::new("shop_coupons.list", "list shop coupons"))
+            }
+            ShopCouponAction::Create { .. } => Some(RequiredPerm::new(
+                "shop_coupons.create",
+                "create shop coupons",
+            )),
+            ShopCouponAction::Edit { .. } => Some(RequiredPerm::new(
+                "shop_coupons.update",
+                "update shop coupons",
+            )),
+            ShopCouponAction::Delete { .. } => Some(RequiredPerm::new(
+                "shop_coupons.delete",
+                "delete shop coupons",
+            )),
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let (id_column, entity_ids) = match self {
+            ShopCouponAction::Create { id, .. } => ("id", vec![id.clone()]),
+            ShopCouponAction::Edit { id, .. } => ("id", vec![id.clone()]),
+            ShopCouponAction::Delete { id } => ("id", vec![id.clone()]),
+            _ => return None,
+        };
+
+        Some(AuditTarget::new(
+            "shop_coupons",
+            id_column,
+            entity_ids,
+            self,
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_shop_coupons(ctx, *self))
+    }
+}
+
+async fn update_shop_coupons(
+    ctx: &ActionContext<'_>,
+    action: ShopCouponAction,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    match action {
+        ShopCouponAction::List => {
+            let rows = sqlx::query!(
+                "SELECT id, code, public, max_uses, created_at, created_by, last_updated, updated_by, reuse_wait_duration, expiry, applicable_items, cents, requirements, allowed_users, usable, target_types FROM shop_coupons ORDER BY created_at DESC"
+            )
+            .fetch_all(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            let mut entries = Vec::new();
+
+            for row in rows {
+                entries.push(ShopCoupon {
+                    id: row.id,
+                    code: row.code,
+                    public: row.public,
+                    max_uses: row.max_uses,
+                    created_at: row.created_at,
+                    created_by: row.created_by,
+                    last_updated: row.last_updated,
+                    updated_by: row.updated_by,
+                    reuse_wait_duration: row.reuse_wait_duration,
+                    expiry: row.expiry,
+                    applicable_items: row.applicable_items,
+                    cents: row.cents,
+                    requirements: row.requirements,
+                    allowed_users: row.allowed_users,
+                    usable: row.usable,
+                    target_types: row.target_types,
+                });
+            }
+
+            Ok((StatusCode::OK, Json(entries)).into_response())
+        }
+        ShopCouponAction::Create {
+            id,
+            code,
+            public,
+            max_uses,
+            reuse_wait_duration,
+            expiry,
+            applicable_items,
+            cents,
+            requirements,
+            allowed_users,
+            usable,
+            target_types,
+        } => {
+            if max_uses.unwrap_or_default() <= 0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Max uses must be greater than 0".to_string(),
+                )
+                    .into_response());
+            }
+
+            if reuse_wait_duration.unwrap_or_default() <= 0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Reuse wait duration must be greater than 0".to_string(),
+                )
+                    .into_response());
+            }
+
+            if expiry.unwrap_or_default() <= 0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Expiry must be greater than 0".to_string(),
+                )
+                    .into_response());
+            }
+
+            if cents.unwrap_or_default() < 0.0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Cents cannot be lower than 0".to_string(),
+                )
+                    .into_response());
+            }
+
+            for item in &applicable_items {
+                let rows = sqlx::query!("SELECT COUNT(*) FROM shop_items WHERE id = $1", item)
+                    .fetch_one(&state.pool)
+                    .await
+                    .map_err(Error::new)?;
+
+                if rows.count.unwrap_or(0) == 0 {
+                    return Ok((
+                        StatusCode::BAD_REQUEST,
+                        format!("Item {:#?} does not exist", item),
+                    )
+                        .into_response());
+                }
+            }
+
+            // Insert entry
+            sqlx::query!(
+                "INSERT INTO shop_coupons (id, code, public, max_uses, created_by, updated_by, reuse_wait_duration, expiry, applicable_items, cents, requirements, allowed_users, usable, target_types) VALUES ($1, $2, $3, $4, $5, $5, $6, $7, $8, $9, $10, $11, $12, $13)",
+                id,
+                code,
+                public,
+                max_uses,
+                &auth_data.user_id,
+                reuse_wait_duration,
+                expiry,
+                &applicable_items,
+                cents,
+                &requirements,
+                &allowed_users,
+                usable,
+                &target_types
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        ShopCouponAction::Edit {
+            id,
+            code,
+            public,
+            max_uses,
+            reuse_wait_duration,
+            expiry,
+            applicable_items,
+            cents,
+            requirements,
+            allowed_users,
+            usable,
+            target_types,
+        } => {
+            if max_uses.unwrap_or_default() <= 0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Max uses must be greater than 0".to_string(),
+                )
+                    .into_response());
+            }
+
+            if reuse_wait_duration.unwrap_or_default() <= 0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Reuse wait duration must be greater than 0".to_string(),
+                )
+                    .into_response());
+            }
+
+            if expiry.unwrap_or_default() <= 0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Expiry must be greater than 0".to_string(),
+                )
+                    .into_response());
+            }
+
+            if cents.unwrap_or_default() < 0.0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Cents cannot be lower than 0".to_string(),
+                )
+                    .into_response());
+            }
+
+            for item in &applicable_items {
+                let rows = sqlx::query!("SELECT COUNT(*) FROM shop_items WHERE id = $1", item)
+                    .fetch_one(&state.pool)
+                    .await
+                    .map_err(Error::new)?;
+
+                if rows.count.unwrap_or(0) == 0 {
+                    return Ok((
+                        StatusCode::BAD_REQUEST,
+                        format!("Item {:#?} does not exist", item),
+                    )
+                        .into_response());
+                }
+            }
+
+            // Insert entry
+            sqlx::query!(
+                "UPDATE shop_coupons SET code = $1, public = $2, max_uses = $3, reuse_wait_duration = $4, expiry = $5, applicable_items = $6, cents = $7, requirements = $8, updated_by = $9, last_updated = NOW(), allowed_users = $10, usable = $11, target_types = $12 WHERE id = $13",
+                code,
+                public,
+                max_uses,
+                reuse_wait_duration,
+                expiry,
+                &applicable_items,
+                cents,
+                &requirements,
+                &auth_data.user_id,
+                &allowed_users,
+                usable,
+                &target_types,
+                id,
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        ShopCouponAction::Delete { id } => {
+            // Check if entry already exists with same vesion
+            if sqlx::query!("SELECT COUNT(*) FROM shop_coupons WHERE id = $1", id)
+                .fetch_one(&state.pool)
+                .await
+                .map_err(Error::new)?
+                .count
+                .unwrap_or(0)
+                == 0
+            {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Entry with same id does not already exist".to_string(),
+                )
+                    .into_response());
+            }
+
+            // Delete entry
+            sqlx::query!("DELETE FROM shop_coupons WHERE id = $1", id)
+                .execute(&state.pool)
+                .await
+                .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+    }
+}
@@ -1,27 +1,65 @@
-use crate::impls::utils::get_user_perms;
-use crate::panelapi::auth::check_auth;
-use crate::panelapi::core::{AppState, Error};
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
 use crate::panelapi::types::shop_items::{ShopHold, ShopHoldAction};
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
-use kittycat::perms;
+use futures_util::future::BoxFuture;
+
+impl PanelAction for ShopHoldAction {
+    fn name(&self) -> String {
+        match self {
+            ShopHoldAction::List => "UpdateShopHolds.List",
+            ShopHoldAction::Create { .. } => "UpdateShopHolds.Create",
+            ShopHoldAction::Edit { .. } => "UpdateShopHolds.Edit",
+            ShopHoldAction::Delete { .. } => "UpdateShopHolds.Delete",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            ShopHoldAction::Create { .. } => {
+                Some(RequiredPerm::new("shop_holds.create", "create shop holds"))
+            }
+            ShopHoldAction::Edit { .. } => {
+                Some(RequiredPerm::new("shop_holds.update", "update shop holds"))
+            }
+            ShopHoldAction::Delete { .. } => {
+                Some(RequiredPerm::new("shop_holds.delete", "delete shop holds"))
+            }
+            _ => None,
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let (id_column, entity_ids) = match self {
+            // The ID of a new shop hold is generated by the database and reported by the handler
+            ShopHoldAction::Create { .. } => ("id", Vec::new()),
+            ShopHoldAction::Edit { id, .. } => ("id", vec![id.to_string()]),
+            ShopHoldAction::Delete { id } => ("id", vec![id.to_string()]),
+            _ => return None,
+        };
 
-pub async fn update_shop_holds(
-    state: &AppState,
-    login_token: String,
+        Some(AuditTarget::new("shop_holds", id_column, entity_ids, self))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_shop_holds(ctx, *self))
+    }
+}
+
+async fn update_shop_holds(
+    ctx: &ActionContext<'_>,
     action: ShopHoldAction,
 ) -> Result<Response, Error> {
-    let auth_data = check_auth(&state.pool, &login_token)
-        .await
-        .map_err(Error::new)?;
-
-    let user_perms = get_user_perms(&state.pool, &auth_data.user_id)
-        .await
-        .map_err(Error::new)?
-        .resolve();
+    let state = ctx.state;
 
     match action {
         ShopHoldAction::List => {
@@ -62,15 +100,6 @@ pub async fn update_shop_holds(
             item,
             duration,
         } => {
-            if !perms::has_perm(&user_perms, &"shop_holds.create".into()) {
-                return Ok((
-                    StatusCode::FORBIDDEN,
-                    "You do not have permission to create shop holds [shop_holds.create]"
-                        .to_string(),
-                )
-                    .into_response());
-            }
-
             let item_exists = sqlx::query!("SELECT COUNT(*) FROM shop_items WHERE id = $1", item,)
                 .fetch_one(&state.pool)
                 .await
@@ -104,19 +133,21 @@ pub async fn update_shop_holds(
             });
 
             let mut tx = state.pool.begin().await.map_err(Error::new)?;
-            sqlx::query!(
-                "INSERT INTO shop_holds (target_id, target_type, item, duration) VALUES ($1, $2, $3, $4)",
+            let rec = sqlx::query!(
+                "INSERT INTO shop_holds (target_id, target_type, item, duration) VALUES ($1, $2, $3, $4) RETURNING id",
                 target_id,
                 target_type,
                 item,
                 duration,
             )
-            .execute(&mut *tx)
+            .fetch_one(&mut *tx)
             .await
             .map_err(Error::new)?;
 
             tx.commit().await.map_err(Error::new)?;
 
+            ctx.created(rec.id);
+
             Ok((StatusCode::NO_CONTENT, "").into_response())
         }
         ShopHoldAction::Edit {
@@ -126,15 +157,6 @@ pub async fn update_shop_holds(
             item,
             duration,
         } => {
-            if !perms::has_perm(&user_perms, &"shop_holds.update".into()) {
-                return Ok((
-                    StatusCode::FORBIDDEN,
-                    "You do not have permission to update shop holds [shop_holds.update]"
-                        .to_string(),
-                )
-                    .into_response());
-            }
-
             // Check if entry already exists with same id
             if sqlx::query!("SELECT COUNT(*) FROM shop_holds WHERE id = $1", id)
                 .fetch_one(&state.pool)
@@ -198,15 +220,6 @@ pub async fn update_shop_holds(
             Ok((StatusCode::NO_CONTENT, "").into_response())
         }
         ShopHoldAction::Delete { id } => {
-            if !perms::has_perm(&user_perms, &"shop_holds.delete".into()) {
-                return Ok((
-                    StatusCode::FORBIDDEN,
-                    "You do not have permission to delete shop holds [shop_holds.delete]"
-                        .to_string(),
-                )
-                    .into_response());
-            }
-
             // Check if entry already exists
             if sqlx::query!("SELECT COUNT(*) FROM shop_holds WHERE id = $1", id)
                 .fetch_one(&state.pool)
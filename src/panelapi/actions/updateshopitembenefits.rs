@@ -0,0 +1,202 @@
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::shop_items::{ShopItemBenefit, ShopItemBenefitAction};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+impl PanelAction for ShopItemBenefitAction {
+    fn name(&self) -> String {
+        match self {
+            ShopItemBenefitAction::List => "UpdateShopItemBenefits.List",
+            ShopItemBenefitAction::Create { .. } => "UpdateShopItemBenefits.Create",
+            ShopItemBenefitAction::Edit { .. } => "UpdateShopItemBenefits.Edit",
+            ShopItemBenefitAction::Delete { .. } => "UpdateShopItemBenefits.Delete",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            ShopItemBenefitAction::Create { .. } => Some(RequiredPerm::new(
+                "shop_item_benefits.create",
+                "create shop item benefits",
+            )),
+            ShopItemBenefitAction::Edit { .. } => Some(RequiredPerm::new(
+                "shop_item_benefits.update",
+                "update shop item benefits",
+            )),
+            ShopItemBenefitAction::Delete { .. } => Some(RequiredPerm::new(
+                "shop_item_benefits.delete",
+                "delete shop item benefits",
+            )),
+            _ => None,
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let (id_column, entity_ids) = match self {
+            ShopItemBenefitAction::Create { id, .. } => ("id", vec![id.clone()]),
+            ShopItemBenefitAction::Edit { id, .. } => ("id", vec![id.clone()]),
+            ShopItemBenefitAction::Delete { id } => ("id", vec![id.clone()]),
+            _ => return None,
+        };
+
+        Some(AuditTarget::new(
+            "shop_item_benefits",
+            id_column,
+            entity_ids,
+            self,
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_shop_item_benefits(ctx, *self))
+    }
+}
+
+async fn update_shop_item_benefits(
+    ctx: &ActionContext<'_>,
+    action: ShopItemBenefitAction,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    match action {
+        ShopItemBenefitAction::List => {
+            let rows = sqlx::query!(
+                "SELECT id, name, description, target_types, created_at, created_by, last_updated, updated_by FROM shop_item_benefits ORDER BY created_at DESC"
+            )
+            .fetch_all(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            let mut entries = Vec::new();
+
+            for row in rows {
+                entries.push(ShopItemBenefit {
+                    id: row.id,
+                    name: row.name,
+                    description: row.description,
+                    target_types: row.target_types,
+                    created_at: row.created_at,
+                    created_by: row.created_by,
+                    last_updated: row.last_updated,
+                    updated_by: row.updated_by,
+                });
+            }
+
+            Ok((StatusCode::OK, Json(entries)).into_response())
+        }
+        ShopItemBenefitAction::Create {
+            id,
+            name,
+            description,
+            target_types,
+        } => {
+            // Insert entry
+            sqlx::query!(
+                "INSERT INTO shop_item_benefits (id, name, description, target_types, created_by, updated_by) VALUES ($1, $2, $3, $4, $5, $6)",
+                id,
+                name,
+                description,
+                &target_types,
+                &auth_data.user_id,
+                &auth_data.user_id,
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        ShopItemBenefitAction::Edit {
+            id,
+            name,
+            description,
+            target_types,
+        } => {
+            // Check if entry already exists with same id
+            if sqlx::query!("SELECT COUNT(*) FROM shop_item_benefits WHERE id = $1", id)
+                .fetch_one(&state.pool)
+                .await
+                .map_err(Error::new)?
+                .count
+                .unwrap_or(0)
+                == 0
+            {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Entry with same id does not already exist".to_string(),
+                )
+                    .into_response());
+            }
+
+            // Update entry
+            sqlx::query!(
+                "UPDATE shop_item_benefits SET name = $1, description = $2, last_updated = NOW(), updated_by = $3, target_types = $4 WHERE id = $5",
+                name,
+                description,
+                &auth_data.user_id,
+                &target_types,
+                id,
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        ShopItemBenefitAction::Delete { id } => {
+            // Check if entry already exists with same vesion
+            if sqlx::query!("SELECT COUNT(*) FROM shop_item_benefits WHERE id = $1", id)
+                .fetch_one(&state.pool)
+                .await
+                .map_err(Error::new)?
+                .count
+                .unwrap_or(0)
+                == 0
+            {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Entry with same id does not already exist".to_string(),
+                )
+                    .into_response());
+            }
+
+            // Check for shop items with this benefit
+            if sqlx::query!(
+                "SELECT COUNT(*) FROM shop_items WHERE $1 = ANY(benefits)",
+                id
+            )
+            .fetch_one(&state.pool)
+            .await
+            .map_err(Error::new)?
+            .count
+            .unwrap_or(0)
+                > 0
+            {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Cannot delete benefit as it is used by shop items".to_string(),
+                )
+                    .into_response());
+            }
+
+            // Delete entry
+            sqlx::query!("DELETE FROM shop_item_benefits WHERE id = $1", id)
+                .execute(&state.pool)
+                .await
+                .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+    }
+}
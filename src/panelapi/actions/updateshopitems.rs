@@ -0,0 +1,257 @@
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::shop_items::{ShopItem, ShopItemAction};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+impl PanelAction for ShopItemAction {
+    fn name(&self) -> String {
+        match self {
+            ShopItemAction::List => "UpdateShopItems.List",
+            ShopItemAction::Create { .. } => "UpdateShopItems.Create",
+            ShopItemAction::Edit { .. } => "UpdateShopItems.Edit",
+            ShopItemAction::Delete { .. } => "UpdateShopItems.Delete",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            ShopItemAction::Create { .. } => {
+                Some(RequiredPerm::new("shop_items.create", "create shop items"))
+            }
+            ShopItemAction::Edit { .. } => {
+                Some(RequiredPerm::new("shop_items.update", "update shop items"))
+            }
+            ShopItemAction::Delete { .. } => {
+                Some(RequiredPerm::new("shop_items.delete", "delete shop items"))
+            }
+            _ => None,
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let (id_column, entity_ids) = match self {
+            ShopItemAction::Create { id, .. } => ("id", vec![id.clone()]),
+            ShopItemAction::Edit { id, .. } => ("id", vec![id.clone()]),
+            ShopItemAction::Delete { id } => ("id", vec![id.clone()]),
+            _ => return None,
+        };
+
+        Some(AuditTarget::new("shop_items", id_column, entity_ids, self))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_shop_items(ctx, *self))
+    }
+}
+
+async fn update_shop_items(
+    ctx: &ActionContext<'_>,
+    action: ShopItemAction,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    match action {
+        ShopItemAction::List => {
+            let rows = sqlx::query!(
+                "SELECT id, name, cents, target_types, benefits, created_at, last_updated, created_by, updated_by, duration, description FROM shop_items ORDER BY created_at DESC"
+            )
+            .fetch_all(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            let mut entries = Vec::new();
+
+            for row in rows {
+                entries.push(ShopItem {
+                    id: row.id,
+                    name: row.name,
+                    cents: row.cents,
+                    target_types: row.target_types,
+                    benefits: row.benefits,
+                    created_at: row.created_at,
+                    last_updated: row.last_updated,
+                    created_by: row.created_by,
+                    updated_by: row.updated_by,
+                    duration: row.duration,
+                    description: row.description,
+                });
+            }
+
+            Ok((StatusCode::OK, Json(entries)).into_response())
+        }
+        ShopItemAction::Create {
+            id,
+            name,
+            cents,
+            target_types,
+            benefits,
+            duration,
+            description,
+        } => {
+            if cents < 0.0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Cents cannot be lower than 0".to_string(),
+                )
+                    .into_response());
+            }
+
+            if duration < 0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Duration cannot be lower than 0".to_string(),
+                )
+                    .into_response());
+            }
+
+            for benefit in &benefits {
+                let rows = sqlx::query!(
+                    "SELECT COUNT(*) FROM shop_item_benefits WHERE id = $1",
+                    benefit
+                )
+                .fetch_one(&state.pool)
+                .await
+                .map_err(Error::new)?;
+
+                if rows.count.unwrap_or(0) == 0 {
+                    return Ok((
+                        StatusCode::BAD_REQUEST,
+                        format!("Benefit {} does not exist", benefit),
+                    )
+                        .into_response());
+                }
+            }
+
+            // Insert entry
+            sqlx::query!(
+                "INSERT INTO shop_items (id, name, cents, target_types, benefits, created_by, updated_by, duration, description) VALUES ($1, $2, $3, $4, $5, $6, $6, $7, $8)",
+                id,
+                name,
+                cents,
+                &target_types,
+                &benefits,
+                &auth_data.user_id,
+                duration,
+                description,
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        ShopItemAction::Edit {
+            id,
+            name,
+            cents,
+            target_types,
+            benefits,
+            duration,
+            description,
+        } => {
+            if cents < 0.0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Cents cannot be lower than 0".to_string(),
+                )
+                    .into_response());
+            }
+
+            if duration < 0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Duration cannot be lower than 0".to_string(),
+                )
+                    .into_response());
+            }
+
+            for benefit in &benefits {
+                let rows = sqlx::query!(
+                    "SELECT COUNT(*) FROM shop_item_benefits WHERE id = $1",
+                    benefit
+                )
+                .fetch_one(&state.pool)
+                .await
+                .map_err(Error::new)?;
+
+                if rows.count.unwrap_or(0) == 0 {
+                    return Ok((
+                        StatusCode::BAD_REQUEST,
+                        format!("Benefit {} does not exist", benefit),
+                    )
+                        .into_response());
+                }
+            }
+
+            // Check if entry already exists with same id
+            if sqlx::query!("SELECT COUNT(*) FROM shop_items WHERE id = $1", id)
+                .fetch_one(&state.pool)
+                .await
+                .map_err(Error::new)?
+                .count
+                .unwrap_or(0)
+                == 0
+            {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Entry with same id does not already exist".to_string(),
+                )
+                    .into_response());
+            }
+
+            // Update entry
+            sqlx::query!(
+                "UPDATE shop_items SET name = $1, cents = $2, target_types = $3, benefits = $4, last_updated = NOW(), updated_by = $5, duration = $6, description = $7 WHERE id = $8",
+                name,
+                cents,
+                &target_types,
+                &benefits,
+                &auth_data.user_id,
+                duration,
+                description,
+                id,
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        ShopItemAction::Delete { id } => {
+            // Check if entry already exists with same vesion
+            if sqlx::query!("SELECT COUNT(*) FROM shop_items WHERE id = $1", id)
+                .fetch_one(&state.pool)
+                .await
+                .map_err(Error::new)?
+                .count
+                .unwrap_or(0)
+                == 0
+            {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Entry with same id does not already exist".to_string(),
+                )
+                    .into_response());
+            }
+
+            // Delete entry
+            sqlx::query!("DELETE FROM shop_items WHERE id = $1", id)
+                .execute(&state.pool)
+                .await
+                .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::impls::applications;
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::staff_applications::{StaffApplicationAction, StaffApplicationState};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+impl PanelAction for StaffApplicationAction {
+    fn name(&self) -> String {
+        match self {
+            StaffApplicationAction::ListApplications { .. } => {
+                "UpdateStaffApplications.ListApplications"
+            }
+            StaffApplicationAction::ListPending => "UpdateStaffApplications.ListPending",
+            StaffApplicationAction::VoteApplication { .. } => {
+                "UpdateStaffApplications.VoteApplication"
+            }
+            StaffApplicationAction::CommentApplication { .. } => {
+                "UpdateStaffApplications.CommentApplication"
+            }
+            StaffApplicationAction::AcceptApplication { .. } => {
+                "UpdateStaffApplications.AcceptApplication"
+            }
+            StaffApplicationAction::RejectApplication { .. } => {
+                "UpdateStaffApplications.RejectApplication"
+            }
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            StaffApplicationAction::ListApplications { .. }
+            | StaffApplicationAction::ListPending => Some(RequiredPerm::new(
+                "staff_applications.view",
+                "view staff applications",
+            )),
+            StaffApplicationAction::VoteApplication { .. }
+            | StaffApplicationAction::CommentApplication { .. } => Some(RequiredPerm::new(
+                "staff_applications.vote",
+                "vote and comment on staff applications",
+            )),
+            StaffApplicationAction::AcceptApplication { .. }
+            | StaffApplicationAction::RejectApplication { .. } => Some(RequiredPerm::new(
+                "staff_applications.manage",
+                "accept or reject staff applications",
+            )),
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let entity_ids = match self {
+            StaffApplicationAction::VoteApplication { id, .. }
+            | StaffApplicationAction::CommentApplication { id, .. }
+            | StaffApplicationAction::AcceptApplication { id, .. }
+            | StaffApplicationAction::RejectApplication { id, .. } => vec![id.clone()],
+            _ => return None,
+        };
+
+        Some(AuditTarget::new(
+            "staff_applications",
+            "id",
+            entity_ids,
+            self,
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_staff_applications(ctx, *self))
+    }
+}
+
+async fn update_staff_applications(
+    ctx: &ActionContext<'_>,
+    action: StaffApplicationAction,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    match action {
+        StaffApplicationAction::ListApplications { user_id } => {
+            let applications = applications::get_applications(&state.pool, Some(&user_id), None)
+                .await
+                .map_err(Error::new)?;
+
+            Ok((StatusCode::OK, Json(applications)).into_response())
+        }
+        StaffApplicationAction::ListPending => {
+            let applications = applications::get_applications(
+                &state.pool,
+                None,
+                Some(StaffApplicationState::Pending),
+            )
+            .await
+            .map_err(Error::new)?;
+
+            Ok((StatusCode::OK, Json(applications)).into_response())
+        }
+        StaffApplicationAction::VoteApplication { id, approve } => {
+            applications::vote(&state.pool, &id, &auth_data.user_id, approve)
+                .await
+                .map_err(bad_request)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        StaffApplicationAction::CommentApplication { id, comment } => {
+            applications::comment(&state.pool, &id, &auth_data.user_id, comment)
+                .await
+                .map_err(bad_request)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        StaffApplicationAction::AcceptApplication { id, reason } => {
+            applications::accept(
+                &state.cache_http,
+                &state.pool,
+                &id,
+                &auth_data.user_id,
+                reason,
+            )
+            .await
+            .map_err(bad_request)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        StaffApplicationAction::RejectApplication { id, reason } => {
+            applications::reject(
+                &state.cache_http,
+                &state.pool,
+                &id,
+                &auth_data.user_id,
+                reason,
+            )
+            .await
+            .map_err(bad_request)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+    }
+}
+
+/// Application lifecycle errors are caused by the request (e.g. voting on an application that was already reviewed)
+fn bad_request(e: crate::Error) -> Error {
+    Error {
+        status: StatusCode::BAD_REQUEST,
+        message: e.to_string(),
+    }
+}
@@ -0,0 +1,716 @@
+use crate::impls::timeline;
+use crate::impls::utils::notify_staff;
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::auth::{get_staff_disciplinaries, get_staff_member};
+use crate::panelapi::core::{AppState, Error};
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::staff_disciplinary::{StaffDisciplinaryAction, StaffDisciplinaryState};
+use crate::panelapi::types::staff_timeline::StaffTimelineEventKind;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+use num_traits::ToPrimitive;
+use serde_json::json;
+use serenity::all::CreateEmbed;
+
+impl PanelAction for StaffDisciplinaryAction {
+    fn name(&self) -> String {
+        match self {
+            StaffDisciplinaryAction::ListDisciplinaries { .. } => {
+                "UpdateStaffDisciplinaries.ListDisciplinaries"
+            }
+            StaffDisciplinaryAction::ListPending => "UpdateStaffDisciplinaries.ListPending",
+            StaffDisciplinaryAction::IssueDisciplinary { .. } => {
+                "UpdateStaffDisciplinaries.IssueDisciplinary"
+            }
+            StaffDisciplinaryAction::ApproveDisciplinary { .. } => {
+                "UpdateStaffDisciplinaries.ApproveDisciplinary"
+            }
+            StaffDisciplinaryAction::DenyDisciplinary { .. } => {
+                "UpdateStaffDisciplinaries.DenyDisciplinary"
+            }
+            StaffDisciplinaryAction::RevokeDisciplinary { .. } => {
+                "UpdateStaffDisciplinaries.RevokeDisciplinary"
+            }
+            StaffDisciplinaryAction::ShortenDisciplinary { .. } => {
+                "UpdateStaffDisciplinaries.ShortenDisciplinary"
+            }
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            // Issuing needs no permission for self-assignable types, this is checked by the handler
+            StaffDisciplinaryAction::ListDisciplinaries { .. }
+            | StaffDisciplinaryAction::IssueDisciplinary { .. } => None,
+            StaffDisciplinaryAction::ListPending
+            | StaffDisciplinaryAction::ApproveDisciplinary { .. }
+            | StaffDisciplinaryAction::DenyDisciplinary { .. } => Some(RequiredPerm::new(
+                "staff_disciplinaries.approve",
+                "approve staff disciplinary actions",
+            )),
+            StaffDisciplinaryAction::RevokeDisciplinary { .. }
+            | StaffDisciplinaryAction::ShortenDisciplinary { .. } => Some(RequiredPerm::new(
+                "staff_disciplinaries.revoke",
+                "revoke staff disciplinary actions",
+            )),
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let entity_ids = match self {
+            // The ID of a new disciplinary action is generated by the database and reported by the handler
+            StaffDisciplinaryAction::IssueDisciplinary { .. } => Vec::new(),
+            StaffDisciplinaryAction::ApproveDisciplinary { id }
+            | StaffDisciplinaryAction::DenyDisciplinary { id, .. }
+            | StaffDisciplinaryAction::RevokeDisciplinary { id, .. }
+            | StaffDisciplinaryAction::ShortenDisciplinary { id, .. } => vec![id.clone()],
+            _ => return None,
+        };
+
+        Some(AuditTarget::new(
+            "staff_disciplinary",
+            "id",
+            entity_ids,
+            self,
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_staff_disciplinaries(ctx, *self))
+    }
+}
+
+async fn update_staff_disciplinaries(
+    ctx: &ActionContext<'_>,
+    action: StaffDisciplinaryAction,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    match action {
+        StaffDisciplinaryAction::ListDisciplinaries { user_id } => {
+            let user_id = user_id.unwrap_or_else(|| auth_data.user_id.clone());
+
+            // Staff members can always view their own disciplinary history
+            if user_id != auth_data.user_id && !ctx.has_perm("staff_disciplinaries.view") {
+                return Ok((
+                    StatusCode::FORBIDDEN,
+                    "You do not have permission to view the disciplinary history of other staff members [staff_disciplinaries.view]"
+                        .to_string(),
+                )
+                    .into_response());
+            }
+
+            let disciplinaries = get_staff_disciplinaries(&state.pool, &user_id, false)
+                .await
+                .map_err(Error::new)?;
+
+            Ok((StatusCode::OK, Json(disciplinaries)).into_response())
+        }
+        StaffDisciplinaryAction::ListPending => {
+            let users = sqlx::query!(
+                "SELECT DISTINCT user_id FROM staff_disciplinary WHERE state = $1",
+                StaffDisciplinaryState::Pending.to_string()
+            )
+            .fetch_all(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            let mut pending = Vec::new();
+
+            for user in users {
+                let disciplinaries = get_staff_disciplinaries(&state.pool, &user.user_id, false)
+                    .await
+                    .map_err(Error::new)?;
+
+                pending.extend(
+                    disciplinaries
+                        .into_iter()
+                        .filter(|d| d.state == StaffDisciplinaryState::Pending.to_string()),
+                );
+            }
+
+            Ok((StatusCode::OK, Json(pending)).into_response())
+        }
+        StaffDisciplinaryAction::IssueDisciplinary {
+            user_id,
+            type_id,
+            title,
+            description,
+            expiry,
+        } => {
+            if title.is_empty() {
+                return Ok(
+                    (StatusCode::BAD_REQUEST, "Title cannot be empty".to_string()).into_response(),
+                );
+            }
+
+            let Some(disc_type) = sqlx::query!(
+                "SELECT name, self_assignable, needs_approval, EXTRACT(epoch FROM max_expiry) AS max_expiry FROM staff_disciplinary_types WHERE id = $1",
+                type_id
+            )
+            .fetch_optional(&state.pool)
+            .await
+            .map_err(Error::new)?
+            else {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Disciplinary type does not exist".to_string(),
+                )
+                    .into_response());
+            };
+
+            let is_staff = sqlx::query!(
+                "SELECT COUNT(*) FROM staff_members WHERE user_id = $1",
+                user_id
+            )
+            .fetch_one(&state.pool)
+            .await
+            .map_err(Error::new)?
+            .count
+            .unwrap_or(0)
+                > 0;
+
+            if !is_staff {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "User is not a staff member".to_string(),
+                )
+                    .into_response());
+            }
+
+            if user_id == auth_data.user_id {
+                if !disc_type.self_assignable {
+                    return Ok((
+                        StatusCode::FORBIDDEN,
+                        "This disciplinary type cannot be self-assigned".to_string(),
+                    )
+                        .into_response());
+                }
+            } else {
+                if !ctx.has_perm("staff_disciplinaries.create") {
+                    return Ok((
+                        StatusCode::FORBIDDEN,
+                        "You do not have permission to issue staff disciplinary actions [staff_disciplinaries.create]"
+                            .to_string(),
+                    )
+                        .into_response());
+                }
+
+                if !is_higher_in_hierarchy(state, &auth_data.user_id, &user_id).await? {
+                    return Ok((
+                        StatusCode::FORBIDDEN,
+                        "You can only issue disciplinary actions to staff members lower in the hierarchy than you"
+                            .to_string(),
+                    )
+                        .into_response());
+                }
+            }
+
+            if let Some(expiry) = expiry {
+                if expiry <= 0 {
+                    return Ok((
+                        StatusCode::BAD_REQUEST,
+                        "Expiry must be greater than 0".to_string(),
+                    )
+                        .into_response());
+                }
+            }
+
+            if let Some(max_expiry) = disc_type.max_expiry.and_then(|d| d.to_i64()) {
+                match expiry {
+                    None => {
+                        return Ok((
+                            StatusCode::BAD_REQUEST,
+                            format!(
+                                "This disciplinary type has a maximum expiry of {} seconds, an expiry must be set",
+                                max_expiry
+                            ),
+                        )
+                            .into_response());
+                    }
+                    Some(expiry) if expiry > max_expiry => {
+                        return Ok((
+                            StatusCode::BAD_REQUEST,
+                            format!(
+                                "Expiry cannot be longer than the maximum expiry of this disciplinary type ({} seconds)",
+                                max_expiry
+                            ),
+                        )
+                            .into_response());
+                    }
+                    _ => {}
+                }
+            }
+
+            let disc_state = if disc_type.needs_approval {
+                StaffDisciplinaryState::Pending
+            } else {
+                StaffDisciplinaryState::Active
+            };
+
+            let mut tx = state.pool.begin().await.map_err(Error::new)?;
+
+            let rec = sqlx::query!(
+                "INSERT INTO staff_disciplinary (user_id, title, description, type, expiry, state, issued_by) VALUES ($1, $2, $3, $4, make_interval(secs => $5), $6, $7) RETURNING id, created_at",
+                user_id,
+                title,
+                description,
+                type_id,
+                expiry.map(|e| e as f64),
+                disc_state.to_string(),
+                auth_data.user_id
+            )
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(Error::new)?;
+
+            ctx.created(rec.id);
+
+            timeline::record(
+                &mut *tx,
+                &user_id,
+                StaffTimelineEventKind::Disciplinary,
+                Some(&auth_data.user_id),
+                format!("Disciplinary action ``{}`` issued ({})", title, disc_state),
+                json!({ "disciplinary_id": rec.id.hyphenated().to_string(), "state": disc_state }),
+            )
+            .await
+            .map_err(Error::new)?;
+
+            tx.commit().await.map_err(Error::new)?;
+
+            let embed = CreateEmbed::new()
+                .title(if disc_state == StaffDisciplinaryState::Pending {
+                    "Disciplinary Action Pending Approval"
+                } else {
+                    "Disciplinary Action Issued"
+                })
+                .description(format!(
+                    "<@{}> issued a disciplinary action to <@{}>",
+                    auth_data.user_id, user_id
+                ))
+                .field("Title", title, true)
+                .field("Type", disc_type.name, true)
+                .field("Expires", format_expiry(rec.created_at, expiry), true)
+                .field("Description", description, false)
+                .field("ID", rec.id.hyphenated().to_string(), false);
+
+            crate::impls::permissions::invalidate(&user_id).await;
+
+            notify_staff(&state.cache_http, &user_id, embed).await;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        StaffDisciplinaryAction::ApproveDisciplinary { id } => {
+            review_disciplinary(ctx, id, None).await
+        }
+        StaffDisciplinaryAction::DenyDisciplinary { id, reason } => {
+            review_disciplinary(ctx, id, Some(reason)).await
+        }
+        StaffDisciplinaryAction::RevokeDisciplinary { id, reason } => {
+            let Some(disc) = fetch_disciplinary(state, &id).await? else {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Disciplinary action does not exist".to_string(),
+                )
+                    .into_response());
+            };
+
+            if let Err(e) = check_in_effect(state, &disc, &auth_data.user_id).await? {
+                return Ok(e.into_response());
+            }
+
+            let mut tx = state.pool.begin().await.map_err(Error::new)?;
+
+            // The state is checked again in case the disciplinary action was changed concurrently
+            let res = sqlx::query!(
+                "UPDATE staff_disciplinary SET state = $1, revoked_by = $2, revoked_at = NOW(), revoke_reason = $3 WHERE id = $4 AND state = ANY($5)",
+                StaffDisciplinaryState::Revoked.to_string(),
+                auth_data.user_id,
+                reason,
+                disc.id,
+                &[
+                    StaffDisciplinaryState::Pending.to_string(),
+                    StaffDisciplinaryState::Active.to_string()
+                ]
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(Error::new)?;
+
+            if res.rows_affected() != 1 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Disciplinary action has already been denied or revoked".to_string(),
+                )
+                    .into_response());
+            }
+
+            timeline::record(
+                &mut *tx,
+                &disc.user_id,
+                StaffTimelineEventKind::Disciplinary,
+                Some(&auth_data.user_id),
+                format!("Disciplinary action ``{}`` revoked: {}", disc.title, reason),
+                json!({ "disciplinary_id": disc.id.hyphenated().to_string(), "state": StaffDisciplinaryState::Revoked }),
+            )
+            .await
+            .map_err(Error::new)?;
+
+            tx.commit().await.map_err(Error::new)?;
+
+            let embed = CreateEmbed::new()
+                .title("Disciplinary Action Revoked")
+                .description(format!(
+                    "<@{}> revoked a disciplinary action issued to <@{}>",
+                    auth_data.user_id, disc.user_id
+                ))
+                .field("Title", disc.title, true)
+                .field("ID", disc.id.hyphenated().to_string(), true)
+                .field("Reason", reason, false);
+
+            crate::impls::permissions::invalidate(&disc.user_id).await;
+
+            notify_staff(&state.cache_http, &disc.user_id, embed).await;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        StaffDisciplinaryAction::ShortenDisciplinary { id, expiry } => {
+            let Some(disc) = fetch_disciplinary(state, &id).await? else {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Disciplinary action does not exist".to_string(),
+                )
+                    .into_response());
+            };
+
+            if let Err(e) = check_in_effect(state, &disc, &auth_data.user_id).await? {
+                return Ok(e.into_response());
+            }
+
+            if expiry <= 0 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Expiry must be greater than 0".to_string(),
+                )
+                    .into_response());
+            }
+
+            if let Some(current_expiry) = disc.expiry {
+                if expiry >= current_expiry {
+                    return Ok((
+                        StatusCode::BAD_REQUEST,
+                        "The new expiry must be shorter than the current expiry".to_string(),
+                    )
+                        .into_response());
+                }
+            }
+
+            let mut tx = state.pool.begin().await.map_err(Error::new)?;
+
+            // The state is checked again in case the disciplinary action was changed concurrently
+            let res = sqlx::query!(
+                "UPDATE staff_disciplinary SET expiry = make_interval(secs => $1) WHERE id = $2 AND state = ANY($3)",
+                expiry as f64,
+                disc.id,
+                &[
+                    StaffDisciplinaryState::Pending.to_string(),
+                    StaffDisciplinaryState::Active.to_string()
+                ]
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(Error::new)?;
+
+            if res.rows_affected() != 1 {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Disciplinary action has already been denied or revoked".to_string(),
+                )
+                    .into_response());
+            }
+
+            timeline::record(
+                &mut *tx,
+                &disc.user_id,
+                StaffTimelineEventKind::Disciplinary,
+                Some(&auth_data.user_id),
+                format!("Disciplinary action ``{}`` shortened", disc.title),
+                json!({ "disciplinary_id": disc.id.hyphenated().to_string(), "expiry": expiry }),
+            )
+            .await
+            .map_err(Error::new)?;
+
+            tx.commit().await.map_err(Error::new)?;
+
+            let embed = CreateEmbed::new()
+                .title("Disciplinary Action Shortened")
+                .description(format!(
+                    "<@{}> shortened a disciplinary action issued to <@{}>",
+                    auth_data.user_id, disc.user_id
+                ))
+                .field("Title", disc.title, true)
+                .field("ID", disc.id.hyphenated().to_string(), true)
+                .field(
+                    "Previous Expiry",
+                    format_expiry(disc.created_at, disc.expiry),
+                    true,
+                )
+                .field(
+                    "New Expiry",
+                    format_expiry(disc.created_at, Some(expiry)),
+                    true,
+                );
+
+            crate::impls::permissions::invalidate(&disc.user_id).await;
+
+            notify_staff(&state.cache_http, &disc.user_id, embed).await;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+    }
+}
+
+/// Approves or (if a reason is given) denies a pending disciplinary action
+async fn review_disciplinary(
+    ctx: &ActionContext<'_>,
+    id: String,
+    deny_reason: Option<String>,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    let Some(disc) = fetch_disciplinary(state, &id).await? else {
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            "Disciplinary action does not exist".to_string(),
+        )
+            .into_response());
+    };
+
+    if disc.state != StaffDisciplinaryState::Pending.to_string() {
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            "Disciplinary action is not pending approval".to_string(),
+        )
+            .into_response());
+    }
+
+    if disc.issued_by.as_ref() == Some(&auth_data.user_id) {
+        return Ok((
+            StatusCode::FORBIDDEN,
+            "You cannot approve or deny a disciplinary action you issued".to_string(),
+        )
+            .into_response());
+    }
+
+    if disc.user_id == auth_data.user_id {
+        return Ok((
+            StatusCode::FORBIDDEN,
+            "You cannot approve or deny a disciplinary action issued to you".to_string(),
+        )
+            .into_response());
+    }
+
+    let new_state = if deny_reason.is_some() {
+        StaffDisciplinaryState::Denied
+    } else {
+        StaffDisciplinaryState::Active
+    };
+
+    let mut tx = state.pool.begin().await.map_err(Error::new)?;
+
+    // The state is checked again in case the disciplinary action was reviewed concurrently
+    let res = sqlx::query!(
+        "UPDATE staff_disciplinary SET state = $1, approved_by = $2, deny_reason = $3 WHERE id = $4 AND state = $5",
+        new_state.to_string(),
+        auth_data.user_id,
+        deny_reason,
+        disc.id,
+        StaffDisciplinaryState::Pending.to_string()
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(Error::new)?;
+
+    if res.rows_affected() != 1 {
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            "Disciplinary action is not pending approval".to_string(),
+        )
+            .into_response());
+    }
+
+    timeline::record(
+        &mut *tx,
+        &disc.user_id,
+        StaffTimelineEventKind::Disciplinary,
+        Some(&auth_data.user_id),
+        match deny_reason {
+            Some(ref reason) => {
+                format!("Disciplinary action ``{}`` denied: {}", disc.title, reason)
+            }
+            None => format!("Disciplinary action ``{}`` approved", disc.title),
+        },
+        json!({ "disciplinary_id": disc.id.hyphenated().to_string(), "state": new_state }),
+    )
+    .await
+    .map_err(Error::new)?;
+
+    tx.commit().await.map_err(Error::new)?;
+
+    let mut embed = CreateEmbed::new().field("Title", disc.title, true).field(
+        "ID",
+        disc.id.hyphenated().to_string(),
+        true,
+    );
+
+    if let Some(reason) = deny_reason {
+        embed = embed
+            .title("Disciplinary Action Denied")
+            .description(format!(
+                "<@{}> denied a disciplinary action issued to <@{}>",
+                auth_data.user_id, disc.user_id
+            ))
+            .field("Reason", reason, false);
+    } else {
+        embed = embed
+            .title("Disciplinary Action Approved")
+            .description(format!(
+                "<@{}> approved a disciplinary action issued to <@{}>",
+                auth_data.user_id, disc.user_id
+            ))
+            .field("Expires", format_expiry(disc.created_at, disc.expiry), true);
+    }
+
+    crate::impls::permissions::invalidate(&disc.user_id).await;
+
+    notify_staff(&state.cache_http, &disc.user_id, embed).await;
+
+    Ok((StatusCode::NO_CONTENT, "").into_response())
+}
+
+struct DisciplinaryRecord {
+    id: sqlx::types::uuid::Uuid,
+    user_id: String,
+    title: String,
+    state: String,
+    issued_by: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    expiry: Option<i64>,
+    expired: bool,
+}
+
+/// Fetches a disciplinary action by ID, returning None if it does not exist
+async fn fetch_disciplinary(
+    state: &AppState,
+    id: &str,
+) -> Result<Option<DisciplinaryRecord>, Error> {
+    let Ok(id) = sqlx::types::uuid::Uuid::parse_str(id) else {
+        return Ok(None);
+    };
+
+    let rec = sqlx::query!(
+        "SELECT id, user_id, title, state, issued_by, created_at, EXTRACT(epoch FROM expiry) AS expiry, (expiry IS NOT NULL AND NOW() - created_at >= expiry) AS \"expired!\" FROM staff_disciplinary WHERE id = $1",
+        id
+    )
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(Error::new)?;
+
+    Ok(rec.map(|rec| DisciplinaryRecord {
+        id: rec.id,
+        user_id: rec.user_id,
+        title: rec.title,
+        state: rec.state,
+        issued_by: rec.issued_by,
+        created_at: rec.created_at,
+        expiry: rec.expiry.map(|d| {
+            // Convert to i64
+            d.to_i64().unwrap_or_default()
+        }),
+        expired: rec.expired,
+    }))
+}
+
+/// Returns whether or not a staff member is higher in the hierarchy than the target staff member
+async fn is_higher_in_hierarchy(
+    state: &AppState,
+    user_id: &str,
+    target_id: &str,
+) -> Result<bool, Error> {
+    let sm = get_staff_member(&state.pool, &state.cache_http, user_id)
+        .await
+        .map_err(Error::new)?;
+
+    let sm_target = get_staff_member(&state.pool, &state.cache_http, target_id)
+        .await
+        .map_err(Error::new)?;
+
+    // Lower index = higher in the hierarchy
+    let sm_lowest_index = sm.positions.iter().map(|p| p.index).min();
+    let sm_target_lowest_index = sm_target.positions.iter().map(|p| p.index).min();
+
+    Ok(sm_lowest_index.unwrap_or(i32::MAX) < sm_target_lowest_index.unwrap_or(i32::MAX))
+}
+
+/// Ensures that a disciplinary action is still in effect (or pending) and can be changed by the user
+async fn check_in_effect(
+    state: &AppState,
+    disc: &DisciplinaryRecord,
+    user_id: &str,
+) -> Result<Result<(), (StatusCode, String)>, Error> {
+    if disc.state != StaffDisciplinaryState::Pending.to_string()
+        && disc.state != StaffDisciplinaryState::Active.to_string()
+    {
+        return Ok(Err((
+            StatusCode::BAD_REQUEST,
+            "Disciplinary action has already been denied or revoked".to_string(),
+        )));
+    }
+
+    if disc.expired {
+        return Ok(Err((
+            StatusCode::BAD_REQUEST,
+            "Disciplinary action has already expired".to_string(),
+        )));
+    }
+
+    if disc.user_id == user_id {
+        // Self-assigned disciplinary actions can be changed by the staff member themselves
+        if disc.issued_by.as_deref() != Some(user_id) {
+            return Ok(Err((
+                StatusCode::FORBIDDEN,
+                "You cannot change a disciplinary action issued to you".to_string(),
+            )));
+        }
+    } else if !is_higher_in_hierarchy(state, user_id, &disc.user_id).await? {
+        return Ok(Err((
+            StatusCode::FORBIDDEN,
+            "You can only change disciplinary actions of staff members lower in the hierarchy than you"
+                .to_string(),
+        )));
+    }
+
+    Ok(Ok(()))
+}
+
+/// Formats when a disciplinary action expires as a Discord timestamp
+fn format_expiry(created_at: chrono::DateTime<chrono::Utc>, expiry: Option<i64>) -> String {
+    match expiry {
+        Some(expiry) => format!(
+            "<t:{}:R>",
+            (created_at + chrono::Duration::seconds(expiry)).timestamp()
+        ),
+        None => "Never".to_string(),
+    }
+}
@@ -0,0 +1,263 @@
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::staff_disciplinary::{
+    StaffDisciplinaryType, StaffDisciplinaryTypeAction,
+};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+use kittycat::perms::{self, Permission};
+use num_traits::ToPrimitive;
+
+impl PanelAction for StaffDisciplinaryTypeAction {
+    fn name(&self) -> String {
+        match self {
+            StaffDisciplinaryTypeAction::ListDisciplinaryTypes => {
+                "UpdateStaffDisciplinaryType.ListDisciplinaryTypes"
+            }
+            StaffDisciplinaryTypeAction::CreateDisciplinaryType { .. } => {
+                "UpdateStaffDisciplinaryType.CreateDisciplinaryType"
+            }
+            StaffDisciplinaryTypeAction::EditDisciplinaryType { .. } => {
+                "UpdateStaffDisciplinaryType.EditDisciplinaryType"
+            }
+            StaffDisciplinaryTypeAction::DeleteDisciplinaryType { .. } => {
+                "UpdateStaffDisciplinaryType.DeleteDisciplinaryType"
+            }
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            StaffDisciplinaryTypeAction::CreateDisciplinaryType { .. } => Some(RequiredPerm::new(
+                "staff_disciplinary_types.create",
+                "create staff disciplinary types",
+            )),
+            StaffDisciplinaryTypeAction::EditDisciplinaryType { .. } => Some(RequiredPerm::new(
+                "staff_disciplinary_types.update",
+                "update staff disciplinary types",
+            )),
+            StaffDisciplinaryTypeAction::DeleteDisciplinaryType { .. } => Some(RequiredPerm::new(
+                "staff_disciplinary_types.delete",
+                "delete staff disciplinary types",
+            )),
+            _ => None,
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let (id_column, entity_ids) = match self {
+            StaffDisciplinaryTypeAction::CreateDisciplinaryType { id, .. } => {
+                ("id", vec![id.clone()])
+            }
+            StaffDisciplinaryTypeAction::EditDisciplinaryType { id, .. } => {
+                ("id", vec![id.clone()])
+            }
+            StaffDisciplinaryTypeAction::DeleteDisciplinaryType { id } => ("id", vec![id.clone()]),
+            _ => return None,
+        };
+
+        Some(AuditTarget::new(
+            "staff_disciplinary_types",
+            id_column,
+            entity_ids,
+            self,
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_staff_disciplinary_type(ctx, *self))
+    }
+}
+
+async fn update_staff_disciplinary_type(
+    ctx: &ActionContext<'_>,
+    action: StaffDisciplinaryTypeAction,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+
+    match action {
+        StaffDisciplinaryTypeAction::ListDisciplinaryTypes => {
+            let rows = sqlx::query!(
+                "SELECT id, name, description, self_assignable, perm_limits, additory, needs_approval, EXTRACT(epoch FROM max_expiry) AS max_expiry, created_at FROM staff_disciplinary_types ORDER BY created_at DESC"
+            )
+            .fetch_all(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            let mut entries = Vec::new();
+
+            for row in rows {
+                entries.push(StaffDisciplinaryType {
+                    id: row.id,
+                    name: row.name,
+                    description: row.description,
+                    self_assignable: row.self_assignable,
+                    perm_limits: row.perm_limits,
+                    additory: row.additory,
+                    needs_approval: row.needs_approval,
+                    max_expiry: row.max_expiry.map(|d| {
+                        // Convert to i64
+                        d.to_f64().unwrap_or_default()
+                    }),
+                    created_at: row.created_at,
+                });
+            }
+
+            Ok((StatusCode::OK, Json(entries)).into_response())
+        }
+        StaffDisciplinaryTypeAction::CreateDisciplinaryType {
+            id,
+            name,
+            description,
+            self_assignable,
+            perm_limits,
+            additory,
+            needs_approval,
+            max_expiry,
+        } => {
+            if let Err(e) = perms::check_patch_changes(
+                &ctx.user_perms,
+                &Vec::new(),
+                &perm_limits
+                    .iter()
+                    .map(|x| Permission::from_string(x))
+                    .collect::<Vec<Permission>>(),
+            ) {
+                return Ok((
+                    StatusCode::FORBIDDEN,
+                    format!(
+                        "You do not have permission to edit the following perms: {}",
+                        e
+                    ),
+                )
+                    .into_response());
+            }
+
+            // Insert entry
+            sqlx::query!(
+                "INSERT INTO staff_disciplinary_types (id, name, description, self_assignable, perm_limits, additory, needs_approval, max_expiry) VALUES ($1, $2, $3, $4, $5, $6, $7, make_interval(secs => $8))",
+                id,
+                name,
+                description,
+                self_assignable,
+                &perm_limits,
+                additory,
+                needs_approval,
+                max_expiry,
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        StaffDisciplinaryTypeAction::EditDisciplinaryType {
+            id,
+            name,
+            description,
+            self_assignable,
+            perm_limits,
+            additory,
+            needs_approval,
+            max_expiry,
+        } => {
+            if let Err(e) = perms::check_patch_changes(
+                &ctx.user_perms,
+                &Vec::new(),
+                &perm_limits
+                    .iter()
+                    .map(|x| Permission::from_string(x))
+                    .collect::<Vec<Permission>>(),
+            ) {
+                return Ok((
+                    StatusCode::FORBIDDEN,
+                    format!(
+                        "You do not have permission to edit the following perms: {}",
+                        e
+                    ),
+                )
+                    .into_response());
+            }
+
+            // Check if entry already exists with same vesion
+            if sqlx::query!(
+                "SELECT COUNT(*) FROM staff_disciplinary_types WHERE id = $1",
+                id
+            )
+            .fetch_one(&state.pool)
+            .await
+            .map_err(Error::new)?
+            .count
+            .unwrap_or(0)
+                == 0
+            {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Entry with same id does not already exist".to_string(),
+                )
+                    .into_response());
+            }
+
+            // Update entry
+            sqlx::query!(
+                "UPDATE staff_disciplinary_types SET name = $1, description = $2, self_assignable = $3, perm_limits = $4, additory = $5, needs_approval = $6, max_expiry = make_interval(secs => $7) WHERE id = $8",
+                name,
+                description,
+                self_assignable,
+                &perm_limits,
+                additory,
+                needs_approval,
+                max_expiry,
+                id,
+            )
+            .execute(&state.pool)
+            .await
+            .map_err(Error::new)?;
+
+            // The perms of every staff member with a disciplinary of this type may have changed
+            crate::impls::permissions::invalidate_all();
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        StaffDisciplinaryTypeAction::DeleteDisciplinaryType { id } => {
+            // Check if entry already exists with same vesion
+            if sqlx::query!(
+                "SELECT COUNT(*) FROM staff_disciplinary_types WHERE id = $1",
+                id
+            )
+            .fetch_one(&state.pool)
+            .await
+            .map_err(Error::new)?
+            .count
+            .unwrap_or(0)
+                == 0
+            {
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    "Entry with same id does not already exist".to_string(),
+                )
+                    .into_response());
+            }
+
+            // Delete entry
+            sqlx::query!("DELETE FROM staff_disciplinary_types WHERE id = $1", id)
+                .execute(&state.pool)
+                .await
+                .map_err(Error::new)?;
+
+            // The perms of every staff member with a disciplinary of this type may have changed
+            crate::impls::permissions::invalidate_all();
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::impls::loa;
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::staff_loa::{StaffLoaAction, StaffLoaState};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+impl PanelAction for StaffLoaAction {
+    fn name(&self) -> String {
+        match self {
+            StaffLoaAction::ListLoas { .. } => "UpdateStaffLoas.ListLoas",
+            StaffLoaAction::ListPending => "UpdateStaffLoas.ListPending",
+            StaffLoaAction::RequestLoa { .. } => "UpdateStaffLoas.RequestLoa",
+            StaffLoaAction::ApproveLoa { .. } => "UpdateStaffLoas.ApproveLoa",
+            StaffLoaAction::DenyLoa { .. } => "UpdateStaffLoas.DenyLoa",
+            StaffLoaAction::CancelLoa { .. } => "UpdateStaffLoas.CancelLoa",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            // Staff members can always view, request and cancel their own leaves of absence, this is checked by the handler
+            StaffLoaAction::ListLoas { .. }
+            | StaffLoaAction::RequestLoa { .. }
+            | StaffLoaAction::CancelLoa { .. } => None,
+            StaffLoaAction::ListPending
+            | StaffLoaAction::ApproveLoa { .. }
+            | StaffLoaAction::DenyLoa { .. } => Some(RequiredPerm::new(
+                "staff_members.loa",
+                "approve leaves of absence",
+            )),
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let entity_ids = match self {
+            // The ID of a new leave of absence is generated by the database
+            StaffLoaAction::RequestLoa { .. } => Vec::new(),
+            StaffLoaAction::ApproveLoa { id }
+            | StaffLoaAction::DenyLoa { id, .. }
+            | StaffLoaAction::CancelLoa { id } => vec![id.clone()],
+            _ => return None,
+        };
+
+        Some(AuditTarget::new("staff_loas", "id", entity_ids, self))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_staff_loas(ctx, *self))
+    }
+}
+
+async fn update_staff_loas(
+    ctx: &ActionContext<'_>,
+    action: StaffLoaAction,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    match action {
+        StaffLoaAction::ListLoas { user_id } => {
+            let user_id = user_id.unwrap_or_else(|| auth_data.user_id.clone());
+
+            if user_id != auth_data.user_id && !ctx.has_perm("staff_members.loa") {
+                return Ok((
+                    StatusCode::FORBIDDEN,
+                    "You do not have permission to view the leaves of absence of other staff members [staff_members.loa]"
+                        .to_string(),
+                )
+                    .into_response());
+            }
+
+            let loas = loa::get_loas(&state.pool, Some(&user_id), None)
+                .await
+                .map_err(Error::new)?;
+
+            Ok((StatusCode::OK, Json(loas)).into_response())
+        }
+        StaffLoaAction::ListPending => {
+            let loas = loa::get_loas(&state.pool, None, Some(StaffLoaState::Pending))
+                .await
+                .map_err(Error::new)?;
+
+            Ok((StatusCode::OK, Json(loas)).into_response())
+        }
+        StaffLoaAction::RequestLoa {
+            reason,
+            start_date,
+            end_date,
+        } => {
+            let loa = loa::request(
+                &state.cache_http,
+                &state.pool,
+                &auth_data.user_id,
+                reason,
+                start_date,
+                end_date,
+            )
+            .await
+            .map_err(bad_request)?;
+
+            ctx.created(&loa.id);
+
+            Ok((StatusCode::OK, Json(loa)).into_response())
+        }
+        StaffLoaAction::ApproveLoa { id } => {
+            loa::review(
+                &state.cache_http,
+                &state.pool,
+                &id,
+                &auth_data.user_id,
+                None,
+            )
+            .await
+            .map_err(bad_request)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        StaffLoaAction::DenyLoa { id, reason } => {
+            loa::review(
+                &state.cache_http,
+                &state.pool,
+                &id,
+                &auth_data.user_id,
+                Some(reason),
+            )
+            .await
+            .map_err(bad_request)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        StaffLoaAction::CancelLoa { id } => {
+            loa::cancel(
+                &state.cache_http,
+                &state.pool,
+                &id,
+                &auth_data.user_id,
+                ctx.has_perm("staff_members.loa"),
+            )
+            .await
+            .map_err(bad_request)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+    }
+}
+
+/// Leave of absence lifecycle errors are caused by the request (e.g. approving an already denied leave of absence)
+fn bad_request(e: crate::Error) -> Error {
+    Error {
+        status: StatusCode::BAD_REQUEST,
+        message: e.to_string(),
+    }
+}
@@ -1,21 +1,65 @@
-use crate::panelapi::auth::{check_auth, get_staff_member};
-use crate::panelapi::core::{AppState, Error};
+use crate::impls::timeline;
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::auth::get_staff_member;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
 use crate::panelapi::types::staff_members::StaffMemberAction;
+use crate::panelapi::types::staff_timeline::StaffTimelineEventKind;
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use futures_util::future::BoxFuture;
 use kittycat::perms::{self, Permission};
 
-pub async fn update_staff_members(
-    state: &AppState,
-    login_token: String,
+impl PanelAction for StaffMemberAction {
+    fn name(&self) -> String {
+        match self {
+            StaffMemberAction::ListMembers => "UpdateStaffMembers.ListMembers",
+            StaffMemberAction::EditMember { .. } => "UpdateStaffMembers.EditMember",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            StaffMemberAction::EditMember { .. } => Some(RequiredPerm::new(
+                "staff_members.edit",
+                "edit staff members",
+            )),
+            _ => None,
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let (id_column, entity_ids) = match self {
+            StaffMemberAction::EditMember { user_id, .. } => ("user_id", vec![user_id.clone()]),
+            _ => return None,
+        };
+
+        Some(AuditTarget::new(
+            "staff_members",
+            id_column,
+            entity_ids,
+            self,
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_staff_members(ctx, *self))
+    }
+}
+
+async fn update_staff_members(
+    ctx: &ActionContext<'_>,
     action: StaffMemberAction,
 ) -> Result<Response, Error> {
-    let auth_data = check_auth(&state.pool, &login_token)
-        .await
-        .map_err(Error::new)?;
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
 
     match action {
         StaffMemberAction::ListMembers => {
@@ -53,15 +97,6 @@ pub async fn update_staff_members(
                 .await
                 .map_err(Error::new)?;
 
-            if !perms::has_perm(&sm.resolved_perms, &"staff_members.edit".into()) {
-                return Ok((
-                    StatusCode::FORBIDDEN,
-                    "You do not have permission to edit staff members [staff_members.edit]"
-                        .to_string(),
-                )
-                    .into_response());
-            }
-
             // Get the lowest index permission of the member
             let mut sm_lowest_index = i32::MAX;
 
@@ -120,7 +155,7 @@ pub async fn update_staff_members(
             let mut tx = state.pool.begin().await.map_err(Error::new)?;
 
             // Lock the member for update
-            sqlx::query!("SELECT perm_overrides, no_autosync, unaccounted FROM staff_members WHERE user_id = $1 FOR UPDATE", user_id)
+            let old = sqlx::query!("SELECT perm_overrides, no_autosync, unaccounted FROM staff_members WHERE user_id = $1 FOR UPDATE", user_id)
         .fetch_one(&mut *tx)
         .await
         .map_err(|e| format!("Error while getting member {}", e))
@@ -138,8 +173,51 @@ pub async fn update_staff_members(
         .map_err(|e| format!("Error while updating member {}", e))
         .map_err(Error::new)?;
 
+            let new_overrides = perm_overrides
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<String>>();
+
+            if old.perm_overrides != new_overrides {
+                timeline::record(
+                    &mut *tx,
+                    &user_id,
+                    StaffTimelineEventKind::PermOverrides,
+                    Some(&auth_data.user_id),
+                    format!(
+                        "Permission overrides changed from [{}] to [{}]",
+                        old.perm_overrides.join(", "),
+                        new_overrides.join(", ")
+                    ),
+                    serde_json::json!({ "old": old.perm_overrides, "new": new_overrides }),
+                )
+                .await
+                .map_err(Error::new)?;
+            }
+
+            if old.no_autosync != no_autosync || old.unaccounted != unaccounted {
+                timeline::record(
+                    &mut *tx,
+                    &user_id,
+                    StaffTimelineEventKind::Flags,
+                    Some(&auth_data.user_id),
+                    format!(
+                        "no_autosync {} -> {}, unaccounted {} -> {}",
+                        old.no_autosync, no_autosync, old.unaccounted, unaccounted
+                    ),
+                    serde_json::json!({
+                        "no_autosync": { "old": old.no_autosync, "new": no_autosync },
+                        "unaccounted": { "old": old.unaccounted, "new": unaccounted },
+                    }),
+                )
+                .await
+                .map_err(Error::new)?;
+            }
+
             tx.commit().await.map_err(Error::new)?;
 
+            crate::impls::permissions::invalidate(&user_id).await;
+
             Ok((StatusCode::NO_CONTENT, "").into_response())
         }
     }
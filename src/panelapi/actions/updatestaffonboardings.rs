@@ -0,0 +1,216 @@
+use crate::impls::onboarding;
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::staff_onboardings::{StaffOnboardingAction, StaffOnboardingState};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+impl PanelAction for StaffOnboardingAction {
+    fn name(&self) -> String {
+        match self {
+            StaffOnboardingAction::ListOnboardings { .. } => {
+                "UpdateStaffOnboardings.ListOnboardings"
+            }
+            StaffOnboardingAction::ListSubmitted => "UpdateStaffOnboardings.ListSubmitted",
+            StaffOnboardingAction::StartOnboarding { .. } => {
+                "UpdateStaffOnboardings.StartOnboarding"
+            }
+            StaffOnboardingAction::SubmitResponses { .. } => {
+                "UpdateStaffOnboardings.SubmitResponses"
+            }
+            StaffOnboardingAction::GradeOnboarding { .. } => {
+                "UpdateStaffOnboardings.GradeOnboarding"
+            }
+            StaffOnboardingAction::CompleteOnboarding { .. } => {
+                "UpdateStaffOnboardings.CompleteOnboarding"
+            }
+            StaffOnboardingAction::VoidOnboarding { .. } => "UpdateStaffOnboardings.VoidOnboarding",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            // Staff members can always view, start and submit their own onboardings, this is checked by the handler
+            StaffOnboardingAction::ListOnboardings { .. }
+            | StaffOnboardingAction::StartOnboarding { .. }
+            | StaffOnboardingAction::SubmitResponses { .. } => None,
+            StaffOnboardingAction::ListSubmitted
+            | StaffOnboardingAction::GradeOnboarding { .. } => Some(RequiredPerm::new(
+                "staff_onboardings.grade",
+                "grade staff onboardings",
+            )),
+            StaffOnboardingAction::CompleteOnboarding { .. } => Some(RequiredPerm::new(
+                "staff_onboardings.complete",
+                "complete staff onboardings",
+            )),
+            StaffOnboardingAction::VoidOnboarding { .. } => Some(RequiredPerm::new(
+                "staff_onboardings.void",
+                "void staff onboardings",
+            )),
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let entity_ids = match self {
+            // The ID of a new onboarding is generated by the database
+            StaffOnboardingAction::StartOnboarding { .. } => Vec::new(),
+            StaffOnboardingAction::SubmitResponses { id, .. }
+            | StaffOnboardingAction::GradeOnboarding { id, .. }
+            | StaffOnboardingAction::CompleteOnboarding { id, .. }
+            | StaffOnboardingAction::VoidOnboarding { id, .. } => vec![id.clone()],
+            _ => return None,
+        };
+
+        Some(AuditTarget::new(
+            "staff_onboardings",
+            "id",
+            entity_ids,
+            self,
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_staff_onboardings(ctx, *self))
+    }
+}
+
+async fn update_staff_onboardings(
+    ctx: &ActionContext<'_>,
+    action: StaffOnboardingAction,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    match action {
+        StaffOnboardingAction::ListOnboardings { user_id } => {
+            let user_id = user_id.unwrap_or_else(|| auth_data.user_id.clone());
+
+            if user_id != auth_data.user_id && !ctx.has_perm("staff_onboardings.view") {
+                return Ok((
+                    StatusCode::FORBIDDEN,
+                    "You do not have permission to view the onboardings of other staff members [staff_onboardings.view]"
+                        .to_string(),
+                )
+                    .into_response());
+            }
+
+            let onboardings = onboarding::get_onboardings(&state.pool, Some(&user_id), None, true)
+                .await
+                .map_err(Error::new)?;
+
+            Ok((StatusCode::OK, Json(onboardings)).into_response())
+        }
+        StaffOnboardingAction::ListSubmitted => {
+            let onboardings = onboarding::get_onboardings(
+                &state.pool,
+                None,
+                Some(StaffOnboardingState::Submitted),
+                false,
+            )
+            .await
+            .map_err(Error::new)?;
+
+            Ok((StatusCode::OK, Json(onboardings)).into_response())
+        }
+        StaffOnboardingAction::StartOnboarding { user_id, bot_id } => {
+            let user_id = user_id.unwrap_or_else(|| auth_data.user_id.clone());
+
+            if user_id != auth_data.user_id && !ctx.has_perm("staff_onboardings.manage") {
+                return Ok((
+                    StatusCode::FORBIDDEN,
+                    "You do not have permission to start the onboarding of other staff members [staff_onboardings.manage]"
+                        .to_string(),
+                )
+                    .into_response());
+            }
+
+            let onboarding = onboarding::start(
+                &state.cache_http,
+                &state.pool,
+                &user_id,
+                &auth_data.user_id,
+                bot_id,
+            )
+            .await
+            .map_err(bad_request)?;
+
+            ctx.created(&onboarding.id);
+
+            Ok((StatusCode::OK, Json(onboarding)).into_response())
+        }
+        StaffOnboardingAction::SubmitResponses { id, responses } => {
+            onboarding::submit(
+                &state.cache_http,
+                &state.pool,
+                &id,
+                &auth_data.user_id,
+                responses,
+            )
+            .await
+            .map_err(bad_request)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        StaffOnboardingAction::GradeOnboarding {
+            id,
+            passed,
+            feedback,
+        } => {
+            onboarding::grade(
+                &state.cache_http,
+                &state.pool,
+                &id,
+                &auth_data.user_id,
+                passed,
+                feedback,
+            )
+            .await
+            .map_err(bad_request)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        StaffOnboardingAction::CompleteOnboarding { id, validity_days } => {
+            onboarding::complete(
+                &state.cache_http,
+                &state.pool,
+                &id,
+                &auth_data.user_id,
+                validity_days,
+            )
+            .await
+            .map_err(bad_request)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        StaffOnboardingAction::VoidOnboarding { id, reason } => {
+            onboarding::void(
+                &state.cache_http,
+                &state.pool,
+                &id,
+                &auth_data.user_id,
+                reason,
+            )
+            .await
+            .map_err(bad_request)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+    }
+}
+
+/// Onboarding lifecycle errors are caused by the request (e.g. grading an onboarding that was not submitted)
+fn bad_request(e: crate::Error) -> Error {
+    Error {
+        status: StatusCode::BAD_REQUEST,
+        message: e.to_string(),
+    }
+}
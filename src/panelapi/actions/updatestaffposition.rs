@@ -1,27 +1,91 @@
-use std::str::FromStr;
-
-use crate::panelapi::auth::{check_auth, get_staff_member};
-use crate::panelapi::core::{AppState, Error};
-use crate::panelapi::types::staff_positions::{
-    CorrespondingServer, StaffPosition, StaffPositionAction,
-};
+use crate::impls::link::Link;
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::auth::get_staff_member;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::staff_positions::{StaffPosition, StaffPositionAction};
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use futures_util::future::BoxFuture;
 use kittycat::perms::{self, Permission};
 use serenity::all::RoleId;
-use strum::VariantNames;
 
-pub async fn update_staff_position(
-    state: &AppState,
-    login_token: String,
+impl PanelAction for StaffPositionAction {
+    fn name(&self) -> String {
+        match self {
+            StaffPositionAction::ListPositions => "UpdateStaffPositions.ListPositions",
+            StaffPositionAction::SwapIndex { .. } => "UpdateStaffPositions.SwapIndex",
+            StaffPositionAction::SetIndex { .. } => "UpdateStaffPositions.SetIndex",
+            StaffPositionAction::CreatePosition { .. } => "UpdateStaffPositions.CreatePosition",
+            StaffPositionAction::EditPosition { .. } => "UpdateStaffPositions.EditPosition",
+            StaffPositionAction::DeletePosition { .. } => "UpdateStaffPositions.DeletePosition",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            StaffPositionAction::SwapIndex { .. } => Some(RequiredPerm::new(
+                "staff_positions.swap_index",
+                "swap indexes of staff positions",
+            )),
+            StaffPositionAction::SetIndex { .. } => Some(RequiredPerm::new(
+                "staff_positions.set_index",
+                "set the indexes of staff positions",
+            )),
+            StaffPositionAction::CreatePosition { .. } => Some(RequiredPerm::new(
+                "staff_positions.create",
+                "create staff positions",
+            )),
+            StaffPositionAction::EditPosition { .. } => Some(RequiredPerm::new(
+                "staff_positions.edit",
+                "edit staff positions",
+            )),
+            StaffPositionAction::DeletePosition { .. } => Some(RequiredPerm::new(
+                "staff_positions.delete",
+                "delete staff positions",
+            )),
+            _ => None,
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let (id_column, entity_ids) = match self {
+            StaffPositionAction::SwapIndex { a, b } => ("id", vec![a.clone(), b.clone()]),
+            StaffPositionAction::SetIndex { id, .. } => ("id", vec![id.clone()]),
+            StaffPositionAction::CreatePosition { role_id, .. } => {
+                ("role_id", vec![role_id.clone()])
+            }
+            StaffPositionAction::EditPosition { id, .. } => ("id", vec![id.clone()]),
+            StaffPositionAction::DeletePosition { id } => ("id", vec![id.clone()]),
+            _ => return None,
+        };
+
+        Some(AuditTarget::new(
+            "staff_positions",
+            id_column,
+            entity_ids,
+            self,
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_staff_position(ctx, *self))
+    }
+}
+
+async fn update_staff_position(
+    ctx: &ActionContext<'_>,
     action: StaffPositionAction,
 ) -> Result<Response, Error> {
-    let auth_data = check_auth(&state.pool, &login_token)
-        .await
-        .map_err(Error::new)?;
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
 
     match action {
         StaffPositionAction::ListPositions => {
@@ -55,14 +119,6 @@ pub async fn update_staff_position(
                 .await
                 .map_err(Error::new)?;
 
-            if !perms::has_perm(&sm.resolved_perms, &"staff_positions.swap_index".into()) {
-                return Ok((
-                StatusCode::FORBIDDEN,
-                "You do not have permission to swap indexes of staff positions [staff_positions.swap_index]".to_string(),
-            )
-                .into_response());
-            }
-
             // Get the lowest index permission of the member
             let mut sm_lowest_index = i32::MAX;
 
@@ -129,6 +185,9 @@ pub async fn update_staff_position(
 
             tx.commit().await.map_err(Error::new)?;
 
+            // The perms of every staff member with this position may have changed
+            crate::impls::permissions::invalidate_all();
+
             Ok((StatusCode::NO_CONTENT, "").into_response())
         }
         StaffPositionAction::SetIndex { id, index } => {
@@ -139,14 +198,6 @@ pub async fn update_staff_position(
                 .await
                 .map_err(Error::new)?;
 
-            if !perms::has_perm(&sm.resolved_perms, &"staff_positions.set_index".into()) {
-                return Ok((
-                StatusCode::FORBIDDEN,
-                "You do not have permission to set the indexes of staff positions [staff_positions.set_index]".to_string(),
-            )
-                .into_response());
-            }
-
             if index < 0 {
                 return Ok((
                     StatusCode::BAD_REQUEST,
@@ -214,6 +265,9 @@ pub async fn update_staff_position(
 
             tx.commit().await.map_err(Error::new)?;
 
+            // The perms of every staff member with this position may have changed
+            crate::impls::permissions::invalidate_all();
+
             Ok((StatusCode::NO_CONTENT, "").into_response())
         }
         StaffPositionAction::CreatePosition {
@@ -229,15 +283,6 @@ pub async fn update_staff_position(
                 .await
                 .map_err(Error::new)?;
 
-            if !perms::has_perm(&sm.resolved_perms, &"staff_positions.create".into()) {
-                return Ok((
-                    StatusCode::FORBIDDEN,
-                    "You do not have permission to create staff positions [staff_positions.create]"
-                        .to_string(),
-                )
-                    .into_response());
-            }
-
             if index < 0 {
                 return Ok((
                     StatusCode::BAD_REQUEST,
@@ -298,42 +343,9 @@ pub async fn update_staff_position(
                     .into_response());
             }
 
-            // Ensure all corresponding_roles exist on the named server if
-            for role in corresponding_roles.iter() {
-                let Ok(corr_server) = CorrespondingServer::from_str(&role.name) else {
-                    return Ok((
-                        StatusCode::BAD_REQUEST,
-                        format!(
-                            "Server {} is not a supported corresponding role. Supported: {:#?}",
-                            role.name,
-                            CorrespondingServer::VARIANTS
-                        ),
-                    )
-                        .into_response());
-                };
-                let role_id_snow = role.value.parse::<RoleId>().map_err(Error::new)?;
-
-                let role_exists = {
-                    let guild = state.cache_http.cache.guild(corr_server.get_id());
-
-                    if let Some(guild) = guild {
-                        guild.roles.get(&role_id_snow).is_some()
-                    } else {
-                        false
-                    }
-                };
-
-                if !role_exists {
-                    return Ok((
-                        StatusCode::BAD_REQUEST,
-                        format!(
-                            "Role {} does not exist on the server {}",
-                            role_id_snow,
-                            corr_server.get_id()
-                        ),
-                    )
-                        .into_response());
-                }
+            // Ensure all corresponding_roles exist on the guild they name
+            if let Err(e) = validate_corresponding_roles(&state.cache_http, &corresponding_roles) {
+                return Ok((StatusCode::BAD_REQUEST, e).into_response());
             }
 
             // Create the position
@@ -370,15 +382,6 @@ pub async fn update_staff_position(
                 .await
                 .map_err(Error::new)?;
 
-            if !perms::has_perm(&sm.resolved_perms, &"staff_positions.edit".into()) {
-                return Ok((
-                    StatusCode::FORBIDDEN,
-                    "You do not have permission to edit staff positions [staff_positions.edit]"
-                        .to_string(),
-                )
-                    .into_response());
-            }
-
             // Get the lowest index permission of the member
             let mut sm_lowest_index = i32::MAX;
 
@@ -455,42 +458,9 @@ pub async fn update_staff_position(
                     .into_response());
             }
 
-            // Ensure all corresponding_roles exist on the named server if
-            for role in corresponding_roles.iter() {
-                let Ok(corr_server) = CorrespondingServer::from_str(&role.name) else {
-                    return Ok((
-                        StatusCode::BAD_REQUEST,
-                        format!(
-                            "Server {} is not a supported corresponding role. Supported: {:#?}",
-                            role.name,
-                            CorrespondingServer::VARIANTS
-                        ),
-                    )
-                        .into_response());
-                };
-                let role_id_snow = role.value.parse::<RoleId>().map_err(Error::new)?;
-
-                let role_exists = {
-                    let guild = state.cache_http.cache.guild(corr_server.get_id());
-
-                    if let Some(guild) = guild {
-                        guild.roles.get(&role_id_snow).is_some()
-                    } else {
-                        false
-                    }
-                };
-
-                if !role_exists {
-                    return Ok((
-                        StatusCode::BAD_REQUEST,
-                        format!(
-                            "Role {} does not exist on the server {}",
-                            role_id_snow,
-                            corr_server.get_id()
-                        ),
-                    )
-                        .into_response());
-                }
+            // Ensure all corresponding_roles exist on the guild they name
+            if let Err(e) = validate_corresponding_roles(&state.cache_http, &corresponding_roles) {
+                return Ok((StatusCode::BAD_REQUEST, e).into_response());
             }
 
             // Update the position
@@ -510,6 +480,9 @@ pub async fn update_staff_position(
 
             tx.commit().await.map_err(Error::new)?;
 
+            // The perms of every staff member with this position may have changed
+            crate::impls::permissions::invalidate_all();
+
             Ok((StatusCode::NO_CONTENT, "").into_response())
         }
         StaffPositionAction::DeletePosition { id } => {
@@ -520,15 +493,6 @@ pub async fn update_staff_position(
                 .await
                 .map_err(Error::new)?;
 
-            if !perms::has_perm(&sm.resolved_perms, &"staff_positions.delete".into()) {
-                return Ok((
-                    StatusCode::FORBIDDEN,
-                    "You do not have permission to delete staff positions [staff_positions.delete]"
-                        .to_string(),
-                )
-                    .into_response());
-            }
-
             // Get the lowest index permission of the member
             let mut sm_lowest_index = i32::MAX;
 
@@ -595,7 +559,44 @@ pub async fn update_staff_position(
 
             tx.commit().await.map_err(Error::new)?;
 
+            // The perms of every staff member with this position may have changed
+            crate::impls::permissions::invalidate_all();
+
             Ok((StatusCode::NO_CONTENT, "").into_response())
         }
     }
 }
+
+/// Ensures every corresponding role names a configured guild alias and exists in that guild
+fn validate_corresponding_roles(
+    cache_http: &botox::cache::CacheHttpImpl,
+    corresponding_roles: &[Link],
+) -> Result<(), String> {
+    for role in corresponding_roles.iter() {
+        let Some(guild_id) = crate::config::CONFIG.servers.get_by_alias(&role.name) else {
+            return Err(format!(
+                "Server {} is not a known guild alias. Known aliases: {}",
+                role.name,
+                crate::config::CONFIG.servers.alias_names().join(", ")
+            ));
+        };
+
+        let Ok(role_id) = role.value.parse::<RoleId>() else {
+            return Err(format!("Role {} is not a valid role ID", role.value));
+        };
+
+        let role_exists = match cache_http.cache.guild(guild_id) {
+            Some(guild) => guild.roles.get(&role_id).is_some(),
+            None => false,
+        };
+
+        if !role_exists {
+            return Err(format!(
+                "Role {} does not exist on the server {} ({})",
+                role_id, role.name, guild_id
+            ));
+        }
+    }
+
+    Ok(())
+}
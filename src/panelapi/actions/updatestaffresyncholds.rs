@@ -0,0 +1,87 @@
+use crate::impls::resync;
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
+use crate::panelapi::types::staff_resync::StaffResyncHoldAction;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::future::BoxFuture;
+
+impl PanelAction for StaffResyncHoldAction {
+    fn name(&self) -> String {
+        match self {
+            StaffResyncHoldAction::ListHolds => "UpdateStaffResyncHolds.ListHolds",
+            StaffResyncHoldAction::ConfirmRemoval { .. } => "UpdateStaffResyncHolds.ConfirmRemoval",
+            StaffResyncHoldAction::RejectRemoval { .. } => "UpdateStaffResyncHolds.RejectRemoval",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        Some(RequiredPerm::new(
+            "staff_members.resync_holds",
+            "confirm or reject held staff position removals",
+        ))
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        match self {
+            StaffResyncHoldAction::ConfirmRemoval { id }
+            | StaffResyncHoldAction::RejectRemoval { id } => Some(AuditTarget::new(
+                "staff_resync_holds",
+                "id",
+                vec![id.clone()],
+                self,
+            )),
+            _ => None,
+        }
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_staff_resync_holds(ctx, *self))
+    }
+}
+
+async fn update_staff_resync_holds(
+    ctx: &ActionContext<'_>,
+    action: StaffResyncHoldAction,
+) -> Result<Response, Error> {
+    let state = ctx.state;
+    let auth_data = ctx.auth_data()?;
+
+    match action {
+        StaffResyncHoldAction::ListHolds => {
+            let holds = resync::get_holds(&state.pool).await.map_err(Error::new)?;
+
+            Ok((StatusCode::OK, Json(holds)).into_response())
+        }
+        StaffResyncHoldAction::ConfirmRemoval { id } => {
+            resync::confirm(&state.cache_http, &state.pool, &id, &auth_data.user_id)
+                .await
+                .map_err(bad_request)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+        StaffResyncHoldAction::RejectRemoval { id } => {
+            resync::reject(&state.cache_http, &state.pool, &id, &auth_data.user_id)
+                .await
+                .map_err(bad_request)?;
+
+            Ok((StatusCode::NO_CONTENT, "").into_response())
+        }
+    }
+}
+
+/// Hold errors are caused by the request (e.g. rejecting a hold for a member who left the staff server)
+fn bad_request(e: crate::Error) -> Error {
+    Error {
+        status: StatusCode::BAD_REQUEST,
+        message: e.to_string(),
+    }
+}
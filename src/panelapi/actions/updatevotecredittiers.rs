@@ -1,27 +1,72 @@
-use crate::impls::utils::get_user_perms;
-use crate::panelapi::auth::check_auth;
-use crate::panelapi::core::{AppState, Error};
+use crate::panelapi::audit::AuditTarget;
+use crate::panelapi::core::Error;
+use crate::panelapi::registry::{ActionContext, PanelAction, RequiredPerm};
 use crate::panelapi::types::vote_credit_tiers::{VoteCreditTier, VoteCreditTierAction};
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
-use kittycat::perms;
+use futures_util::future::BoxFuture;
+
+impl PanelAction for VoteCreditTierAction {
+    fn name(&self) -> String {
+        match self {
+            VoteCreditTierAction::ListTiers => "UpdateVoteCreditTiers.ListTiers",
+            VoteCreditTierAction::CreateTier { .. } => "UpdateVoteCreditTiers.CreateTier",
+            VoteCreditTierAction::EditTier { .. } => "UpdateVoteCreditTiers.EditTier",
+            VoteCreditTierAction::DeleteTier { .. } => "UpdateVoteCreditTiers.DeleteTier",
+        }
+        .to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        match self {
+            VoteCreditTierAction::CreateTier { .. } => Some(RequiredPerm::new(
+                "vote_credit_tiers.create",
+                "create vote credit tiers",
+            )),
+            VoteCreditTierAction::EditTier { .. } => Some(RequiredPerm::new(
+                "vote_credit_tiers.update",
+                "update vote credit tiers",
+            )),
+            VoteCreditTierAction::DeleteTier { .. } => Some(RequiredPerm::new(
+                "vote_credit_tiers.delete",
+                "delete vote credit tiers",
+            )),
+            _ => None,
+        }
+    }
+
+    fn audit_target(&self) -> Option<AuditTarget> {
+        let (id_column, entity_ids) = match self {
+            VoteCreditTierAction::CreateTier { id, .. } => ("id", vec![id.clone()]),
+            VoteCreditTierAction::EditTier { id, .. } => ("id", vec![id.clone()]),
+            VoteCreditTierAction::DeleteTier { id } => ("id", vec![id.clone()]),
+            _ => return None,
+        };
+
+        Some(AuditTarget::new(
+            "vote_credit_tiers",
+            id_column,
+            entity_ids,
+            self,
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(update_vote_credit_tiers(ctx, *self))
+    }
+}
 
-pub async fn update_vote_credit_tiers(
-    state: &AppState,
-    login_token: String,
+async fn update_vote_credit_tiers(
+    ctx: &ActionContext<'_>,
     action: VoteCreditTierAction,
 ) -> Result<Response, Error> {
-    let auth_data = check_auth(&state.pool, &login_token)
-        .await
-        .map_err(Error::new)?;
-
-    let user_perms = get_user_perms(&state.pool, &auth_data.user_id)
-        .await
-        .map_err(Error::new)?
-        .resolve();
+    let state = ctx.state;
 
     match action {
         VoteCreditTierAction::ListTiers => {
@@ -54,14 +99,6 @@ pub async fn update_vote_credit_tiers(
             cents,
             votes,
         } => {
-            if !perms::has_perm(&user_perms, &"vote_credit_tiers.create".into()) {
-                return Ok((
-                StatusCode::FORBIDDEN,
-                "You do not have permission to create vote credit tiers [vote_credit_tiers.create]".to_string(),
-            )
-                .into_response());
-            }
-
             if cents < 0.0 {
                 return Ok((
                     StatusCode::BAD_REQUEST,
@@ -146,14 +183,6 @@ pub async fn update_vote_credit_tiers(
             cents,
             votes,
         } => {
-            if !perms::has_perm(&user_perms, &"vote_credit_tiers.update".into()) {
-                return Ok((
-                StatusCode::FORBIDDEN,
-                "You do not have permission to update vote credit tiers [vote_credit_tiers.update]".to_string(),
-            )
-                .into_response());
-            }
-
             // Check if entry already exists with same id
             if sqlx::query!("SELECT COUNT(*) FROM vote_credit_tiers WHERE id = $1", id)
                 .fetch_one(&state.pool)
@@ -249,14 +278,6 @@ pub async fn update_vote_credit_tiers(
             Ok((StatusCode::NO_CONTENT, "").into_response())
         }
         VoteCreditTierAction::DeleteTier { id } => {
-            if !perms::has_perm(&user_perms, &"vote_credit_tiers.delete".into()) {
-                return Ok((
-                StatusCode::FORBIDDEN,
-                "You do not have permission to delete vote credit tiers [vote_credit_tiers.delete]".to_string(),
-            )
-                .into_response());
-            }
-
             // Check if entry already exists with same vesion
             if sqlx::query!("SELECT COUNT(*) FROM vote_credit_tiers WHERE id = $1", id)
                 .fetch_one(&state.pool)
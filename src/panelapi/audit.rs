@@ -0,0 +1,194 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use serenity::all::{CreateEmbed, CreateMessage};
+use sqlx::{PgPool, Row};
+
+use super::core::AppState;
+
+/// The maximum length of the changes shown when mirroring an entry to staff_logs
+const MAX_MIRROR_CHANGES_LENGTH: usize = 1000;
+
+/// The entities changed by a mutating panel action
+pub struct AuditTarget {
+    /// The table the entities are stored in, this is also the entity type of the audit log entry
+    pub table: &'static str,
+    /// The column the entities are looked up by
+    pub id_column: &'static str,
+    /// The IDs of the entities being changed
+    ///
+    /// This may be empty if the ID is not known before the action runs (e.g. generated IDs), the
+    /// action then reports the created IDs with `ActionContext::created`
+    pub entity_ids: Vec<String>,
+    /// The data sent with the action
+    pub data: Value,
+}
+
+impl AuditTarget {
+    pub fn new(
+        table: &'static str,
+        id_column: &'static str,
+        entity_ids: Vec<String>,
+        data: impl Serialize,
+    ) -> Self {
+        Self {
+            table,
+            id_column,
+            entity_ids,
+            data: serde_json::to_value(data).unwrap_or(Value::Null),
+        }
+    }
+}
+
+/// Returns the current state of the entities of an audit target
+///
+/// If the target has a single entity, this is the row itself, otherwise this is an object
+/// mapping the ID of each entity to its row
+pub async fn snapshot(pool: &PgPool, target: &AuditTarget) -> Result<Option<Value>, crate::Error> {
+    if target.entity_ids.is_empty() {
+        return Ok(None);
+    }
+
+    let sql = format!(
+        "SELECT {id_column}::text AS id, row_to_json(t)::jsonb AS data FROM {table} t WHERE {id_column}::text = ANY($1)",
+        id_column = target.id_column,
+        table = target.table
+    );
+
+    let rows = sqlx::query(&sql)
+        .bind(&target.entity_ids)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Error while snapshotting {}: {}", target.table, e))?;
+
+    if target.entity_ids.len() == 1 {
+        return match rows.first() {
+            Some(row) => Ok(Some(row.try_get::<Value, &str>("data")?)),
+            None => Ok(None),
+        };
+    }
+
+    let mut entities = Map::new();
+
+    for row in rows {
+        entities.insert(
+            row.try_get::<String, &str>("id")?,
+            row.try_get::<Value, &str>("data")?,
+        );
+    }
+
+    if entities.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(Value::Object(entities)))
+}
+
+/// Returns the changes between two snapshots, or None if nothing changed
+///
+/// Objects are compared key by key, all other changed values are recorded as
+/// `{"old": ..., "new": ...}`
+pub fn diff(before: &Value, after: &Value) -> Option<Value> {
+    match (before, after) {
+        (Value::Object(b), Value::Object(a)) => {
+            let keys = b.keys().chain(a.keys()).collect::<BTreeSet<&String>>();
+
+            let mut changes = Map::new();
+
+            for key in keys {
+                let old = b.get(key).unwrap_or(&Value::Null);
+                let new = a.get(key).unwrap_or(&Value::Null);
+
+                if let Some(change) = diff(old, new) {
+                    changes.insert(key.clone(), change);
+                }
+            }
+
+            if changes.is_empty() {
+                None
+            } else {
+                Some(Value::Object(changes))
+            }
+        }
+        _ if before == after => None,
+        _ => Some(json!({
+            "old": before,
+            "new": after,
+        })),
+    }
+}
+
+/// Records an audit log entry for a panel action, mirroring it to staff_logs if enabled
+pub async fn record(
+    state: &AppState,
+    user_id: &str,
+    action: &str,
+    target: AuditTarget,
+    before: Option<Value>,
+    after: Option<Value>,
+) -> Result<(), crate::Error> {
+    let changes = diff(
+        before.as_ref().unwrap_or(&Value::Null),
+        after.as_ref().unwrap_or(&Value::Null),
+    );
+
+    sqlx::query!(
+        "INSERT INTO staffpanel__audit_log (user_id, action, entity_type, entity_ids, data, before, after, diff) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        user_id,
+        action,
+        target.table,
+        &target.entity_ids,
+        target.data,
+        before,
+        after,
+        changes
+    )
+    .execute(&state.pool)
+    .await?;
+
+    if !crate::config::CONFIG.panel.mirror_audit_log {
+        return Ok(());
+    }
+
+    let entity_ids = if target.entity_ids.is_empty() {
+        "New entity".to_string()
+    } else {
+        target.entity_ids.join(", ")
+    };
+
+    let changes = match changes {
+        Some(changes) => {
+            let changes = serde_json::to_string_pretty(&changes)?;
+
+            if changes.len() > MAX_MIRROR_CHANGES_LENGTH {
+                format!(
+                    "{}...",
+                    changes
+                        .chars()
+                        .take(MAX_MIRROR_CHANGES_LENGTH)
+                        .collect::<String>()
+                )
+            } else {
+                changes
+            }
+        }
+        None => "No changes".to_string(),
+    };
+
+    crate::config::CONFIG
+        .channels
+        .staff_logs
+        .send_message(
+            &state.cache_http.http,
+            CreateMessage::new().embeds(vec![CreateEmbed::new()
+                .title("Panel Audit Log")
+                .description(format!("<@{}> performed `{}`", user_id, action))
+                .field("Entity Type", target.table, true)
+                .field("Entity IDs", entity_ids, true)
+                .field("Changes", format!("```json\n{}\n```", changes), false)]),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -5,10 +5,18 @@ use kittycat::perms::{PartialStaffPosition, Permission, StaffPermissions};
 use num_traits::cast::ToPrimitive;
 use sqlx::PgPool;
 
+use super::core::{AppState, RequestMeta};
 use super::types::{
-    auth::AuthData, staff_disciplinary::StaffDisciplinary, staff_members::StaffMember,
-    staff_positions::StaffPosition,
+    auth::AuthData, login_history::LoginEvent, staff_disciplinary::StaffDisciplinary,
+    staff_members::StaffMember, staff_positions::StaffPosition,
 };
+use serenity::all::{CreateEmbed, CreateMessage};
+
+/// How many MFA failures within `MFA_FAILURE_WINDOW_MINUTES` triggers an alert
+const MFA_FAILURE_ALERT_THRESHOLD: i64 = 3;
+
+/// The window in which MFA failures are counted
+const MFA_FAILURE_WINDOW_MINUTES: i32 = 15;
 
 /// Checks auth, but does not ensure active sessions
 pub async fn check_auth_insecure(pool: &PgPool, token: &str) -> Result<AuthData, Error> {
@@ -79,6 +87,98 @@ pub async fn check_auth(pool: &PgPool, token: &str) -> Result<AuthData, Error> {
     Ok(rec)
 }
 
+/// Records a login-related event for a staff member, alerting staff_logs if the
+/// event comes from a new device or follows repeated MFA failures
+pub async fn record_login_event(
+    state: &AppState,
+    user_id: &str,
+    event: LoginEvent,
+    meta: &RequestMeta,
+) -> Result<(), Error> {
+    // Check for a previously seen device before inserting the new entry
+    let new_device = if event == LoginEvent::MfaSuccess {
+        let rec = sqlx::query!(
+            "SELECT COUNT(*) AS total, COUNT(*) FILTER (WHERE ip = $3 AND user_agent = $4) AS seen FROM staffpanel__login_history WHERE user_id = $1 AND event = $2",
+            user_id,
+            LoginEvent::MfaSuccess.to_string(),
+            meta.ip,
+            meta.user_agent
+        )
+        .fetch_one(&state.pool)
+        .await?;
+
+        // The first ever login is not considered to be a 'new' device
+        rec.total.unwrap_or(0) > 0 && rec.seen.unwrap_or(0) == 0
+    } else {
+        false
+    };
+
+    sqlx::query!(
+        "INSERT INTO staffpanel__login_history (user_id, event, ip, user_agent) VALUES ($1, $2, $3, $4)",
+        user_id,
+        event.to_string(),
+        meta.ip,
+        meta.user_agent
+    )
+    .execute(&state.pool)
+    .await?;
+
+    let alert = if new_device {
+        Some(
+            CreateEmbed::new()
+                .title("New Device Login")
+                .description(format!(
+                    "<@{}> logged into the staff panel from a previously unseen device",
+                    user_id
+                ))
+                .field("IP", meta.ip.clone(), true)
+                .field("User Agent", meta.user_agent.clone(), true),
+        )
+    } else if event == LoginEvent::MfaFailure {
+        let failures = sqlx::query!(
+            "SELECT COUNT(*) FROM staffpanel__login_history WHERE user_id = $1 AND event = $2 AND created_at > NOW() - make_interval(mins => $3)",
+            user_id,
+            LoginEvent::MfaFailure.to_string(),
+            MFA_FAILURE_WINDOW_MINUTES
+        )
+        .fetch_one(&state.pool)
+        .await?
+        .count
+        .unwrap_or(0);
+
+        // Only alert once when the threshold is crossed to avoid spamming staff_logs
+        if failures == MFA_FAILURE_ALERT_THRESHOLD {
+            Some(
+                CreateEmbed::new()
+                    .title("Repeated MFA Failures")
+                    .description(format!(
+                        "<@{}> has entered an invalid MFA code {} times in the last {} minutes",
+                        user_id, failures, MFA_FAILURE_WINDOW_MINUTES
+                    ))
+                    .field("IP", meta.ip.clone(), true)
+                    .field("User Agent", meta.user_agent.clone(), true),
+            )
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    if let Some(alert) = alert {
+        crate::config::CONFIG
+            .channels
+            .staff_logs
+            .send_message(
+                &state.cache_http.http,
+                CreateMessage::new().embeds(vec![alert]),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
 pub async fn get_staff_disciplinaries(
     pool: &PgPool,
     user_id: &str,
@@ -91,12 +191,19 @@ pub async fn get_staff_disciplinaries(
         title: String,
         description: String,
         r#type: String,
+        state: String,
+        issued_by: Option<String>,
+        approved_by: Option<String>,
+        revoked_by: Option<String>,
+        revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+        revoke_reason: Option<String>,
+        deny_reason: Option<String>,
     }
 
     let rec = {
         if active {
             let r = sqlx::query!(
-                "SELECT id, created_at, EXTRACT(epoch FROM expiry) as expiry, title, description, type FROM staff_disciplinary WHERE user_id = $1 AND NOW() - created_at < expiry",
+                "SELECT id, created_at, EXTRACT(epoch FROM expiry) as expiry, title, description, type, state, issued_by, approved_by, revoked_by, revoked_at, revoke_reason, deny_reason FROM staff_disciplinary WHERE user_id = $1 AND state = 'active' AND (expiry IS NULL OR NOW() - created_at < expiry)",
                 user_id
             )
             .fetch_all(pool)
@@ -115,13 +222,20 @@ pub async fn get_staff_disciplinaries(
                     title: rec.title,
                     description: rec.description,
                     r#type: rec.r#type,
+                    state: rec.state,
+                    issued_by: rec.issued_by,
+                    approved_by: rec.approved_by,
+                    revoked_by: rec.revoked_by,
+                    revoked_at: rec.revoked_at,
+                    revoke_reason: rec.revoke_reason,
+                    deny_reason: rec.deny_reason,
                 });
             }
 
             trec
         } else {
             let r = sqlx::query!(
-                "SELECT id, created_at, EXTRACT(epoch FROM expiry) as expiry, title, description, type FROM staff_disciplinary WHERE user_id = $1",
+                "SELECT id, created_at, EXTRACT(epoch FROM expiry) as expiry, title, description, type, state, issued_by, approved_by, revoked_by, revoked_at, revoke_reason, deny_reason FROM staff_disciplinary WHERE user_id = $1 ORDER BY created_at DESC",
                 user_id
             )
             .fetch_all(pool)
@@ -140,6 +254,13 @@ pub async fn get_staff_disciplinaries(
                     title: rec.title,
                     description: rec.description,
                     r#type: rec.r#type,
+                    state: rec.state,
+                    issued_by: rec.issued_by,
+                    approved_by: rec.approved_by,
+                    revoked_by: rec.revoked_by,
+                    revoked_at: rec.revoked_at,
+                    revoke_reason: rec.revoke_reason,
+                    deny_reason: rec.deny_reason,
                 });
             }
 
@@ -190,6 +311,13 @@ pub async fn get_staff_disciplinaries(
             title: disciplinary.title,
             description: disciplinary.description,
             r#type: disciplinary_type,
+            state: disciplinary.state,
+            issued_by: disciplinary.issued_by,
+            approved_by: disciplinary.approved_by,
+            revoked_by: disciplinary.revoked_by,
+            revoked_at: disciplinary.revoked_at,
+            revoke_reason: disciplinary.revoke_reason,
+            deny_reason: disciplinary.deny_reason,
         });
     }
 
@@ -252,38 +380,9 @@ pub async fn get_staff_member(
 
     let disciplinaries = get_staff_disciplinaries(pool, user_id, true).await?;
 
-    let resolved_perms = {
-        if disciplinaries.is_empty() {
-            let sp = sp.clone();
-            sp.resolve()
-        } else {
-            let mut virtual_sp = sp.clone();
-            let mut added_ids = Vec::new();
-            for disc in &disciplinaries {
-                // Add oermissions to virtual_sp as a index 0 position
-                virtual_sp.user_positions.push(PartialStaffPosition {
-                    id: disc.id.clone(),
-                    index: 0,
-                    perms: disc
-                        .r#type
-                        .perm_limits
-                        .iter()
-                        .map(|x| Permission::from_string(x))
-                        .collect::<Vec<Permission>>(),
-                });
-                added_ids.push(disc.id.clone());
-
-                if !disc.r#type.additory {
-                    // Remove all not in added_ids
-                    virtual_sp
-                        .user_positions
-                        .retain(|p| added_ids.contains(&p.id));
-                }
-            }
+    let resolved_perms = crate::impls::permissions::get_resolved_perms(pool, user_id).await?;
 
-            virtual_sp.resolve()
-        }
-    };
+    let loa = crate::impls::loa::get_current_loa(pool, user_id).await?;
 
     Ok(StaffMember {
         user_id: user_id.to_string().clone(),
@@ -295,6 +394,7 @@ pub async fn get_staff_member(
         .await?,
         positions,
         disciplinaries,
+        loa,
         perm_overrides: data.perm_overrides,
         resolved_perms_kc: resolved_perms.iter().map(|x| x.to_string()).collect(),
         resolved_perms,
@@ -1,8 +1,9 @@
 use axum::{
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::{IntoResponse, Response},
 };
 use std::fmt::Display;
+use std::net::{IpAddr, SocketAddr};
 
 pub struct Error {
     pub status: StatusCode,
@@ -28,3 +29,56 @@ pub struct AppState {
     pub cache_http: botox::cache::CacheHttpImpl,
     pub pool: sqlx::PgPool,
 }
+
+/// Information about the client making a request to the panel
+///
+/// Used for login history and new device alerts
+#[derive(Clone)]
+pub struct RequestMeta {
+    /// The IP address of the client, taking the headers of trusted proxies into account
+    pub ip: String,
+    /// The user agent of the client
+    pub user_agent: String,
+}
+
+impl RequestMeta {
+    pub fn new(headers: &HeaderMap, addr: SocketAddr) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        let trusted = &crate::config::CONFIG.panel.trusted_proxies;
+
+        let mut ip = addr.ip();
+
+        // Forwarded headers can be set by anyone, so only honour them when they come from our own proxies
+        if trusted.contains(&ip) {
+            if let Some(forwarded) = header("x-forwarded-for") {
+                // Each proxy appends the address it got the request from, the client is the right-most untrusted hop
+                for hop in forwarded.rsplit(',') {
+                    let Ok(hop) = hop.trim().parse::<IpAddr>() else {
+                        break;
+                    };
+
+                    ip = hop;
+
+                    if !trusted.contains(&hop) {
+                        break;
+                    }
+                }
+            } else if let Some(real_ip) = header("x-real-ip").and_then(|v| v.parse::<IpAddr>().ok())
+            {
+                ip = real_ip;
+            }
+        }
+
+        Self {
+            ip: ip.to_string(),
+            user_agent: header("user-agent").unwrap_or_else(|| "Unknown".to_string()),
+        }
+    }
+}
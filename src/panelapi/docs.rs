@@ -0,0 +1,856 @@
+//! OpenAPI documentation for the panel API
+//!
+//! All panel queries are sent as a `PanelQuery` to `POST /`. As OpenAPI only allows one operation per
+//! path, each `PanelQuery` variant is documented under a virtual `/#Variant` path. As URL fragments
+//! are never sent to the server, clients generated from this document will still hit `POST /`.
+//!
+//! The functions in this module only exist to carry the `utoipa::path` attributes and are never called
+#![allow(dead_code)]
+
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use utoipa::{IntoResponses, OpenApi};
+
+use super::panel_query::PanelQuery;
+use super::types::{
+    analytics::BaseAnalytics,
+    audit_log::AuditLogEntry,
+    auth::{AuthData, AuthorizeAction, MfaLogin, MfaLoginSecret},
+    blog::{BlogAction, BlogPost},
+    bot_queue::{QueuePriority, QueuedBot},
+    bot_whitelist::{BotWhitelist, BotWhitelistAction},
+    entity::{PartialBot, PartialEntity, PartialServer},
+    events::PanelEvent,
+    invite_analysis::InviteAnalysis,
+    login_history::{LoginEvent, LoginHistoryEntry},
+    metrics::PanelActionMetrics,
+    partners::{CreatePartner, Partner, PartnerAction, PartnerType, Partners},
+    permission_simulation::{
+        PermissionEffect, PermissionSimulation, PermissionSource, PermissionStep,
+        SimulatedDisciplinary, SimulatedPosition,
+    },
+    pre_review::{PreReview, PreReviewCheck},
+    review_checklists::{ReviewChecklist, ReviewChecklistAnswer, ReviewChecklistItem},
+    review_history::{ReviewHistory, ReviewHistoryEntry},
+    review_templates::{ReviewReasonTemplate, ReviewReasonTemplateAction},
+    review_transcripts::{ReviewTranscript, ReviewTranscriptMessage},
+    rpc::RPCWebAction,
+    rpclogs::RPCLogEntry,
+    shop_items::{
+        ShopCoupon, ShopCouponAction, ShopHold, ShopHoldAction, ShopItem, ShopItemAction,
+        ShopItemBenefit, ShopItemBenefitAction,
+    },
+    staff_activity::StaffActivity,
+    staff_applications::{
+        StaffApplication, StaffApplicationAction, StaffApplicationComment,
+        StaffApplicationResponse, StaffApplicationState, StaffApplicationVote,
+    },
+    staff_disciplinary::{
+        StaffDisciplinary, StaffDisciplinaryAction, StaffDisciplinaryState, StaffDisciplinaryType,
+        StaffDisciplinaryTypeAction,
+    },
+    staff_loa::{StaffLoa, StaffLoaAction, StaffLoaState},
+    staff_members::{StaffMember, StaffMemberAction},
+    staff_onboardings::{
+        StaffOnboarding, StaffOnboardingAction, StaffOnboardingResponse, StaffOnboardingState,
+    },
+    staff_positions::{StaffPosition, StaffPositionAction},
+    staff_resync::{StaffResyncHold, StaffResyncHoldAction},
+    staff_timeline::{StaffTimelineEvent, StaffTimelineEventKind},
+    vote_credit_tiers::{VoteCreditTier, VoteCreditTierAction},
+    webcore::{CoreConstants, Hello, InstanceConfig, PanelServers, StartAuth},
+};
+use crate::impls::{dovewing::PlatformUser, link::Link, target_types::TargetType};
+use crate::rpc::core::{FieldType, RPCField, RPCMethod};
+
+/// Error responses shared by all panel queries
+///
+/// Errors are always returned as a plain text message
+#[derive(IntoResponses)]
+enum ErrorResponses {
+    /// The request was invalid, the body contains the reason
+    #[response(status = 400, content_type = "text/plain")]
+    BadRequest(String),
+    /// The staff member does not have the permission needed, the body contains the permission
+    #[response(status = 403, content_type = "text/plain")]
+    Forbidden(String),
+    /// An internal error occurred, the body contains the error. Authentication errors such as
+    /// `identityExpired` and `sessionNotActive` are also returned with this status
+    #[response(status = 500, content_type = "text/plain")]
+    InternalError(String),
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::server::query,
+        super::events::events,
+        authorize_begin,
+        authorize_create_session,
+        authorize_check_mfa_state,
+        authorize_reset_mfa_totp,
+        authorize_activate_session,
+        authorize_logout,
+        hello,
+        base_analytics,
+        get_user,
+        get_login_history,
+        get_audit_log,
+        get_panel_metrics,
+        simulate_permissions,
+        get_staff_activity,
+        get_staff_timeline,
+        bot_queue,
+        get_review_checklists,
+        get_review_transcripts,
+        execute_rpc,
+        get_rpc_methods,
+        get_rpc_log_entries,
+        search_entitys,
+        update_partners,
+        update_blog,
+        update_staff_positions,
+        update_staff_members,
+        update_staff_disciplinary_type,
+        update_staff_disciplinaries,
+        update_staff_onboardings,
+        update_staff_onboardings_start,
+        update_staff_loas,
+        update_staff_loas_request,
+        update_staff_applications,
+        update_staff_resync_holds,
+        update_vote_credit_tiers,
+        update_shop_items,
+        update_shop_item_benefits,
+        update_shop_coupons,
+        update_shop_holds,
+        update_bot_whitelist,
+        update_review_reason_templates,
+        update_review_reason_templates_render,
+    ),
+    components(schemas(
+        PanelQuery,
+        InstanceConfig,
+        CoreConstants,
+        PanelServers,
+        Hello,
+        StartAuth,
+        AuthData,
+        MfaLogin,
+        MfaLoginSecret,
+        BaseAnalytics,
+        PlatformUser,
+        RPCMethod,
+        RPCWebAction,
+        RPCField,
+        FieldType,
+        RPCLogEntry,
+        TargetType,
+        PartialEntity,
+        PartialBot,
+        QueuedBot,
+        QueuePriority,
+        PreReview,
+        PreReviewCheck,
+        InviteAnalysis,
+        ReviewChecklist,
+        ReviewChecklistAnswer,
+        ReviewChecklistItem,
+        ReviewHistory,
+        ReviewHistoryEntry,
+        ReviewTranscript,
+        ReviewTranscriptMessage,
+        PartialServer,
+        PartnerAction,
+        CreatePartner,
+        Partner,
+        PartnerType,
+        Partners,
+        AuthorizeAction,
+        BlogAction,
+        BlogPost,
+        StaffPositionAction,
+        StaffPosition,
+        StaffMemberAction,
+        StaffMember,
+        StaffDisciplinaryTypeAction,
+        StaffDisciplinaryType,
+        StaffDisciplinary,
+        StaffDisciplinaryAction,
+        StaffDisciplinaryState,
+        StaffOnboarding,
+        StaffOnboardingResponse,
+        StaffOnboardingState,
+        StaffOnboardingAction,
+        StaffLoa,
+        StaffLoaState,
+        StaffLoaAction,
+        StaffApplication,
+        StaffApplicationResponse,
+        StaffApplicationVote,
+        StaffApplicationComment,
+        StaffApplicationState,
+        StaffApplicationAction,
+        StaffResyncHold,
+        StaffResyncHoldAction,
+        StaffTimelineEvent,
+        StaffTimelineEventKind,
+        VoteCreditTierAction,
+        VoteCreditTier,
+        ShopItem,
+        ShopItemAction,
+        ShopItemBenefit,
+        ShopItemBenefitAction,
+        ShopCoupon,
+        ShopCouponAction,
+        ShopHold,
+        ShopHoldAction,
+        BotWhitelist,
+        BotWhitelistAction,
+        ReviewReasonTemplate,
+        ReviewReasonTemplateAction,
+        Link,
+        LoginEvent,
+        LoginHistoryEntry,
+        PanelEvent,
+        PanelActionMetrics,
+        SimulatedPosition,
+        SimulatedDisciplinary,
+        PermissionEffect,
+        PermissionStep,
+        PermissionSource,
+        PermissionSimulation,
+        StaffActivity,
+        AuditLogEntry,
+    ))
+)]
+pub struct ApiDoc;
+
+/// Returns the OpenAPI document of the panel API
+pub async fn docs() -> impl IntoResponse {
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-Type", "application/json".parse().unwrap());
+    let data = ApiDoc::openapi().to_json();
+
+    if let Ok(data) = data {
+        return (headers, data).into_response();
+    }
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Failed to generate docs".to_string(),
+    )
+        .into_response()
+}
+
+/// Authorize (Begin)
+///
+/// Begins an authorization request, returning the login url
+#[utoipa::path(
+    post,
+    path = "/#Authorize/Begin",
+    tag = "Authorize",
+    request_body(content = PanelQuery, description = "`PanelQuery::Authorize` with `AuthorizeAction::Begin`"),
+    responses(
+        (status = 200, description = "The login data", body = StartAuth),
+        ErrorResponses,
+    ),
+)]
+fn authorize_begin() {}
+
+/// Authorize (CreateSession)
+///
+/// Creates a new pending session, returning the login token
+#[utoipa::path(
+    post,
+    path = "/#Authorize/CreateSession",
+    tag = "Authorize",
+    request_body(content = PanelQuery, description = "`PanelQuery::Authorize` with `AuthorizeAction::CreateSession`"),
+    responses(
+        (status = 200, description = "The login token", body = String, content_type = "text/plain"),
+        ErrorResponses,
+    ),
+)]
+fn authorize_create_session() {}
+
+/// Authorize (CheckMfaState)
+///
+/// Returns MFA setup information if MFA has not yet been set up
+#[utoipa::path(
+    post,
+    path = "/#Authorize/CheckMfaState",
+    tag = "Authorize",
+    request_body(content = PanelQuery, description = "`PanelQuery::Authorize` with `AuthorizeAction::CheckMfaState`"),
+    responses(
+        (status = 200, description = "The MFA state", body = MfaLogin),
+        ErrorResponses,
+    ),
+)]
+fn authorize_check_mfa_state() {}
+
+/// Authorize (ResetMfaTotp)
+///
+/// Resets the MFA of the staff member, revoking all sessions
+#[utoipa::path(
+    post,
+    path = "/#Authorize/ResetMfaTotp",
+    tag = "Authorize",
+    request_body(content = PanelQuery, description = "`PanelQuery::Authorize` with `AuthorizeAction::ResetMfaTotp`"),
+    responses(
+        (status = 204, description = "MFA was reset"),
+        ErrorResponses,
+    ),
+)]
+fn authorize_reset_mfa_totp() {}
+
+/// Authorize (ActivateSession)
+///
+/// Activates a pending session using an MFA code
+#[utoipa::path(
+    post,
+    path = "/#Authorize/ActivateSession",
+    tag = "Authorize",
+    request_body(content = PanelQuery, description = "`PanelQuery::Authorize` with `AuthorizeAction::ActivateSession`"),
+    responses(
+        (status = 204, description = "The session was activated"),
+        ErrorResponses,
+    ),
+)]
+fn authorize_activate_session() {}
+
+/// Authorize (Logout)
+///
+/// Logs out a session
+#[utoipa::path(
+    post,
+    path = "/#Authorize/Logout",
+    tag = "Authorize",
+    request_body(content = PanelQuery, description = "`PanelQuery::Authorize` with `AuthorizeAction::Logout`"),
+    responses(
+        (status = 200, description = "The number of sessions logged out", body = String, content_type = "text/plain"),
+        ErrorResponses,
+    ),
+)]
+fn authorize_logout() {}
+
+/// Hello
+///
+/// Returns configuration data for the panel
+#[utoipa::path(
+    post,
+    path = "/#Hello",
+    request_body(content = PanelQuery, description = "`PanelQuery::Hello`"),
+    responses(
+        (status = 200, description = "Configuration data for the panel", body = Hello),
+        ErrorResponses,
+    ),
+)]
+fn hello() {}
+
+/// BaseAnalytics
+///
+/// Returns base analytics
+#[utoipa::path(
+    post,
+    path = "/#BaseAnalytics",
+    request_body(content = PanelQuery, description = "`PanelQuery::BaseAnalytics`"),
+    responses(
+        (status = 200, description = "Base analytics", body = BaseAnalytics),
+        ErrorResponses,
+    ),
+)]
+fn base_analytics() {}
+
+/// GetUser
+///
+/// Returns user information given a user id
+#[utoipa::path(
+    post,
+    path = "/#GetUser",
+    request_body(content = PanelQuery, description = "`PanelQuery::GetUser`"),
+    responses(
+        (status = 200, description = "The user", body = PlatformUser),
+        ErrorResponses,
+    ),
+)]
+fn get_user() {}
+
+/// GetLoginHistory
+///
+/// Returns the login history of a staff member
+#[utoipa::path(
+    post,
+    path = "/#GetLoginHistory",
+    request_body(content = PanelQuery, description = "`PanelQuery::GetLoginHistory`"),
+    responses(
+        (status = 200, description = "The login history, newest first", body = Vec<LoginHistoryEntry>),
+        ErrorResponses,
+    ),
+)]
+fn get_login_history() {}
+
+/// GetAuditLog
+///
+/// Returns entries from the audit log of panel mutations
+#[utoipa::path(
+    post,
+    path = "/#GetAuditLog",
+    request_body(content = PanelQuery, description = "`PanelQuery::GetAuditLog`"),
+    responses(
+        (status = 200, description = "The matching audit log entries, newest first", body = Vec<AuditLogEntry>),
+        ErrorResponses,
+    ),
+)]
+fn get_audit_log() {}
+
+/// GetPanelMetrics
+///
+/// Returns the metrics of each panel action since startup
+#[utoipa::path(
+    post,
+    path = "/#GetPanelMetrics",
+    request_body(content = PanelQuery, description = "`PanelQuery::GetPanelMetrics`"),
+    responses(
+        (status = 200, description = "The metrics of each panel action, sorted by name", body = Vec<PanelActionMetrics>),
+        ErrorResponses,
+    ),
+)]
+fn get_panel_metrics() {}
+
+/// SimulatePermissions
+///
+/// Resolves the permissions of a staff member or a hypothetical set of positions, explaining where each permission came from
+#[utoipa::path(
+    post,
+    path = "/#SimulatePermissions",
+    request_body(content = PanelQuery, description = "`PanelQuery::SimulatePermissions`"),
+    responses(
+        (status = 200, description = "The simulated permissions", body = PermissionSimulation),
+        ErrorResponses,
+    ),
+)]
+fn simulate_permissions() {}
+
+/// GetStaffActivity
+///
+/// Returns the activity of staff members over a rolling window
+#[utoipa::path(
+    post,
+    path = "/#GetStaffActivity",
+    request_body(content = PanelQuery, description = "`PanelQuery::GetStaffActivity`"),
+    responses(
+        (status = 200, description = "The activity of each staff member", body = Vec<StaffActivity>),
+        ErrorResponses,
+    ),
+)]
+fn get_staff_activity() {}
+
+/// GetStaffTimeline
+///
+/// Returns the timeline of changes made to a staff member, oldest first
+#[utoipa::path(
+    post,
+    path = "/#GetStaffTimeline",
+    request_body(content = PanelQuery, description = "`PanelQuery::GetStaffTimeline`"),
+    responses(
+        (status = 200, description = "The timeline of the staff member", body = Vec<StaffTimelineEvent>),
+        ErrorResponses,
+    ),
+)]
+fn get_staff_timeline() {}
+
+/// BotQueue
+///
+/// Returns the bot queue ordered by priority
+#[utoipa::path(
+    post,
+    path = "/#BotQueue",
+    request_body(content = PanelQuery, description = "`PanelQuery::BotQueue`"),
+    responses(
+        (status = 200, description = "The bots in the queue, highest priority first", body = Vec<QueuedBot>),
+        ErrorResponses,
+    ),
+)]
+fn bot_queue() {}
+
+/// GetReviewChecklists
+///
+/// Returns the review checklists filled in when approving or denying bots, newest first
+#[utoipa::path(
+    post,
+    path = "/#GetReviewChecklists",
+    request_body(content = PanelQuery, description = "`PanelQuery::GetReviewChecklists`"),
+    responses(
+        (status = 200, description = "The matching review checklists", body = Vec<ReviewChecklist>),
+        ErrorResponses,
+    ),
+)]
+fn get_review_checklists() {}
+
+/// GetReviewTranscripts
+///
+/// Returns the archived transcripts of private review channels, newest first
+#[utoipa::path(
+    post,
+    path = "/#GetReviewTranscripts",
+    request_body(content = PanelQuery, description = "`PanelQuery::GetReviewTranscripts`"),
+    responses(
+        (status = 200, description = "The matching review transcripts", body = Vec<ReviewTranscript>),
+        ErrorResponses,
+    ),
+)]
+fn get_review_transcripts() {}
+
+/// ExecuteRpc
+///
+/// Executes an RPC on a target
+#[utoipa::path(
+    post,
+    path = "/#ExecuteRpc",
+    request_body(content = PanelQuery, description = "`PanelQuery::ExecuteRpc`"),
+    responses(
+        (status = 200, description = "The RPC method returned content", body = String, content_type = "text/plain"),
+        (status = 204, description = "The RPC method succeeded with no content"),
+        ErrorResponses,
+    ),
+)]
+fn execute_rpc() {}
+
+/// GetRpcMethods
+///
+/// Returns all RPC actions available
+#[utoipa::path(
+    post,
+    path = "/#GetRpcMethods",
+    request_body(content = PanelQuery, description = "`PanelQuery::GetRpcMethods`"),
+    responses(
+        (status = 200, description = "The RPC actions", body = Vec<RPCWebAction>),
+        ErrorResponses,
+    ),
+)]
+fn get_rpc_methods() {}
+
+/// GetRpcLogEntries
+///
+/// Returns all RPC log entries
+#[utoipa::path(
+    post,
+    path = "/#GetRpcLogEntries",
+    request_body(content = PanelQuery, description = "`PanelQuery::GetRpcLogEntries`"),
+    responses(
+        (status = 200, description = "The RPC log entries, newest first", body = Vec<RPCLogEntry>),
+        ErrorResponses,
+    ),
+)]
+fn get_rpc_log_entries() {}
+
+/// SearchEntitys
+///
+/// Searches for an entity based on a query
+#[utoipa::path(
+    post,
+    path = "/#SearchEntitys",
+    request_body(content = PanelQuery, description = "`PanelQuery::SearchEntitys`"),
+    responses(
+        (status = 200, description = "The matching entities", body = Vec<PartialEntity>),
+        ErrorResponses,
+    ),
+)]
+fn search_entitys() {}
+
+/// UpdatePartners
+///
+/// Updates/handles partners
+#[utoipa::path(
+    post,
+    path = "/#UpdatePartners",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdatePartners`"),
+    responses(
+        (status = 200, description = "`List`: the partners and partner types", body = Partners),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_partners() {}
+
+/// UpdateBlog
+///
+/// Updates/handles the blog of the list
+#[utoipa::path(
+    post,
+    path = "/#UpdateBlog",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateBlog`"),
+    responses(
+        (status = 200, description = "`ListEntries`: the blog posts", body = Vec<BlogPost>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_blog() {}
+
+/// UpdateStaffPositions
+///
+/// Fetch and modify staff positions
+#[utoipa::path(
+    post,
+    path = "/#UpdateStaffPositions",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateStaffPositions`"),
+    responses(
+        (status = 200, description = "`ListPositions`: the staff positions", body = Vec<StaffPosition>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_staff_positions() {}
+
+/// UpdateStaffMembers
+///
+/// Fetch and modify staff members
+#[utoipa::path(
+    post,
+    path = "/#UpdateStaffMembers",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateStaffMembers`"),
+    responses(
+        (status = 200, description = "`ListMembers`: the staff members", body = Vec<StaffMember>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_staff_members() {}
+
+/// UpdateStaffDisciplinaryType
+///
+/// Fetch and update staff disciplinary types
+#[utoipa::path(
+    post,
+    path = "/#UpdateStaffDisciplinaryType",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateStaffDisciplinaryType`"),
+    responses(
+        (status = 200, description = "`ListDisciplinaryTypes`: the disciplinary types", body = Vec<StaffDisciplinaryType>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_staff_disciplinary_type() {}
+
+/// UpdateStaffDisciplinaries
+///
+/// Fetch, issue, approve and revoke staff disciplinary actions
+#[utoipa::path(
+    post,
+    path = "/#UpdateStaffDisciplinaries",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateStaffDisciplinaries`"),
+    responses(
+        (status = 200, description = "`ListDisciplinaries`, `ListPending`: the disciplinary actions", body = Vec<StaffDisciplinary>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_staff_disciplinaries() {}
+
+/// UpdateStaffOnboardings
+///
+/// Fetch, start, grade, complete and void staff onboardings
+#[utoipa::path(
+    post,
+    path = "/#UpdateStaffOnboardings",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateStaffOnboardings`"),
+    responses(
+        (status = 200, description = "`ListOnboardings`, `ListSubmitted`: the onboardings", body = Vec<StaffOnboarding>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_staff_onboardings() {}
+
+/// UpdateStaffOnboardings (StartOnboarding)
+///
+/// Starts an onboarding, returning it
+#[utoipa::path(
+    post,
+    path = "/#UpdateStaffOnboardings/StartOnboarding",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateStaffOnboardings` with `StaffOnboardingAction::StartOnboarding`"),
+    responses(
+        (status = 200, description = "The started onboarding", body = StaffOnboarding),
+        ErrorResponses,
+    ),
+)]
+fn update_staff_onboardings_start() {}
+
+/// UpdateStaffLoas
+///
+/// Fetch, request, approve and cancel staff leaves of absence
+#[utoipa::path(
+    post,
+    path = "/#UpdateStaffLoas",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateStaffLoas`"),
+    responses(
+        (status = 200, description = "`ListLoas`, `ListPending`: the leaves of absence", body = Vec<StaffLoa>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_staff_loas() {}
+
+/// UpdateStaffLoas (RequestLoa)
+///
+/// Requests a leave of absence, returning it
+#[utoipa::path(
+    post,
+    path = "/#UpdateStaffLoas/RequestLoa",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateStaffLoas` with `StaffLoaAction::RequestLoa`"),
+    responses(
+        (status = 200, description = "The requested leave of absence", body = StaffLoa),
+        ErrorResponses,
+    ),
+)]
+fn update_staff_loas_request() {}
+
+/// UpdateStaffApplications
+///
+/// Fetch, vote on, comment on, accept and reject staff applications
+#[utoipa::path(
+    post,
+    path = "/#UpdateStaffApplications",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateStaffApplications`"),
+    responses(
+        (status = 200, description = "`ListApplications`, `ListPending`: the applications", body = Vec<StaffApplication>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_staff_applications() {}
+
+/// UpdateStaffResyncHolds
+///
+/// Fetch, confirm and reject position removals held by staff resync
+#[utoipa::path(
+    post,
+    path = "/#UpdateStaffResyncHolds",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateStaffResyncHolds`"),
+    responses(
+        (status = 200, description = "`ListHolds`: the held position removals", body = Vec<StaffResyncHold>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_staff_resync_holds() {}
+
+/// UpdateVoteCreditTiers
+///
+/// Fetch and update/modify vote credit tiers
+#[utoipa::path(
+    post,
+    path = "/#UpdateVoteCreditTiers",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateVoteCreditTiers`"),
+    responses(
+        (status = 200, description = "`ListTiers`: the vote credit tiers", body = Vec<VoteCreditTier>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_vote_credit_tiers() {}
+
+/// UpdateShopItems
+///
+/// Fetch and update/modify shop items
+#[utoipa::path(
+    post,
+    path = "/#UpdateShopItems",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateShopItems`"),
+    responses(
+        (status = 200, description = "`List`: the shop items", body = Vec<ShopItem>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_shop_items() {}
+
+/// UpdateShopItemBenefits
+///
+/// Fetch and update/modify shop item benefits
+#[utoipa::path(
+    post,
+    path = "/#UpdateShopItemBenefits",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateShopItemBenefits`"),
+    responses(
+        (status = 200, description = "`List`: the shop item benefits", body = Vec<ShopItemBenefit>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_shop_item_benefits() {}
+
+/// UpdateShopCoupons
+///
+/// Fetch and update/modify shop coupons
+#[utoipa::path(
+    post,
+    path = "/#UpdateShopCoupons",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateShopCoupons`"),
+    responses(
+        (status = 200, description = "`List`: the shop coupons", body = Vec<ShopCoupon>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_shop_coupons() {}
+
+/// UpdateShopHolds
+///
+/// Fetch and update/modify shop holds
+#[utoipa::path(
+    post,
+    path = "/#UpdateShopHolds",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateShopHolds`"),
+    responses(
+        (status = 200, description = "`List`: the shop holds", body = Vec<ShopHold>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_shop_holds() {}
+
+/// UpdateBotWhitelist
+///
+/// Fetch and update/modify bot whitelist
+#[utoipa::path(
+    post,
+    path = "/#UpdateBotWhitelist",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateBotWhitelist`"),
+    responses(
+        (status = 200, description = "`List`: the whitelisted bots", body = Vec<BotWhitelist>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_bot_whitelist() {}
+
+/// UpdateReviewReasonTemplates
+///
+/// Fetch, update and render the reason templates used when denying or unverifying bots
+#[utoipa::path(
+    post,
+    path = "/#UpdateReviewReasonTemplates",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateReviewReasonTemplates`"),
+    responses(
+        (status = 200, description = "`List`: the reason templates", body = Vec<ReviewReasonTemplate>),
+        (status = 204, description = "The action was successful"),
+        ErrorResponses,
+    ),
+)]
+fn update_review_reason_templates() {}
+
+/// UpdateReviewReasonTemplates (Render)
+///
+/// Combines reason templates and a custom note, returning the reason to submit with the RPC method
+#[utoipa::path(
+    post,
+    path = "/#UpdateReviewReasonTemplates/Render",
+    request_body(content = PanelQuery, description = "`PanelQuery::UpdateReviewReasonTemplates` with `ReviewReasonTemplateAction::Render`"),
+    responses(
+        (status = 200, description = "The combined reason", body = String),
+        ErrorResponses,
+    ),
+)]
+fn update_review_reason_templates_render() {}
@@ -0,0 +1,171 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::{Query, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use futures_util::stream::{self, Stream};
+use kittycat::perms::{self, Permission};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use tokio::sync::broadcast;
+use tokio::time::{interval_at, Instant, Interval, MissedTickBehavior};
+
+use super::auth::check_auth;
+use super::core::{AppState, Error};
+pub use super::types::events::PanelEvent;
+use crate::impls::permissions::get_resolved_perms;
+
+/// How many events can be buffered per subscriber before it starts lagging
+const EVENT_BUFFER: usize = 512;
+
+/// How often the session of a subscriber is revalidated
+const REVALIDATE_INTERVAL: Duration = Duration::from_secs(60);
+
+static EVENTS: Lazy<broadcast::Sender<PanelEvent>> =
+    Lazy::new(|| broadcast::channel(EVENT_BUFFER).0);
+
+/// Publishes an event to all connected panel clients
+///
+/// This never fails, events are simply dropped if nobody is listening
+pub fn publish(event: PanelEvent) {
+    let _ = EVENTS.send(event);
+}
+
+#[derive(Deserialize)]
+pub struct EventsQuery {
+    /// Login token
+    ///
+    /// Passed as a query parameter as `EventSource` does not support custom headers
+    login_token: String,
+}
+
+struct Subscriber {
+    state: Arc<AppState>,
+    login_token: String,
+    user_perms: Vec<Permission>,
+    revalidate_ticker: Interval,
+    expired: bool,
+    rx: broadcast::Receiver<PanelEvent>,
+}
+
+impl Subscriber {
+    /// Revalidates the session of the subscriber, refreshing their permissions
+    ///
+    /// Returns false if the session is no longer valid
+    async fn revalidate(&mut self) -> bool {
+        let Ok(auth_data) = check_auth(&self.state.pool, &self.login_token).await else {
+            return false;
+        };
+
+        match get_resolved_perms(&self.state.pool, &auth_data.user_id).await {
+            Ok(perms) => self.user_perms = perms,
+            Err(e) => {
+                log::error!("Failed to refresh perms of event subscriber: {}", e);
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns the next event the subscriber can see, or None if the stream should end
+    async fn next_event(&mut self) -> Option<Event> {
+        loop {
+            // Sessions are revalidated on a timer so idle streams do not outlive their login token
+            let event = tokio::select! {
+                _ = self.revalidate_ticker.tick() => {
+                    if !self.revalidate().await {
+                        self.expired = true;
+                        return Some(Event::default().event("SessionExpired").data(""));
+                    }
+
+                    continue;
+                }
+                res = self.rx.recv() => match res {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        // Let the client know that it should refetch its state
+                        return Some(Event::default().event("Lagged").data(skipped.to_string()));
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                },
+            };
+
+            if let Some(perm) = event.required_perm() {
+                if !perms::has_perm(&self.user_perms, &perm.into()) {
+                    continue;
+                }
+            }
+
+            match Event::default().event(event.name()).json_data(&event) {
+                Ok(sse_event) => return Some(sse_event),
+                Err(e) => {
+                    log::error!("Failed to serialize panel event: {}", e);
+                    continue;
+                }
+            }
+        }
+    }
+}
+
+/// Streams panel events to an authenticated staff member using Server-Sent Events
+///
+/// Events the staff member does not have permission to see are filtered out. A `SessionExpired`
+/// event is sent (and the stream closed) once the login token is no longer valid
+#[utoipa::path(
+    get,
+    path = "/events",
+    params(
+        ("login_token" = String, Query, description = "Login token"),
+    ),
+    responses(
+        (status = 200, description = "A stream of panel events, the SSE event name is the variant name", body = PanelEvent, content_type = "text/event-stream"),
+        (status = INTERNAL_SERVER_ERROR, description = "An internal or authentication error occured", body = String, content_type = "text/plain"),
+    ),
+)]
+pub async fn events(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<EventsQuery>,
+) -> Result<Response, Error> {
+    let auth_data = check_auth(&state.pool, &query.login_token)
+        .await
+        .map_err(Error::new)?;
+
+    let user_perms = get_resolved_perms(&state.pool, &auth_data.user_id)
+        .await
+        .map_err(Error::new)?;
+
+    let mut revalidate_ticker =
+        interval_at(Instant::now() + REVALIDATE_INTERVAL, REVALIDATE_INTERVAL);
+    revalidate_ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let subscriber = Subscriber {
+        state,
+        login_token: query.login_token,
+        user_perms,
+        revalidate_ticker,
+        expired: false,
+        rx: EVENTS.subscribe(),
+    };
+
+    Ok(Sse::new(event_stream(subscriber))
+        .keep_alive(KeepAlive::default())
+        .into_response())
+}
+
+fn event_stream(subscriber: Subscriber) -> impl Stream<Item = Result<Event, Infallible>> {
+    stream::unfold(Some(subscriber), |subscriber| async move {
+        let mut subscriber = subscriber?;
+
+        let event = subscriber.next_event().await?;
+
+        // Close the stream after telling the client its session expired
+        if subscriber.expired {
+            return Some((Ok(event), None));
+        }
+
+        Some((Ok(event), Some(subscriber)))
+    })
+}
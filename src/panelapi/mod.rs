@@ -1,6 +1,10 @@
 mod actions;
+mod audit;
 mod auth;
 mod core;
+mod docs;
+pub mod events;
 pub mod panel_query;
+mod registry;
 pub mod server;
-mod types;
+pub mod types;
@@ -6,8 +6,14 @@ use crate::panelapi::types::{
     blog::BlogAction,
     bot_whitelist::BotWhitelistAction,
     partners::PartnerAction,
+    permission_simulation::{SimulatedDisciplinary, SimulatedPosition},
+    review_templates::ReviewReasonTemplateAction,
     shop_items::{ShopCouponAction, ShopHoldAction, ShopItemAction, ShopItemBenefitAction},
-    staff_disciplinary::StaffDisciplinaryTypeAction,
+    staff_applications::StaffApplicationAction,
+    staff_disciplinary::{StaffDisciplinaryAction, StaffDisciplinaryTypeAction},
+    staff_loa::StaffLoaAction,
+    staff_onboardings::StaffOnboardingAction,
+    staff_resync::StaffResyncHoldAction,
     vote_credit_tiers::VoteCreditTierAction,
 };
 use crate::rpc::core::RPCMethod;
@@ -45,6 +51,74 @@ pub enum PanelQuery {
         /// User ID to fetch details for
         user_id: String,
     },
+    /// Returns the login history (session creations, MFA attempts and logouts) of a staff member
+    ///
+    /// Viewing the login history of another staff member requires the `login_history.view` permission
+    GetLoginHistory {
+        /// Login token
+        login_token: String,
+        /// User ID to fetch the login history of, defaults to the current user
+        user_id: Option<String>,
+    },
+    /// Returns entries from the audit log of panel mutations, newest first
+    ///
+    /// All filters are optional
+    GetAuditLog {
+        /// Login token
+        login_token: String,
+        /// Only return entries created by this user
+        user_id: Option<String>,
+        /// Only return entries whose action starts with this (e.g. `UpdatePartners` or `UpdatePartners.Create`)
+        action: Option<String>,
+        /// Only return entries for this entity type (the table the entity is stored in, e.g. `partners`)
+        entity_type: Option<String>,
+        /// Only return entries affecting this entity ID
+        entity_id: Option<String>,
+        /// The number of entries to skip
+        offset: Option<i64>,
+    },
+    /// Returns the number of calls, errors and average duration of each panel action since startup
+    GetPanelMetrics {
+        /// Login token
+        login_token: String,
+    },
+    /// Resolves the permissions of a staff member or a hypothetical set of positions, permission
+    /// overrides and disciplinary actions, explaining where each permission came from
+    ///
+    /// If a user ID is given, anything not given is taken from the member and the result is
+    /// diffed against their current permissions
+    SimulatePermissions {
+        /// Login token
+        login_token: String,
+        /// The staff member to simulate
+        user_id: Option<String>,
+        /// The positions to simulate, replacing the positions of the member
+        positions: Option<Vec<SimulatedPosition>>,
+        /// The permission overrides to simulate, replacing the overrides of the member
+        perm_overrides: Option<Vec<String>>,
+        /// The active disciplinary actions to simulate, replacing the disciplinaries of the member
+        disciplinaries: Option<Vec<SimulatedDisciplinary>>,
+    },
+    /// Returns the activity of staff members over a rolling window, flagged against the
+    /// configured inactivity thresholds
+    ///
+    /// Staff members can always view their own activity, viewing the activity of others needs the
+    /// `staff_activity.view` permission
+    GetStaffActivity {
+        /// Login token
+        login_token: String,
+        /// Only return the activity of this staff member, defaults to all staff members
+        user_id: Option<String>,
+        /// The number of days to compute activity over, defaults to the configured window
+        window_days: Option<i32>,
+    },
+    /// Returns the timeline of changes made to a staff member, oldest first
+    GetStaffTimeline {
+        /// Login token
+        login_token: String,
+        /// The staff member to return the timeline of, defaults to the current user
+        user_id: Option<String>,
+    },
     /// Returns the bot queue
     ///
     /// This is public to all staff members
@@ -52,6 +126,30 @@ pub enum PanelQuery {
         /// Login token
         login_token: String,
     },
+    /// Returns the review checklists filled in when approving or denying bots, newest first
+    GetReviewChecklists {
+        /// Login token
+        login_token: String,
+        /// Only return checklists of this bot
+        bot_id: Option<String>,
+        /// Only return checklists filled in by this reviewer
+        user_id: Option<String>,
+        /// Only return checklists where the item with this ID was not passed
+        failed_item: Option<String>,
+        /// The number of checklists to skip
+        offset: Option<i64>,
+    },
+    /// Returns the archived transcripts of private review channels, newest first
+    GetReviewTranscripts {
+        /// Login token
+        login_token: String,
+        /// Only return transcripts of this bot
+        bot_id: Option<String>,
+        /// Only return transcripts of this reviewer
+        user_id: Option<String>,
+        /// The number of transcripts to skip
+        offset: Option<i64>,
+    },
     /// Executes an RPC on a target
     ///
     /// The endpoint itself is public to all staff members however RPC will only execute if the user has permission for the RPC method
@@ -125,6 +223,41 @@ pub enum PanelQuery {
         /// Action
         action: StaffDisciplinaryTypeAction,
     },
+    /// Fetch, issue, approve and revoke staff disciplinary actions
+    UpdateStaffDisciplinaries {
+        /// Login token
+        login_token: String,
+        /// Action
+        action: StaffDisciplinaryAction,
+    },
+    /// Fetch, start, grade, complete and void staff onboardings
+    UpdateStaffOnboardings {
+        /// Login token
+        login_token: String,
+        /// Action
+        action: StaffOnboardingAction,
+    },
+    /// Fetch, request, approve and cancel staff leaves of absence
+    UpdateStaffLoas {
+        /// Login token
+        login_token: String,
+        /// Action
+        action: StaffLoaAction,
+    },
+    /// Fetch, vote on, comment on, accept and reject staff applications
+    UpdateStaffApplications {
+        /// Login token
+        login_token: String,
+        /// Action
+        action: StaffApplicationAction,
+    },
+    /// Fetch, confirm and reject position removals held by staff resync
+    UpdateStaffResyncHolds {
+        /// Login token
+        login_token: String,
+        /// Action
+        action: StaffResyncHoldAction,
+    },
     /// Fetch and update/modify vote credit tiers
     UpdateVoteCreditTiers {
         /// Login token
@@ -167,4 +300,11 @@ pub enum PanelQuery {
         /// Action
         action: BotWhitelistAction,
     },
+    /// Fetch, update and render the reason templates used when denying or unverifying bots
+    UpdateReviewReasonTemplates {
+        /// Login token
+        login_token: String,
+        /// Action
+        action: ReviewReasonTemplateAction,
+    },
 }
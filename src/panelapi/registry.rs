@@ -0,0 +1,538 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::Instant;
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use futures_util::future::BoxFuture;
+use kittycat::perms::{self, Permission};
+use once_cell::sync::Lazy;
+
+use super::actions;
+use super::audit::{self, AuditTarget};
+use super::auth::{check_auth, check_auth_insecure};
+use super::core::{AppState, Error, RequestMeta};
+use super::panel_query::PanelQuery;
+use super::types::auth::{AuthData, AuthorizeAction};
+use super::types::metrics::PanelActionMetrics;
+use crate::impls::permissions::get_resolved_perms;
+
+/// The level of authentication a panel action needs
+#[derive(Clone, Copy, PartialEq)]
+pub enum AuthLevel {
+    /// No authentication is needed, e.g. to begin a login
+    None,
+    /// A session is needed, but it does not need to be active (MFA verified) yet
+    Insecure,
+    /// An active session is needed
+    Active,
+}
+
+/// A permission needed to perform a panel action
+pub struct RequiredPerm {
+    /// The permission needed
+    pub perm: &'static str,
+    /// What the permission allows doing, used in the error message (e.g. `create blog entries`)
+    pub description: &'static str,
+}
+
+impl RequiredPerm {
+    pub const fn new(perm: &'static str, description: &'static str) -> Self {
+        Self { perm, description }
+    }
+}
+
+/// Context passed to panel actions
+pub struct ActionContext<'a> {
+    /// The state of the panel API
+    pub state: &'a AppState,
+    /// Information about the client making the request
+    pub meta: &'a RequestMeta,
+    /// The auth data of the session, `None` if the action has an auth level of `AuthLevel::None`
+    pub auth_data: Option<AuthData>,
+    /// The resolved permissions of the staff member, empty if unauthenticated
+    pub user_perms: Vec<Permission>,
+    /// The IDs of the entities created by the action, see `ActionContext::created`
+    created_ids: Mutex<Vec<String>>,
+}
+
+impl ActionContext<'_> {
+    /// Returns the auth data of the session, erroring if the action is unauthenticated
+    pub fn auth_data(&self) -> Result<&AuthData, Error> {
+        self.auth_data
+            .as_ref()
+            .ok_or_else(|| Error::new("This action requires authentication"))
+    }
+
+    /// Returns whether or not the staff member has a permission
+    pub fn has_perm(&self, perm: &str) -> bool {
+        perms::has_perm(&self.user_perms, &perm.into())
+    }
+
+    /// Reports the ID of an entity created by the action
+    ///
+    /// Actions creating entities with database-generated IDs must call this so the created
+    /// entity can be snapshotted for the audit log
+    pub fn created(&self, id: impl ToString) {
+        if let Ok(mut created_ids) = self.created_ids.lock() {
+            created_ids.push(id.to_string());
+        }
+    }
+}
+
+/// A panel action, this is what every `PanelQuery` is converted into before being handled
+///
+/// Authentication, permission checks, auditing and metrics are all handled by `dispatch`. Actions
+/// only need to perform checks that cannot be expressed as a single permission (such as hierarchy
+/// checks)
+pub trait PanelAction: Send + 'static {
+    /// The name of the action, used in audit logs and metrics
+    fn name(&self) -> String;
+
+    /// The level of authentication the action needs
+    fn auth_level(&self) -> AuthLevel {
+        AuthLevel::Active
+    }
+
+    /// The permission needed to perform the action, if any
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        None
+    }
+
+    /// The entities changed by the action, `None` if the action does not change anything
+    ///
+    /// Actions returning a target are recorded in the audit log along with the state of the
+    /// entities before and after the action
+    fn audit_target(&self) -> Option<AuditTarget> {
+        None
+    }
+
+    /// Handles the action
+    fn handle<'a>(
+        self: Box<Self>,
+        ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>>;
+}
+
+/// Converts a panel query into its login token (if any) and action
+fn into_action(query: PanelQuery) -> (Option<String>, Box<dyn PanelAction>) {
+    match query {
+        PanelQuery::Authorize { version, action } => {
+            let login_token = match &action {
+                AuthorizeAction::Begin { .. } | AuthorizeAction::CreateSession { .. } => None,
+                AuthorizeAction::CheckMfaState { login_token }
+                | AuthorizeAction::ResetMfaTotp { login_token, .. }
+                | AuthorizeAction::ActivateSession { login_token, .. }
+                | AuthorizeAction::Logout { login_token } => Some(login_token.clone()),
+            };
+
+            (
+                login_token,
+                Box::new(actions::authorize::AuthorizeQuery { version, action }),
+            )
+        }
+        PanelQuery::Hello {
+            login_token,
+            version,
+        } => (
+            Some(login_token),
+            Box::new(actions::hello::HelloQuery { version }),
+        ),
+        PanelQuery::BaseAnalytics { login_token } => (
+            Some(login_token),
+            Box::new(actions::baseanalytics::BaseAnalyticsQuery),
+        ),
+        PanelQuery::GetUser {
+            login_token,
+            user_id,
+        } => (
+            Some(login_token),
+            Box::new(actions::getuser::GetUserQuery { user_id }),
+        ),
+        PanelQuery::GetLoginHistory {
+            login_token,
+            user_id,
+        } => (
+            Some(login_token),
+            Box::new(actions::getloginhistory::GetLoginHistoryQuery { user_id }),
+        ),
+        PanelQuery::GetAuditLog {
+            login_token,
+            user_id,
+            action,
+            entity_type,
+            entity_id,
+            offset,
+        } => (
+            Some(login_token),
+            Box::new(actions::getauditlog::GetAuditLogQuery {
+                user_id,
+                action,
+                entity_type,
+                entity_id,
+                offset,
+            }),
+        ),
+        PanelQuery::GetPanelMetrics { login_token } => {
+            (Some(login_token), Box::new(GetPanelMetricsQuery))
+        }
+        PanelQuery::SimulatePermissions {
+            login_token,
+            user_id,
+            positions,
+            perm_overrides,
+            disciplinaries,
+        } => (
+            Some(login_token),
+            Box::new(actions::simulatepermissions::SimulatePermissionsQuery {
+                user_id,
+                positions,
+                perm_overrides,
+                disciplinaries,
+            }),
+        ),
+        PanelQuery::GetStaffActivity {
+            login_token,
+            user_id,
+            window_days,
+        } => (
+            Some(login_token),
+            Box::new(actions::getstaffactivity::GetStaffActivityQuery {
+                user_id,
+                window_days,
+            }),
+        ),
+        PanelQuery::GetStaffTimeline {
+            login_token,
+            user_id,
+        } => (
+            Some(login_token),
+            Box::new(actions::getstafftimeline::GetStaffTimelineQuery { user_id }),
+        ),
+        PanelQuery::BotQueue { login_token } => (
+            Some(login_token),
+            Box::new(actions::botqueue::BotQueueQuery),
+        ),
+        PanelQuery::GetReviewChecklists {
+            login_token,
+            bot_id,
+            user_id,
+            failed_item,
+            offset,
+        } => (
+            Some(login_token),
+            Box::new(actions::getreviewchecklists::GetReviewChecklistsQuery {
+                bot_id,
+                user_id,
+                failed_item,
+                offset,
+            }),
+        ),
+        PanelQuery::GetReviewTranscripts {
+            login_token,
+            bot_id,
+            user_id,
+            offset,
+        } => (
+            Some(login_token),
+            Box::new(actions::getreviewtranscripts::GetReviewTranscriptsQuery {
+                bot_id,
+                user_id,
+                offset,
+            }),
+        ),
+        PanelQuery::ExecuteRpc {
+            login_token,
+            target_type,
+            method,
+        } => (
+            Some(login_token),
+            Box::new(actions::executerpc::ExecuteRpcQuery {
+                target_type,
+                method,
+            }),
+        ),
+        PanelQuery::GetRpcMethods {
+            login_token,
+            filtered,
+        } => (
+            Some(login_token),
+            Box::new(actions::getrpcmethods::GetRpcMethodsQuery { filtered }),
+        ),
+        PanelQuery::GetRpcLogEntries { login_token } => (
+            Some(login_token),
+            Box::new(actions::getrpclogentries::GetRpcLogEntriesQuery),
+        ),
+        PanelQuery::SearchEntitys {
+            login_token,
+            target_type,
+            query,
+        } => (
+            Some(login_token),
+            Box::new(actions::searchentitys::SearchEntitysQuery { target_type, query }),
+        ),
+        PanelQuery::UpdatePartners {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateBlog {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateStaffPositions {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateStaffMembers {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateStaffDisciplinaryType {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateStaffDisciplinaries {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateStaffOnboardings {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateStaffLoas {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateStaffApplications {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateStaffResyncHolds {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateVoteCreditTiers {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateShopItems {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateShopItemBenefits {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateShopCoupons {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateShopHolds {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateBotWhitelist {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+        PanelQuery::UpdateReviewReasonTemplates {
+            login_token,
+            action,
+        } => (Some(login_token), Box::new(action)),
+    }
+}
+
+/// Authenticates, checks permissions for and runs a panel action
+async fn run(
+    state: &AppState,
+    meta: &RequestMeta,
+    login_token: Option<String>,
+    action: Box<dyn PanelAction>,
+) -> Result<Response, Error> {
+    let auth_level = action.auth_level();
+
+    let auth_data = if auth_level == AuthLevel::None {
+        None
+    } else {
+        let Some(login_token) = login_token else {
+            return Err(Error {
+                status: StatusCode::BAD_REQUEST,
+                message: "This action requires a login token".to_string(),
+            });
+        };
+
+        let auth_data = if auth_level == AuthLevel::Insecure {
+            check_auth_insecure(&state.pool, &login_token).await
+        } else {
+            check_auth(&state.pool, &login_token).await
+        }
+        .map_err(Error::new)?;
+
+        Some(auth_data)
+    };
+
+    let user_perms = match auth_data {
+        Some(ref auth_data) => get_resolved_perms(&state.pool, &auth_data.user_id)
+            .await
+            .map_err(Error::new)?,
+        None => Vec::new(),
+    };
+
+    if let Some(required_perm) = action.required_perm() {
+        if !perms::has_perm(&user_perms, &required_perm.perm.into()) {
+            return Ok((
+                StatusCode::FORBIDDEN,
+                format!(
+                    "You do not have permission to {} [{}]",
+                    required_perm.description, required_perm.perm
+                ),
+            )
+                .into_response());
+        }
+    }
+
+    let name = action.name();
+    let audit_target = action.audit_target();
+
+    let before = match audit_target {
+        Some(ref target) => audit::snapshot(&state.pool, target)
+            .await
+            .map_err(Error::new)?,
+        None => None,
+    };
+
+    let ctx = ActionContext {
+        state,
+        meta,
+        auth_data,
+        user_perms,
+        created_ids: Mutex::new(Vec::new()),
+    };
+
+    let resp = action.handle(&ctx).await?;
+
+    if let (Some(mut target), Some(auth_data)) = (audit_target, &ctx.auth_data) {
+        if resp.status().is_success() {
+            if target.entity_ids.is_empty() {
+                if let Ok(mut created_ids) = ctx.created_ids.lock() {
+                    target.entity_ids = std::mem::take(&mut *created_ids);
+                }
+            }
+
+            if let Err(e) = record_audit(state, &auth_data.user_id, &name, target, before).await {
+                log::error!("Failed to record audit log entry for {}: {}", name, e);
+            }
+        }
+    }
+
+    Ok(resp)
+}
+
+/// Snapshots the entities of a successful action and records them in the audit log
+async fn record_audit(
+    state: &AppState,
+    user_id: &str,
+    name: &str,
+    target: AuditTarget,
+    before: Option<serde_json::Value>,
+) -> Result<(), crate::Error> {
+    let after = audit::snapshot(&state.pool, &target).await?;
+
+    audit::record(state, user_id, name, target, before, after).await
+}
+
+/// Converts a panel query into a panel action and runs it, recording audit logs and metrics
+pub async fn dispatch(
+    state: &AppState,
+    meta: &RequestMeta,
+    query: PanelQuery,
+) -> Result<Response, Error> {
+    let (login_token, action) = into_action(query);
+    let name = action.name();
+
+    let start = Instant::now();
+    let resp = run(state, meta, login_token, action).await;
+    let elapsed = start.elapsed();
+
+    let status = match resp {
+        Ok(ref r) => r.status(),
+        Err(ref e) => e.status,
+    };
+
+    record_metrics(&name, status, elapsed.as_millis() as u64);
+
+    log::info!(
+        "[panelapi] {} from {} -> {} ({}ms)",
+        name,
+        meta.ip,
+        status,
+        elapsed.as_millis()
+    );
+
+    resp
+}
+
+#[derive(Default)]
+struct ActionMetrics {
+    calls: u64,
+    errors: u64,
+    total_duration_ms: u64,
+}
+
+static METRICS: Lazy<RwLock<HashMap<String, ActionMetrics>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+fn record_metrics(name: &str, status: StatusCode, duration_ms: u64) {
+    let Ok(mut metrics) = METRICS.write() else {
+        return;
+    };
+
+    let entry = metrics.entry(name.to_string()).or_default();
+    entry.calls += 1;
+    entry.total_duration_ms += duration_ms;
+
+    if status.is_client_error() || status.is_server_error() {
+        entry.errors += 1;
+    }
+}
+
+/// Returns the metrics of all panel actions run since startup
+pub struct GetPanelMetricsQuery;
+
+impl PanelAction for GetPanelMetricsQuery {
+    fn name(&self) -> String {
+        "GetPanelMetrics".to_string()
+    }
+
+    fn required_perm(&self) -> Option<RequiredPerm> {
+        Some(RequiredPerm::new(
+            "arcadia.view_metrics",
+            "view panel metrics",
+        ))
+    }
+
+    fn handle<'a>(
+        self: Box<Self>,
+        _ctx: &'a ActionContext<'a>,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(async move {
+            let metrics = METRICS
+                .read()
+                .map_err(|_| Error::new("Failed to read panel metrics"))?;
+
+            let mut entries = metrics
+                .iter()
+                .map(|(name, m)| PanelActionMetrics {
+                    name: name.clone(),
+                    calls: m.calls,
+                    errors: m.errors,
+                    avg_duration_ms: m.total_duration_ms / m.calls.max(1),
+                })
+                .collect::<Vec<PanelActionMetrics>>();
+
+            entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+            Ok((StatusCode::OK, axum::Json(entries)).into_response())
+        })
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// An entry in the audit log of panel mutations
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/AuditLogEntry.ts")]
+pub struct AuditLogEntry {
+    /// The ID of the entry
+    pub id: String,
+    /// The user ID of the staff member who performed the action
+    pub user_id: String,
+    /// The action that was performed (e.g. `UpdatePartners.Create`)
+    pub action: String,
+    /// The type of entity that was changed, this is the table the entity is stored in
+    pub entity_type: String,
+    /// The IDs of the entities that were changed, empty if not known before the action ran
+    pub entity_ids: Vec<String>,
+    /// The data sent with the action
+    #[ts(type = "any")]
+    pub data: serde_json::Value,
+    /// The state of the entities before the action, `None` if they did not exist
+    #[ts(type = "any")]
+    pub before: Option<serde_json::Value>,
+    /// The state of the entities after the action, `None` if they no longer exist
+    #[ts(type = "any")]
+    pub after: Option<serde_json::Value>,
+    /// The changes between `before` and `after`, `None` if nothing changed
+    ///
+    /// Objects are compared key by key, changed values are recorded as `{"old": ..., "new": ...}`
+    #[ts(type = "any")]
+    pub diff: Option<serde_json::Value>,
+    /// When the action was performed
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -75,7 +75,7 @@ pub struct MfaLogin {
     pub info: Option<MfaLoginSecret>,
 }
 
-#[derive(Serialize, Deserialize, TS, Clone)]
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
 #[ts(export, export_to = ".generated/AuthData.ts")]
 pub struct AuthData {
     pub user_id: String,
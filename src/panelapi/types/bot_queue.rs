@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use super::entity::PartialBot;
+use super::invite_analysis::InviteAnalysis;
+use super::pre_review::PreReview;
+
+/// How a bot in the queue was prioritized
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/QueuePriority.ts")]
+pub struct QueuePriority {
+    /// The ID of the bot
+    pub bot_id: String,
+    /// The position of the bot in the queue, starting at 1
+    pub position: usize,
+    /// The priority score of the bot, bots with a higher score should be reviewed first
+    pub score: f64,
+    /// When the bot entered the queue, this is the later of when it was added and when it was last denied or unverified
+    pub queued_at: chrono::DateTime<chrono::Utc>,
+    /// Whether or not the bot has premium
+    pub premium: bool,
+    /// Whether or not the bot was denied or unverified before
+    pub resubmission: bool,
+    /// The number of other approved bots of the owner
+    pub owner_approved: i64,
+    /// The number of other denied bots of the owner
+    pub owner_denied: i64,
+}
+
+/// A bot in the queue
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/QueuedBot.ts")]
+pub struct QueuedBot {
+    /// The bot itself
+    pub bot: PartialBot,
+    /// How the bot was prioritized
+    pub priority: QueuePriority,
+    /// The latest automated pre-review checks of the bot, if they have been run yet
+    pub pre_review: Option<PreReview>,
+    /// What the stored invite of the bot asks for
+    pub invite_analysis: InviteAnalysis,
+}
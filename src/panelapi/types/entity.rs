@@ -1,3 +1,4 @@
+use super::review_history::ReviewHistory;
 use crate::impls::dovewing::PlatformUser;
 use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumVariantNames};
@@ -23,6 +24,8 @@ pub struct PartialBot {
     pub mentionable: Vec<String>,
     pub invite: String,
     pub client_id: String,
+    /// Past claims and decisions on the bot
+    pub review_history: ReviewHistory,
 }
 
 #[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use super::rpclogs::RPCLogEntry;
+
+/// An event pushed to panel clients over the `/events` stream
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/PanelEvent.ts")]
+pub enum PanelEvent {
+    /// A bot in the queue was claimed, unclaimed, approved, denied or otherwise had its queue state changed
+    QueueUpdate {
+        /// The ID of the bot
+        bot_id: String,
+        /// The action that was taken (e.g. `Claim`, `Unclaim`, `Approve`, `Deny`)
+        action: String,
+        /// The user who took the action, `None` if performed automatically by Arcadia
+        user_id: Option<String>,
+    },
+    /// A new RPC log entry was created
+    ///
+    /// Requires the `rpc_logs.view` permission to receive
+    RpcLogEntry {
+        /// The RPC log entry
+        entry: RPCLogEntry,
+    },
+    /// The positions of a staff member were changed by a staff resync
+    StaffResync {
+        /// The user ID of the staff member
+        user_id: String,
+        /// The IDs of the positions the staff member had before the resync
+        old_positions: Vec<String>,
+        /// The IDs of the positions the staff member has after the resync, empty if removed
+        new_positions: Vec<String>,
+    },
+    /// A background task finished running
+    ///
+    /// Requires the `arcadia.view_tasks` permission to receive
+    TaskOutcome {
+        /// The name of the task
+        name: String,
+        /// Whether or not the task succeeded
+        success: bool,
+        /// The error returned by the task, if any
+        error: Option<String>,
+    },
+}
+
+impl PanelEvent {
+    /// Returns the name of the event, used as the SSE event name
+    pub fn name(&self) -> &'static str {
+        match self {
+            PanelEvent::QueueUpdate { .. } => "QueueUpdate",
+            PanelEvent::RpcLogEntry { .. } => "RpcLogEntry",
+            PanelEvent::StaffResync { .. } => "StaffResync",
+            PanelEvent::TaskOutcome { .. } => "TaskOutcome",
+        }
+    }
+
+    /// Returns the permission needed to receive the event, if any
+    ///
+    /// Events with no required permission are sent to all staff members
+    pub fn required_perm(&self) -> Option<&'static str> {
+        match self {
+            PanelEvent::QueueUpdate { .. } => None,
+            PanelEvent::RpcLogEntry { .. } => Some("rpc_logs.view"),
+            PanelEvent::StaffResync { .. } => None,
+            PanelEvent::TaskOutcome { .. } => Some("arcadia.view_tasks"),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// What a bot's stored invite asks for
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/InviteAnalysis.ts")]
+pub struct InviteAnalysis {
+    /// The client ID the invite is for
+    pub client_id: Option<String>,
+    /// The OAuth2 scopes the invite asks for
+    pub scopes: Vec<String>,
+    /// The permission bitfield the invite asks for, as a string as it may not fit in a JavaScript number
+    pub permissions: Option<String>,
+    /// Where the user is redirected to after inviting the bot
+    pub redirect_uri: Option<String>,
+    /// The dangerous permissions the invite asks for, such as ``Administrator``
+    pub dangerous_permissions: Vec<String>,
+    /// Other problems with the invite, such as unexpected scopes
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString, EnumVariantNames};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// The type of event recorded in the login history of a staff member
+#[derive(
+    Serialize, Deserialize, ToSchema, TS, EnumString, EnumVariantNames, Display, Clone, PartialEq,
+)]
+#[ts(export, export_to = ".generated/LoginEvent.ts")]
+pub enum LoginEvent {
+    /// A new (pending) session was created through Discord OAuth2
+    CreateSession,
+    /// A session was successfully activated using MFA
+    MfaSuccess,
+    /// An invalid MFA code was entered
+    MfaFailure,
+    /// A session was logged out
+    Logout,
+}
+
+/// An entry in the login history of a staff member
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/LoginHistoryEntry.ts")]
+pub struct LoginHistoryEntry {
+    /// The ID of the entry
+    pub id: String,
+    /// The user ID of the staff member
+    pub user_id: String,
+    /// The event that occurred
+    pub event: String,
+    /// The IP address the event came from
+    pub ip: String,
+    /// The user agent the event came from
+    pub user_agent: String,
+    /// When the event occurred
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// Metrics of a panel action since the panel API was started
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/PanelActionMetrics.ts")]
+pub struct PanelActionMetrics {
+    /// The name of the action
+    pub name: String,
+    /// The number of times the action was run
+    pub calls: u64,
+    /// The number of times the action returned an error status
+    pub errors: u64,
+    /// The average time taken to run the action, in milliseconds
+    pub avg_duration_ms: u64,
+}
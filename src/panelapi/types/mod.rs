@@ -1,14 +1,32 @@
 pub mod analytics;
+pub mod audit_log;
 pub mod auth;
 pub mod blog;
+pub mod bot_queue;
 pub mod bot_whitelist;
 pub mod entity;
+pub mod events;
+pub mod invite_analysis;
+pub mod login_history;
+pub mod metrics;
 pub mod partners;
+pub mod permission_simulation;
+pub mod pre_review;
+pub mod review_checklists;
+pub mod review_history;
+pub mod review_templates;
+pub mod review_transcripts;
 pub mod rpc;
 pub mod rpclogs;
 pub mod shop_items;
+pub mod staff_activity;
+pub mod staff_applications;
 pub mod staff_disciplinary;
+pub mod staff_loa;
 pub mod staff_members;
+pub mod staff_onboardings;
 pub mod staff_positions;
+pub mod staff_resync;
+pub mod staff_timeline;
 pub mod vote_credit_tiers;
 pub mod webcore;
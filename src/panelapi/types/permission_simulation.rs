@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// A real or hypothetical staff position to simulate
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/SimulatedPosition.ts")]
+pub struct SimulatedPosition {
+    /// The ID of the position, this is only used to explain where permissions come from
+    pub id: String,
+    /// The index of the position, lower is higher in the hierarchy
+    pub index: i32,
+    /// The permissions of the position
+    pub perms: Vec<String>,
+}
+
+/// A real or hypothetical disciplinary action to simulate
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/SimulatedDisciplinary.ts")]
+pub struct SimulatedDisciplinary {
+    /// The ID of the disciplinary action, this is only used to explain where permissions come from
+    pub id: String,
+    /// The permission limits of the disciplinary type
+    pub perm_limits: Vec<String>,
+    /// Whether the perm limits are combined with the positions of the member or replace them
+    pub additory: bool,
+}
+
+/// What a permission did while resolving
+#[derive(Serialize, Deserialize, TS, ToSchema, EnumString, Display, Clone, PartialEq)]
+#[ts(export, export_to = ".generated/PermissionEffect.ts")]
+pub enum PermissionEffect {
+    /// The permission was granted, replacing a negation of it
+    Added,
+    /// The permission was negated, removing it if it was granted
+    Negated,
+    /// All permissions of the namespace (or all permissions for `global`) were removed
+    Cleared,
+}
+
+/// A single permission applied while resolving, in resolution order
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/PermissionStep.ts")]
+pub struct PermissionStep {
+    /// The position the permission came from, `perm_overrides` for the permission overrides of the member
+    pub source: String,
+    /// The index of the position
+    pub index: i32,
+    /// The permission
+    pub perm: String,
+    /// What the permission did
+    pub effect: PermissionEffect,
+}
+
+/// Where a resolved permission came from
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/PermissionSource.ts")]
+pub struct PermissionSource {
+    /// The resolved permission
+    pub perm: String,
+    /// The position the permission came from, `perm_overrides` for the permission overrides of the member
+    pub source: String,
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/PermissionSimulation.ts")]
+pub struct PermissionSimulation {
+    /// The resolved permissions
+    pub resolved_perms: Vec<String>,
+    /// The position each resolved permission came from
+    pub sources: Vec<PermissionSource>,
+    /// Every permission applied while resolving, in resolution order
+    pub steps: Vec<PermissionStep>,
+    /// The positions removed by non-additory disciplinary actions
+    pub removed_positions: Vec<String>,
+    /// The current resolved permissions of the member, if a user ID was given
+    pub current_perms: Option<Vec<String>>,
+    /// The permissions the simulation has that the member currently does not
+    pub added: Vec<String>,
+    /// The permissions the member currently has that the simulation does not
+    pub removed: Vec<String>,
+}
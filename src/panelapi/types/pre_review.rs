@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// The outcome of a single automated pre-review check
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/PreReviewCheck.ts")]
+pub struct PreReviewCheck {
+    /// The ID of the check, one of ``invite``, ``client_id``, ``dovewing``, ``not_banned`` or ``long_description``
+    pub id: String,
+    /// The label shown to reviewers
+    pub label: String,
+    /// Whether or not the bot passed the check
+    pub passed: bool,
+    /// Why the check failed, if it did
+    pub details: Option<String>,
+}
+
+/// The latest automated pre-review checks of a bot
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/PreReview.ts")]
+pub struct PreReview {
+    /// The ID of the bot
+    pub bot_id: String,
+    /// The checks that were run
+    pub checks: Vec<PreReviewCheck>,
+    /// When the checks were run
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// An item of the checklist reviewers fill in when approving or denying a bot
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/ReviewChecklistItem.ts")]
+pub struct ReviewChecklistItem {
+    /// The ID of the item, this is what checklists are submitted with
+    pub id: String,
+    /// What the reviewer should check
+    pub label: String,
+    /// Whether or not the bot must pass this item to be approved
+    pub required_for_approval: bool,
+}
+
+/// The answer to an item of a review checklist
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/ReviewChecklistAnswer.ts")]
+pub struct ReviewChecklistAnswer {
+    /// The ID of the item
+    pub id: String,
+    /// The label of the item at the time of the review
+    pub label: String,
+    /// Whether or not the bot passed the item
+    pub passed: bool,
+}
+
+/// A review checklist filled in when approving or denying a bot
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/ReviewChecklist.ts")]
+pub struct ReviewChecklist {
+    /// The ID of the review checklist
+    pub id: String,
+    /// The bot that was reviewed
+    pub bot_id: String,
+    /// The reviewer
+    pub user_id: String,
+    /// The RPC method the checklist was submitted with, either ``Approve`` or ``Deny``
+    pub decision: String,
+    /// The reason given for the decision
+    pub reason: String,
+    /// The answers to each item of the checklist
+    pub answers: Vec<ReviewChecklistAnswer>,
+    /// When the review happened
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
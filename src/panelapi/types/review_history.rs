@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// A past claim, unclaim or decision on a bot
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/ReviewHistoryEntry.ts")]
+pub struct ReviewHistoryEntry {
+    /// What happened, one of ``Claim``, ``Unclaim``, ``Approve``, ``Deny`` or ``Unverify``
+    pub action: String,
+    /// The staff member who did it
+    pub user_id: String,
+    /// The reason given, claims have no reason
+    pub reason: Option<String>,
+    /// When it happened
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The review history of a bot, so reviewers can check that earlier issues were fixed
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone, Default)]
+#[ts(export, export_to = ".generated/ReviewHistory.ts")]
+pub struct ReviewHistory {
+    /// The most recent entries, newest first
+    pub entries: Vec<ReviewHistoryEntry>,
+    /// The number of times the bot has been denied
+    pub denials: i64,
+    /// The staff member who last approved, denied or unverified the bot
+    pub previous_reviewer: Option<String>,
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString, EnumVariantNames};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// A reusable reason for denying or unverifying a bot
+///
+/// The content can contain the placeholders ``{bot_name}``, ``{bot_id}``, ``{owner}`` (mentions of the
+/// owners of the bot), ``{frontend_url}`` and ``{guide}`` (the staff guide)
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/ReviewReasonTemplate.ts")]
+pub struct ReviewReasonTemplate {
+    /// The ID of the template
+    pub id: String,
+    /// The name of the template, this is what reviewers pick the template by
+    pub name: String,
+    /// The content of the template
+    pub content: String,
+    /// The RPC methods the template can be used with, e.g. ``Deny``
+    pub methods: Vec<String>,
+    /// Who created the template
+    pub created_by: String,
+    /// Who last updated the template
+    pub updated_by: String,
+    /// The time the template was created
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// The time the template was last updated
+    pub last_updated: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    ToSchema,
+    TS,
+    EnumString,
+    EnumVariantNames,
+    Display,
+    Clone,
+    PartialEq,
+    Default,
+)]
+#[ts(export, export_to = ".generated/ReviewReasonTemplateAction.ts")]
+pub enum ReviewReasonTemplateAction {
+    /// List all reason templates
+    #[default]
+    List,
+    /// Create a new reason template
+    Create {
+        /// The name of the template
+        name: String,
+        /// The content of the template
+        content: String,
+        /// The RPC methods the template can be used with
+        methods: Vec<String>,
+    },
+    /// Edit a reason template
+    Edit {
+        /// The ID of the template
+        id: String,
+        /// The name of the template
+        name: String,
+        /// The content of the template
+        content: String,
+        /// The RPC methods the template can be used with
+        methods: Vec<String>,
+    },
+    /// Delete a reason template
+    Delete {
+        /// The ID of the template
+        id: String,
+    },
+    /// Combines reason templates and a custom note into the reason to submit with the RPC method
+    Render {
+        /// The IDs of the templates, in the order they should appear in
+        ids: Vec<String>,
+        /// The bot being denied or unverified
+        bot_id: String,
+        /// A custom note added after the templates
+        note: Option<String>,
+    },
+}
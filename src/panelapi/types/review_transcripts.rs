@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// A message sent in a review channel
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/ReviewTranscriptMessage.ts")]
+pub struct ReviewTranscriptMessage {
+    /// The ID of the author, this may be the bot under review
+    pub author_id: String,
+    /// The username of the author at the time of the review
+    pub author_name: String,
+    /// The content of the message
+    pub content: String,
+    /// The URLs of the attachments of the message
+    pub attachments: Vec<String>,
+    /// When the message was sent
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The archived transcript of the private review channel of a bot
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/ReviewTranscript.ts")]
+pub struct ReviewTranscript {
+    /// The ID of the transcript
+    pub id: String,
+    /// The bot that was reviewed
+    pub bot_id: String,
+    /// The ID of the review channel, the channel itself is deleted once archived
+    pub channel_id: String,
+    /// The reviewer the channel was created for
+    pub user_id: String,
+    /// What ended the review, one of ``Approve``, ``Deny``, ``Unclaim``, ``AutoUnclaim`` or ``Claim`` (for force claims)
+    pub action: String,
+    /// The messages sent in the channel, oldest first
+    pub messages: Vec<ReviewTranscriptMessage>,
+    /// When the transcript was archived
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
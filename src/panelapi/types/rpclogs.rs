@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, ToSchema, TS)]
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
 #[ts(export, export_to = ".generated/RPCLogEntry.ts")]
 pub struct RPCLogEntry {
     /// ID of the RPC log entry
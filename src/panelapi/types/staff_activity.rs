@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// The activity of a staff member over a rolling window
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/StaffActivity.ts")]
+pub struct StaffActivity {
+    /// The user ID of the staff member
+    pub user_id: String,
+    /// The number of days the activity was computed over
+    pub window_days: i32,
+    /// The number of bots approved or denied
+    pub reviews: i64,
+    /// The number of bots claimed
+    pub claims: i64,
+    /// The average time between claiming a bot and approving or denying it, in seconds
+    pub avg_claim_to_decision_secs: Option<f64>,
+    /// The number of bots auto-unclaimed from the staff member for being claimed too long
+    pub auto_unclaims: i64,
+    /// The number of panel logins
+    pub panel_logins: i64,
+    /// Whether or not the staff member was on an approved leave of absence during the window
+    pub on_loa: bool,
+    /// The activity thresholds the staff member did not meet, empty if they are active or on leave
+    pub flags: Vec<String>,
+}
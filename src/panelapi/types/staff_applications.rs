@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString, EnumVariantNames};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/StaffApplication.ts")]
+pub struct StaffApplication {
+    /// The ID of the application
+    pub id: String,
+
+    /// The user ID of the applicant
+    pub user_id: String,
+
+    /// The name of the question set the applicant applied with
+    pub question_set: String,
+
+    /// The questions asked and the responses submitted by the applicant
+    pub responses: Vec<StaffApplicationResponse>,
+
+    /// The state of the application, see `StaffApplicationState`
+    pub state: String,
+
+    /// The votes cast by staff members on the application
+    pub votes: Vec<StaffApplicationVote>,
+
+    /// The comments left by staff members on the application
+    pub comments: Vec<StaffApplicationComment>,
+
+    /// The user ID of the staff member who accepted or rejected the application
+    pub reviewed_by: Option<String>,
+
+    /// The reason given when accepting or rejecting the application
+    pub review_reason: Option<String>,
+
+    /// When the application was accepted or rejected
+    pub reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// When the application was submitted
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/StaffApplicationResponse.ts")]
+pub struct StaffApplicationResponse {
+    /// The question asked
+    pub question: String,
+
+    /// The response of the applicant
+    pub answer: String,
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/StaffApplicationVote.ts")]
+pub struct StaffApplicationVote {
+    /// The user ID of the staff member who voted
+    pub user_id: String,
+
+    /// Whether the staff member voted to accept (true) or reject (false) the applicant
+    pub approve: bool,
+
+    /// When the vote was last changed
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/StaffApplicationComment.ts")]
+pub struct StaffApplicationComment {
+    /// The ID of the comment
+    pub id: String,
+
+    /// The user ID of the staff member who commented
+    pub user_id: String,
+
+    /// The comment
+    pub comment: String,
+
+    /// When the comment was made
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The state of a staff application
+#[derive(
+    Serialize, Deserialize, ToSchema, TS, EnumString, EnumVariantNames, Display, Clone, PartialEq,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = ".generated/StaffApplicationState.ts")]
+pub enum StaffApplicationState {
+    /// The application is waiting to be reviewed
+    Pending,
+    /// The applicant was accepted and is awaiting staff
+    Accepted,
+    /// The applicant was rejected and must wait before applying again
+    Rejected,
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    ToSchema,
+    TS,
+    EnumString,
+    EnumVariantNames,
+    Display,
+    Clone,
+    PartialEq,
+    Default,
+)]
+#[ts(export, export_to = ".generated/StaffApplicationAction.ts")]
+pub enum StaffApplicationAction {
+    /// List the applications of a user
+    #[default]
+    ListApplications {
+        /// The user ID of the applicant
+        user_id: String,
+    },
+
+    /// List all applications waiting to be reviewed
+    ListPending,
+
+    /// Vote to accept or reject an applicant, voting again replaces your previous vote
+    VoteApplication {
+        /// The ID of the application
+        id: String,
+
+        /// Whether to vote to accept (true) or reject (false) the applicant
+        approve: bool,
+    },
+
+    /// Leave a comment on an application
+    CommentApplication {
+        /// The ID of the application
+        id: String,
+
+        /// The comment
+        comment: String,
+    },
+
+    /// Accept an applicant, giving them the awaiting staff role and adding them as a staff member
+    AcceptApplication {
+        /// The ID of the application
+        id: String,
+
+        /// An optional note for the applicant
+        reason: Option<String>,
+    },
+
+    /// Reject an applicant, they must wait the configured cooldown before applying again
+    RejectApplication {
+        /// The ID of the application
+        id: String,
+
+        /// The reason for rejecting the applicant
+        reason: String,
+    },
+}
@@ -86,7 +86,7 @@ pub enum StaffDisciplinaryTypeAction {
     },
 }
 
-#[derive(Serialize, Deserialize, TS, Clone)]
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
 #[ts(export, export_to = ".generated/StaffDisciplinaryType.ts")]
 pub struct StaffDisciplinaryType {
     /// The id of the type
@@ -119,7 +119,7 @@ pub struct StaffDisciplinaryType {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Serialize, Deserialize, TS, Clone)]
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
 #[ts(export, export_to = ".generated/StaffDisciplinary.ts")]
 pub struct StaffDisciplinary {
     /// The ID of the position
@@ -142,4 +142,129 @@ pub struct StaffDisciplinary {
 
     /// The type of the disciplinary
     pub r#type: StaffDisciplinaryType,
+
+    /// The state of the disciplinary action, see `StaffDisciplinaryState`
+    pub state: String,
+
+    /// The user ID of the staff member who issued the disciplinary action
+    pub issued_by: Option<String>,
+
+    /// The user ID of the staff member who approved or denied the disciplinary action, if it needed approval
+    pub approved_by: Option<String>,
+
+    /// The user ID of the staff member who revoked the disciplinary action
+    pub revoked_by: Option<String>,
+
+    /// When the disciplinary action was revoked
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// The reason the disciplinary action was revoked
+    pub revoke_reason: Option<String>,
+
+    /// The reason the disciplinary action was denied
+    pub deny_reason: Option<String>,
+}
+
+/// The state of a staff disciplinary action
+#[derive(
+    Serialize, Deserialize, ToSchema, TS, EnumString, EnumVariantNames, Display, Clone, PartialEq,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = ".generated/StaffDisciplinaryState.ts")]
+pub enum StaffDisciplinaryState {
+    /// The disciplinary action is waiting for approval as its type needs approval
+    Pending,
+    /// The disciplinary action is in effect until it expires
+    Active,
+    /// The disciplinary action was denied by an approver
+    Denied,
+    /// The disciplinary action was revoked before it expired
+    Revoked,
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    ToSchema,
+    TS,
+    EnumString,
+    EnumVariantNames,
+    Display,
+    Clone,
+    PartialEq,
+    Default,
+)]
+#[ts(export, export_to = ".generated/StaffDisciplinaryAction.ts")]
+pub enum StaffDisciplinaryAction {
+    /// List the disciplinary history of a staff member
+    ///
+    /// Staff members can always view their own history, viewing the history of others needs
+    /// the `staff_disciplinaries.view` permission
+    #[default]
+    ListDisciplinaries {
+        /// The user ID of the staff member, defaults to the current user
+        user_id: Option<String>,
+    },
+
+    /// List all disciplinary actions waiting for approval
+    ListPending,
+
+    /// Issue a disciplinary action to a staff member
+    ///
+    /// If the type needs approval, the disciplinary action will only take effect once approved
+    /// by another staff member
+    IssueDisciplinary {
+        /// The user ID of the staff member to issue the disciplinary action to
+        user_id: String,
+
+        /// The ID of the disciplinary type
+        type_id: String,
+
+        /// The title of the disciplinary action report
+        title: String,
+
+        /// The description of the disciplinary action report
+        description: String,
+
+        /// When the disciplinary action expires, in seconds from when it was issued
+        ///
+        /// Required if the type has a maximum expiry, `None` means the action never expires
+        expiry: Option<i64>,
+    },
+
+    /// Approve a pending disciplinary action
+    ApproveDisciplinary {
+        /// The ID of the disciplinary action
+        id: String,
+    },
+
+    /// Deny a pending disciplinary action
+    DenyDisciplinary {
+        /// The ID of the disciplinary action
+        id: String,
+
+        /// The reason for denying the disciplinary action
+        reason: String,
+    },
+
+    /// Revoke an active or pending disciplinary action
+    RevokeDisciplinary {
+        /// The ID of the disciplinary action
+        id: String,
+
+        /// The reason for revoking the disciplinary action
+        reason: String,
+    },
+
+    /// Shorten the expiry of an active or pending disciplinary action
+    ShortenDisciplinary {
+        /// The ID of the disciplinary action
+        id: String,
+
+        /// The new expiry of the disciplinary action, in seconds from when it was issued
+        ///
+        /// Must be shorter than the current expiry
+        expiry: i64,
+    },
 }
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString, EnumVariantNames};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/StaffLoa.ts")]
+pub struct StaffLoa {
+    /// The ID of the leave of absence
+    pub id: String,
+
+    /// The user ID of the staff member on leave
+    pub user_id: String,
+
+    /// Why the staff member is going on leave
+    pub reason: String,
+
+    /// When the leave of absence starts
+    pub start_date: chrono::DateTime<chrono::Utc>,
+
+    /// When the leave of absence ends
+    pub end_date: chrono::DateTime<chrono::Utc>,
+
+    /// The state of the leave of absence, see `StaffLoaState`
+    pub state: String,
+
+    /// The user ID of the staff member who approved or denied the leave of absence
+    pub reviewed_by: Option<String>,
+
+    /// The reason the leave of absence was denied
+    pub deny_reason: Option<String>,
+
+    /// When the leave of absence was requested
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The state of a leave of absence
+#[derive(
+    Serialize, Deserialize, ToSchema, TS, EnumString, EnumVariantNames, Display, Clone, PartialEq,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = ".generated/StaffLoaState.ts")]
+pub enum StaffLoaState {
+    /// The leave of absence is waiting for approval
+    Pending,
+    /// The leave of absence was approved, the staff member is on leave between its start and end dates
+    Approved,
+    /// The leave of absence was denied
+    Denied,
+    /// The leave of absence was cancelled before it ended
+    Cancelled,
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    ToSchema,
+    TS,
+    EnumString,
+    EnumVariantNames,
+    Display,
+    Clone,
+    PartialEq,
+    Default,
+)]
+#[ts(export, export_to = ".generated/StaffLoaAction.ts")]
+pub enum StaffLoaAction {
+    /// List the leaves of absence of a staff member
+    ///
+    /// Staff members can always view their own leaves of absence, viewing those of others needs
+    /// the `staff_members.loa` permission
+    #[default]
+    ListLoas {
+        /// The user ID of the staff member, defaults to the current user
+        user_id: Option<String>,
+    },
+
+    /// List all leaves of absence waiting for approval
+    ListPending,
+
+    /// Request a leave of absence for yourself
+    RequestLoa {
+        /// Why you are going on leave
+        reason: String,
+
+        /// When the leave of absence starts
+        start_date: chrono::DateTime<chrono::Utc>,
+
+        /// When the leave of absence ends
+        end_date: chrono::DateTime<chrono::Utc>,
+    },
+
+    /// Approve a pending leave of absence
+    ApproveLoa {
+        /// The ID of the leave of absence
+        id: String,
+    },
+
+    /// Deny a pending leave of absence
+    DenyLoa {
+        /// The ID of the leave of absence
+        id: String,
+
+        /// The reason for denying the leave of absence
+        reason: String,
+    },
+
+    /// Cancel a pending or approved leave of absence
+    ///
+    /// Staff members can always cancel their own leaves of absence, cancelling those of others
+    /// needs the `staff_members.loa` permission
+    CancelLoa {
+        /// The ID of the leave of absence
+        id: String,
+    },
+}
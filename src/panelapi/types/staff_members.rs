@@ -1,4 +1,5 @@
 use super::staff_disciplinary::StaffDisciplinary;
+use super::staff_loa::StaffLoa;
 use crate::impls::dovewing::PlatformUser;
 use kittycat::perms::Permission;
 use serde::{Deserialize, Serialize};
@@ -49,7 +50,7 @@ fn _sp_default() -> kittycat::perms::StaffPermissions {
     }
 }
 
-#[derive(Serialize, Deserialize, TS, Clone)]
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
 #[ts(export, export_to = ".generated/StaffMember.ts")]
 pub struct StaffMember {
     /// The id of the user
@@ -63,6 +64,8 @@ pub struct StaffMember {
     pub staff_permission: kittycat::perms::StaffPermissions,
     /// The disciplinary actions recieved by the member
     pub disciplinaries: Vec<StaffDisciplinary>,
+    /// The approved leave of absence the member is currently on, if any
+    pub loa: Option<StaffLoa>,
     /// The permission overrides of the staff member
     pub perm_overrides: Vec<String>,
     #[serde(skip)]
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString, EnumVariantNames};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/StaffOnboarding.ts")]
+pub struct StaffOnboarding {
+    /// The ID of the onboarding
+    pub id: String,
+
+    /// The user ID of the staff member being onboarded
+    pub user_id: String,
+
+    /// The state of the onboarding, see `StaffOnboardingState`
+    pub state: String,
+
+    /// The ID of the test bot the staff member must review
+    pub bot_id: Option<String>,
+
+    /// The user ID of the staff member who started the onboarding
+    pub started_by: Option<String>,
+
+    /// The questions asked and the responses submitted by the staff member
+    pub responses: Vec<StaffOnboardingResponse>,
+
+    /// When the responses were submitted
+    pub submitted_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// The user ID of the reviewer who graded the onboarding
+    pub graded_by: Option<String>,
+
+    /// Whether or not the reviewer passed the onboarding
+    pub passed: Option<bool>,
+
+    /// The feedback given by the reviewer
+    pub feedback: Option<String>,
+
+    /// When the onboarding was graded
+    pub graded_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// The user ID of the staff member who completed the onboarding
+    pub completed_by: Option<String>,
+
+    /// When the onboarding was completed
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// When the completed onboarding stops being valid
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Whether or not the onboarding has been voided
+    pub void: bool,
+
+    /// The user ID of the staff member who voided the onboarding
+    pub voided_by: Option<String>,
+
+    /// The reason the onboarding was voided
+    pub void_reason: Option<String>,
+
+    /// When the onboarding was started
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/StaffOnboardingResponse.ts")]
+pub struct StaffOnboardingResponse {
+    /// The question asked
+    pub question: String,
+
+    /// The response of the staff member
+    pub answer: String,
+}
+
+/// The state of a staff onboarding
+#[derive(
+    Serialize, Deserialize, ToSchema, TS, EnumString, EnumVariantNames, Display, Clone, PartialEq,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = ".generated/StaffOnboardingState.ts")]
+pub enum StaffOnboardingState {
+    /// The staff member is reviewing the test bot
+    Pending,
+    /// The staff member has submitted their responses and is waiting to be graded
+    Submitted,
+    /// A reviewer has passed the onboarding, it is waiting to be completed
+    Graded,
+    /// A reviewer has failed the onboarding, a new onboarding must be started
+    Failed,
+    /// The onboarding is complete and valid until it expires
+    Completed,
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    ToSchema,
+    TS,
+    EnumString,
+    EnumVariantNames,
+    Display,
+    Clone,
+    PartialEq,
+    Default,
+)]
+#[ts(export, export_to = ".generated/StaffOnboardingAction.ts")]
+pub enum StaffOnboardingAction {
+    /// List the onboardings of a staff member
+    ///
+    /// Staff members can always view their own onboardings, viewing the onboardings of others
+    /// needs the `staff_onboardings.view` permission
+    #[default]
+    ListOnboardings {
+        /// The user ID of the staff member, defaults to the current user
+        user_id: Option<String>,
+    },
+
+    /// List all onboardings waiting to be graded
+    ListSubmitted,
+
+    /// Start an onboarding
+    ///
+    /// Staff members can always start their own onboarding, starting the onboarding of others
+    /// needs the `staff_onboardings.manage` permission
+    StartOnboarding {
+        /// The user ID of the staff member, defaults to the current user
+        user_id: Option<String>,
+
+        /// The ID of the test bot to review, defaults to the configured test bot
+        bot_id: Option<String>,
+    },
+
+    /// Submit the responses to an onboarding, only the staff member being onboarded can do this
+    SubmitResponses {
+        /// The ID of the onboarding
+        id: String,
+
+        /// The responses, in the same order as the configured questions
+        responses: Vec<String>,
+    },
+
+    /// Grade a submitted onboarding
+    GradeOnboarding {
+        /// The ID of the onboarding
+        id: String,
+
+        /// Whether or not the onboarding passed
+        passed: bool,
+
+        /// Feedback for the staff member
+        feedback: String,
+    },
+
+    /// Complete a passed onboarding
+    CompleteOnboarding {
+        /// The ID of the onboarding
+        id: String,
+
+        /// How many days the onboarding is valid for, defaults to the configured validity
+        validity_days: Option<i64>,
+    },
+
+    /// Void an onboarding, this also invalidates a completed onboarding
+    VoidOnboarding {
+        /// The ID of the onboarding
+        id: String,
+
+        /// The reason for voiding the onboarding
+        reason: String,
+    },
+}
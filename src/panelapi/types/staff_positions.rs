@@ -1,5 +1,4 @@
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
 use strum_macros::{Display, EnumString, EnumVariantNames};
 use ts_rs::TS;
 use utoipa::ToSchema;
@@ -43,7 +42,7 @@ pub enum StaffPositionAction {
         name: String,
         /// The role id associated with this position on Discord [staff server]
         role_id: String,
-        /// The corresponding role on discord
+        /// The corresponding roles on discord, the name of each link is a guild alias (see `PanelServers.aliases`) and the value is the role ID
         corresponding_roles: Vec<Link>,
         /// The preset permissions of this position
         perms: Vec<String>,
@@ -62,7 +61,7 @@ pub enum StaffPositionAction {
         name: String,
         /// The role id associated with this position on Discord
         role_id: String,
-        /// The corresponding role on discord
+        /// The corresponding roles on discord, the name of each link is a guild alias (see `PanelServers.aliases`) and the value is the role ID
         corresponding_roles: Vec<Link>,
         /// The preset permissions of this position
         perms: Vec<String>,
@@ -76,7 +75,7 @@ pub enum StaffPositionAction {
     },
 }
 
-#[derive(Serialize, Deserialize, TS, Clone)]
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
 #[ts(export, export_to = ".generated/StaffPosition.ts")]
 pub struct StaffPosition {
     /// The ID of the position
@@ -96,34 +95,3 @@ pub struct StaffPosition {
     /// When the staff position was created/added
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
-
-#[derive(Serialize, Deserialize, ToSchema, TS, EnumVariantNames, Display, Clone, PartialEq)]
-#[ts(export, export_to = ".generated/CorrespondingServer.ts")]
-pub enum CorrespondingServer {
-    Main,
-    Testing,
-    Staff,
-}
-
-impl FromStr for CorrespondingServer {
-    type Err = crate::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "main" => Ok(CorrespondingServer::Main),
-            "testing" => Ok(CorrespondingServer::Testing),
-            "staff" => Ok(CorrespondingServer::Staff),
-            _ => Err(format!("Invalid corresponding server: {}", s).into()),
-        }
-    }
-}
-
-impl CorrespondingServer {
-    pub fn get_id(&self) -> serenity::all::GuildId {
-        match self {
-            CorrespondingServer::Main => crate::config::CONFIG.servers.main,
-            CorrespondingServer::Testing => crate::config::CONFIG.servers.testing,
-            CorrespondingServer::Staff => crate::config::CONFIG.servers.staff,
-        }
-    }
-}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString, EnumVariantNames};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// A position removal staff resync is holding until it is confirmed
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
+#[ts(export, export_to = ".generated/StaffResyncHold.ts")]
+pub struct StaffResyncHold {
+    /// The ID of the hold
+    pub id: String,
+
+    /// The user ID of the staff member losing the position
+    pub user_id: String,
+
+    /// The ID of the position being removed
+    pub position_id: String,
+
+    /// The name of the position being removed
+    pub position_name: String,
+
+    /// The index of the position being removed
+    pub position_index: i32,
+
+    /// When the removal was first held
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    ToSchema,
+    TS,
+    EnumString,
+    EnumVariantNames,
+    Display,
+    Clone,
+    PartialEq,
+    Default,
+)]
+#[ts(export, export_to = ".generated/StaffResyncHoldAction.ts")]
+pub enum StaffResyncHoldAction {
+    /// List all held position removals
+    #[default]
+    ListHolds,
+
+    /// Confirm a held removal, removing the position from the staff member
+    ConfirmRemoval {
+        /// The ID of the hold
+        id: String,
+    },
+
+    /// Reject a held removal, giving the staff member back the role of the position
+    RejectRemoval {
+        /// The ID of the hold
+        id: String,
+    },
+}
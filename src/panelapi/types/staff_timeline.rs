@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString, EnumVariantNames};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// A change to a staff member
+#[derive(Serialize, Deserialize, ToSchema, TS, Clone)]
+#[ts(export, export_to = ".generated/StaffTimelineEvent.ts")]
+pub struct StaffTimelineEvent {
+    /// The ID of the event
+    pub id: String,
+    /// The user ID of the staff member that was changed
+    pub user_id: String,
+    /// The kind of change, see `StaffTimelineEventKind`
+    pub kind: String,
+    /// The user ID of the staff member who made the change, `None` for automatic changes (e.g. staff resync)
+    pub actor_id: Option<String>,
+    /// A human readable summary of the change
+    pub summary: String,
+    /// The details of the change, such as the old and new values
+    #[ts(type = "any")]
+    pub data: serde_json::Value,
+    /// When the change was made
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The kind of change a timeline event records
+#[derive(
+    Serialize, Deserialize, ToSchema, TS, EnumString, EnumVariantNames, Display, Clone, PartialEq,
+)]
+#[ts(export, export_to = ".generated/StaffTimelineEventKind.ts")]
+pub enum StaffTimelineEventKind {
+    /// The positions of the staff member changed
+    Positions,
+    /// The permission overrides of the staff member changed
+    PermOverrides,
+    /// The `no_autosync` or `unaccounted` flags of the staff member changed
+    Flags,
+    /// The staff member was added or removed
+    Membership,
+    /// A disciplinary action of the staff member was issued, reviewed, revoked or shortened
+    Disciplinary,
+    /// An onboarding of the staff member changed state
+    Onboarding,
+}
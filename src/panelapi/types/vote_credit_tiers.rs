@@ -16,7 +16,7 @@ use utoipa::ToSchema;
 /// Next 200 votes: 0.05 cents [Tier 2]
 /// Next 50 votes: 0.025 cents [Tier 3]
 /// Last 275 votes: 0.025 cents [last tier used at end of tiering]
-#[derive(Serialize, Deserialize, TS, Clone)]
+#[derive(Serialize, Deserialize, TS, ToSchema, Clone)]
 #[ts(export, export_to = ".generated/VoteCreditTier.ts")]
 pub struct VoteCreditTier {
     /// The ID of the tier
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utoipa::ToSchema;
 
+use super::review_checklists::ReviewChecklistItem;
 use crate::impls::target_types::TargetType;
 
 use super::{auth::AuthData, staff_members::StaffMember};
@@ -31,6 +34,8 @@ pub struct CoreConstants {
     pub htmlsanitize_url: String,
     /// Servers
     pub servers: PanelServers,
+    /// The checklist reviewers must fill in when approving or denying a bot
+    pub review_checklist: Vec<ReviewChecklistItem>,
 }
 
 /// Same as CONFIG.servers but using strings instead of NonZeroU64s
@@ -40,6 +45,8 @@ pub struct PanelServers {
     pub main: String,
     pub staff: String,
     pub testing: String,
+    /// All guild aliases positions can have corresponding roles in, mapped to their guild IDs
+    pub aliases: HashMap<String, String>,
 }
 
 /// StartAuth contains the needed data to begin a login
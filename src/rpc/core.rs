@@ -4,11 +4,14 @@ use serde_json::json;
 use serenity::all::{CreateEmbed, CreateEmbedFooter, CreateMessage, GuildId, UserId};
 use serenity::model::Color;
 use sqlx::{types::Uuid, PgPool};
+use std::collections::HashMap;
 use strum_macros::{Display, EnumString, EnumVariantNames};
 use ts_rs::TS;
 
 use crate::{
-    impls::{target_types::TargetType, utils::get_user_perms},
+    impls::{permissions::get_resolved_perms, target_types::TargetType},
+    panelapi::events::{self, PanelEvent},
+    panelapi::types::rpclogs::RPCLogEntry,
     Error,
 };
 use kittycat::perms;
@@ -41,10 +44,16 @@ pub enum RPCMethod {
     Approve {
         target_id: String,
         reason: String,
+        /// The answers to the review checklist, keyed by item ID
+        #[serde(default)]
+        checklist: HashMap<String, bool>,
     },
     Deny {
         target_id: String,
         reason: String,
+        /// The answers to the review checklist, keyed by item ID
+        #[serde(default)]
+        checklist: HashMap<String, bool>,
     },
     Unverify {
         target_id: String,
@@ -161,6 +170,16 @@ impl RPCField {
             placeholder: "Reason for performing this action".to_string(),
         }
     }
+
+    fn checklist() -> Self {
+        RPCField {
+            id: "checklist".to_string(),
+            label: "Review Checklist".to_string(),
+            field_type: FieldType::Checklist,
+            icon: "material-symbols:checklist".to_string(),
+            placeholder: "The items of the review checklist the entity passed".to_string(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, ToSchema, TS)]
@@ -174,6 +193,7 @@ pub enum FieldType {
     Number,
     Hour, // Time expressed as a number of hours
     Boolean,
+    Checklist, // The configured review checklist, submitted as a map of item ID to whether it passed
 }
 
 pub struct RPCHandle {
@@ -282,7 +302,7 @@ impl RPCMethod {
         }
 
         // Next, ensure we have the permissions needed
-        let user_perms = get_user_perms(&state.pool, &state.user_id).await?.resolve();
+        let user_perms = get_resolved_perms(&state.pool, &state.user_id).await?;
 
         let required_perm = format!("rpc.{}", self).into();
         if !perms::has_perm(&user_perms, &required_perm) {
@@ -295,20 +315,13 @@ impl RPCMethod {
         }
 
         // Also ensure that onboarding has happened
-        if sqlx::query!(
-            "SELECT COUNT(*) FROM staff_onboardings WHERE user_id = $1 AND void = false AND state = 'completed' AND NOW() - created_at < INTERVAL '1 month'",
-            &state.user_id,
-        )
-        .fetch_one(&state.pool)
-        .await?
-        .count
-        .unwrap_or(0) == 0 {
+        if !crate::impls::onboarding::has_valid_onboarding(&state.pool, &state.user_id).await? {
             return Err("You need to have completed onboarding in order to use RPC!".into());
         }
 
         // Insert into rpc_logs
         let id = sqlx::query!(
-            "INSERT INTO rpc_logs (method, user_id, data) VALUES ($1, $2, $3) RETURNING id",
+            "INSERT INTO rpc_logs (method, user_id, data) VALUES ($1, $2, $3) RETURNING id, created_at",
             self.to_string(),
             &state.user_id,
             json!(self)
@@ -342,30 +355,55 @@ impl RPCMethod {
         // Now we can handle the method
         let resp = self.handle_method(&state).await;
 
+        let log_state = match resp {
+            Ok(_) => "success".to_string(),
+            Err(ref e) => e.to_string(),
+        };
+
+        sqlx::query!(
+            "UPDATE rpc_logs SET state = $1 WHERE id = $2",
+            log_state,
+            id.id
+        )
+        .execute(&state.pool)
+        .await?;
+
+        events::publish(PanelEvent::RpcLogEntry {
+            entry: RPCLogEntry {
+                id: id.id.to_string(),
+                user_id: state.user_id.clone(),
+                method: self.to_string(),
+                state: log_state,
+                data: json!(self),
+                created_at: id.created_at,
+            },
+        });
+
         if resp.is_ok() {
-            sqlx::query!(
-                "UPDATE rpc_logs SET state = $1 WHERE id = $2",
-                "success",
-                id.id
-            )
-            .execute(&state.pool)
-            .await?;
-        } else {
-            sqlx::query!(
-                "UPDATE rpc_logs SET state = $1 WHERE id = $2",
-                resp.as_ref()
-                    .err()
-                    .ok_or("Err variant doesnt have an error!")?
-                    .to_string(),
-                id.id
-            )
-            .execute(&state.pool)
-            .await?;
+            if let Some(bot_id) = self.queue_target() {
+                events::publish(PanelEvent::QueueUpdate {
+                    bot_id: bot_id.to_string(),
+                    action: self.to_string(),
+                    user_id: Some(state.user_id.clone()),
+                });
+            }
         }
 
         resp
     }
 
+    /// Returns the bot whose queue state is changed by this method, if any
+    pub fn queue_target(&self) -> Option<&str> {
+        match self {
+            RPCMethod::Claim { target_id, .. }
+            | RPCMethod::Unclaim { target_id, .. }
+            | RPCMethod::Approve { target_id, .. }
+            | RPCMethod::Deny { target_id, .. }
+            | RPCMethod::Unverify { target_id, .. } => Some(target_id),
+            _ => None,
+        }
+    }
+
     /// The low-level method handler
     async fn handle_method(&self, state: &RPCHandle) -> Result<RPCSuccess, Error> {
         match self {
@@ -392,6 +430,21 @@ impl RPCMethod {
                             format!("This bot is already claimed by <@{}>", claimed_by).into()
                         );
                     }
+
+                    // Assigned bots are reserved for the assignee until the assignment expires
+                    if let Some(assignment) =
+                        crate::impls::assignment::get_pending_for_bot(&state.pool, target_id)
+                            .await?
+                    {
+                        if assignment.user_id != state.user_id {
+                            return Err(format!(
+                                "This bot is assigned to <@{}> until <t:{}:t>",
+                                assignment.user_id,
+                                assignment.expires_at.timestamp()
+                            )
+                            .into());
+                        }
+                    }
                 }
 
                 let owners = crate::impls::utils::get_entity_managers(
@@ -410,6 +463,21 @@ impl RPCMethod {
                 .execute(&state.pool)
                 .await?;
 
+                crate::impls::assignment::close_on_claim(&state.pool, target_id, &state.user_id)
+                    .await?;
+
+                // The bot is already claimed, a missing review channel should not undo that
+                if let Err(e) = crate::impls::sandbox::create(
+                    &state.cache_http,
+                    &state.pool,
+                    target_id,
+                    &state.user_id,
+                )
+                .await
+                {
+                    error!("Failed to create review channel for {}: {}", target_id, e);
+                }
+
                 sqlx::query!(
                     "INSERT INTO staff_general_logs (user_id, action, data) VALUES ($1, $2, $3)",
                     &state.user_id,
@@ -517,13 +585,37 @@ impl RPCMethod {
                     .send_message(&state.cache_http.http, msg)
                     .await?;
 
+                if let Err(e) = crate::impls::sandbox::archive(
+                    &state.cache_http,
+                    &state.pool,
+                    target_id,
+                    &self.to_string(),
+                )
+                .await
+                {
+                    error!("Failed to archive review channel of {}: {}", target_id, e);
+                }
+
+                // The bot is back in the queue, refresh its pre-review for the next reviewer
+                if let Err(e) =
+                    crate::impls::prereview::run(&state.pool, &state.cache_http, target_id).await
+                {
+                    error!("Failed to pre-review {}: {}", target_id, e);
+                }
+
                 Ok(RPCSuccess::NoContent)
             }
-            RPCMethod::Approve { target_id, reason } => {
+            RPCMethod::Approve {
+                target_id,
+                reason,
+                checklist,
+            } => {
                 if reason.len() > 2000 {
                     return Err("Reason must be lower than/equal to 2000 characters".into());
                 }
 
+                let answers = crate::impls::review::resolve_checklist(checklist, true)?;
+
                 let claimed = sqlx::query!(
                     "SELECT type, claimed_by, last_claimed FROM bots WHERE bot_id = $1",
                     target_id
@@ -553,6 +645,16 @@ impl RPCMethod {
                 )
                 .await?;
 
+                let invite_data = sqlx::query!(
+                    "SELECT client_id, invite FROM bots WHERE bot_id = $1",
+                    target_id
+                )
+                .fetch_one(&state.pool)
+                .await?;
+
+                let invite_analysis = crate::impls::invite::analyze(&invite_data.invite);
+                let invite_problems = crate::impls::invite::problems(&invite_analysis);
+
                 let mut tx = state.pool.begin().await?;
 
                 sqlx::query!(
@@ -562,6 +664,16 @@ impl RPCMethod {
                 .execute(&mut *tx)
                 .await?;
 
+                crate::impls::review::record(
+                    &mut *tx,
+                    target_id,
+                    &state.user_id,
+                    &self.to_string(),
+                    reason,
+                    &answers,
+                )
+                .await?;
+
                 // Add to cache server using borealis
                 #[derive(serde::Serialize, serde::Deserialize)]
                 struct BorealisCacheServer {
@@ -583,30 +695,49 @@ impl RPCMethod {
                     .await
                     .map_err(|e| format!("Error decoding borealis response: {:?}", e))?;
 
+                let mut embed = CreateEmbed::default()
+                    .title(" Approved!")
+                    .url(format!(
+                        "{}/bots/{}",
+                        crate::config::CONFIG.frontend_url.get(),
+                        target_id
+                    ))
+                    .description(format!(
+                        "<@!{}> has approved <@!{}>",
+                        &state.user_id, target_id
+                    ))
+                    .field(
+                        "Cache Server",
+                        format!("[{}](https://discord.gg/{})", csr.name, csr.invite_code),
+                        true,
+                    )
+                    .field("Feedback", reason, true)
+                    .field("Moderator", "<@!".to_string() + &state.user_id + ">", true)
+                    .footer(CreateEmbedFooter::new("Well done, young traveller!"))
+                    .color(0x00ff00);
+
+                if !answers.is_empty() {
+                    embed = embed.field(
+                        "Checklist",
+                        crate::impls::review::format_answers(&answers),
+                        false,
+                    );
+                }
+
+                if !invite_problems.is_empty() {
+                    embed = embed.field(
+                        "Invite Warnings",
+                        crate::impls::invite::format_analysis(
+                            &invite_analysis,
+                            &invite_data.client_id,
+                        ),
+                        false,
+                    );
+                }
+
                 let msg = CreateMessage::default()
                     .content(owners.mention_users())
-                    .embed(
-                        CreateEmbed::default()
-                            .title(" Approved!")
-                            .url(format!(
-                                "{}/bots/{}",
-                                crate::config::CONFIG.frontend_url.get(),
-                                target_id
-                            ))
-                            .description(format!(
-                                "<@!{}> has approved <@!{}>",
-                                &state.user_id, target_id
-                            ))
-                            .field(
-                                "Cache Server",
-                                format!("[{}](https://discord.gg/{})", csr.name, csr.invite_code),
-                                true,
-                            )
-                            .field("Feedback", reason, true)
-                            .field("Moderator", "<@!".to_string() + &state.user_id + ">", true)
-                            .footer(CreateEmbedFooter::new("Well done, young traveller!"))
-                            .color(0x00ff00),
-                    );
+                    .embed(embed);
 
                 crate::config::CONFIG
                     .channels
@@ -649,6 +780,17 @@ impl RPCMethod {
                     }
                 }
 
+                if let Err(e) = crate::impls::sandbox::archive(
+                    &state.cache_http,
+                    &state.pool,
+                    target_id,
+                    &self.to_string(),
+                )
+                .await
+                {
+                    error!("Failed to archive review channel of {}: {}", target_id, e);
+                }
+
                 // Kick the bot from the testing server
                 if member_on_guild(
                     &state.cache_http,
@@ -669,27 +811,33 @@ impl RPCMethod {
                     }
                 }
 
-                let invite_data =
-                    sqlx::query!("SELECT client_id FROM bots WHERE bot_id = $1", target_id)
-                        .fetch_one(&state.pool)
-                        .await?;
+                let mut content = format!(
+                    "**Cache Server Invite:** {csr_invite}\n**Invite URL:** {invite}",
+                    csr_invite = "https://discord.gg/".to_string() + &csr.invite_code,
+                    invite =
+                        crate::impls::invite::safe_invite(&invite_data.client_id, &csr.guild_id)
+                );
 
-                Ok(
-                    RPCSuccess::Content(
-                        format!(
-                            "**Cache Server Invite:** {csr_invite}\n**Invite URL:** https://discord.com/api/v10/oauth2/authorize?client_id={client_id}&permissions=0&scope=bot%20applications.commands&guild_id={guild_id}", 
-                            csr_invite = "https://discord.gg/".to_string() + &csr.invite_code,
-                            client_id = invite_data.client_id,
-                            guild_id = csr.guild_id
-                        )
-                    )
-                )
+                if !invite_problems.is_empty() {
+                    content.push_str(&format!(
+                        "\n**Invite warnings:** {}",
+                        invite_problems.join(", ")
+                    ));
+                }
+
+                Ok(RPCSuccess::Content(content))
             }
-            RPCMethod::Deny { target_id, reason } => {
+            RPCMethod::Deny {
+                target_id,
+                reason,
+                checklist,
+            } => {
                 if reason.len() > 2000 {
                     return Err("Reason must be lower than/equal to 2000 characters".into());
                 }
 
+                let answers = crate::impls::review::resolve_checklist(checklist, false)?;
+
                 let claimed = sqlx::query!(
                     "SELECT type, claimed_by, owner, last_claimed FROM bots WHERE bot_id = $1",
                     target_id
@@ -719,29 +867,53 @@ impl RPCMethod {
                 )
                 .await?;
 
+                let mut tx = state.pool.begin().await?;
+
                 sqlx::query!(
                     "UPDATE bots SET type = 'denied', claimed_by = NULL WHERE bot_id = $1",
                     target_id
                 )
-                .execute(&state.pool)
+                .execute(&mut *tx)
                 .await?;
 
-                let msg = CreateMessage::new().content(owners.mention_users()).embed(
-                    CreateEmbed::default()
-                        .title(" Denied!")
-                        .url(format!(
-                            "{}/bots/{}",
-                            crate::config::CONFIG.frontend_url.get(),
-                            target_id
-                        ))
-                        .description(format!("<@{}> has denied <@{}>", &state.user_id, target_id))
-                        .field("Reason", reason, true)
-                        .field("Moderator", "<@!".to_string() + &state.user_id + ">", true)
-                        .footer(CreateEmbedFooter::new(
-                            "Well done, young traveller at getting denied from the club!",
-                        ))
-                        .color(0x00ff00),
-                );
+                crate::impls::review::record(
+                    &mut *tx,
+                    target_id,
+                    &state.user_id,
+                    &self.to_string(),
+                    reason,
+                    &answers,
+                )
+                .await?;
+
+                tx.commit().await?;
+
+                let mut embed = CreateEmbed::default()
+                    .title(" Denied!")
+                    .url(format!(
+                        "{}/bots/{}",
+                        crate::config::CONFIG.frontend_url.get(),
+                        target_id
+                    ))
+                    .description(format!("<@{}> has denied <@{}>", &state.user_id, target_id))
+                    .field("Reason", reason, true)
+                    .field("Moderator", "<@!".to_string() + &state.user_id + ">", true)
+                    .footer(CreateEmbedFooter::new(
+                        "Well done, young traveller at getting denied from the club!",
+                    ))
+                    .color(0x00ff00);
+
+                if !answers.is_empty() {
+                    embed = embed.field(
+                        "Checklist",
+                        crate::impls::review::format_answers(&answers),
+                        false,
+                    );
+                }
+
+                let msg = CreateMessage::new()
+                    .content(owners.mention_users())
+                    .embed(embed);
 
                 crate::config::CONFIG
                     .channels
@@ -749,6 +921,17 @@ impl RPCMethod {
                     .send_message(&state.cache_http.http, msg)
                     .await?;
 
+                if let Err(e) = crate::impls::sandbox::archive(
+                    &state.cache_http,
+                    &state.pool,
+                    target_id,
+                    &self.to_string(),
+                )
+                .await
+                {
+                    error!("Failed to archive review channel of {}: {}", target_id, e);
+                }
+
                 Ok(RPCSuccess::NoContent)
             }
             RPCMethod::Unverify { target_id, reason } => {
@@ -796,6 +979,14 @@ impl RPCMethod {
                     .mod_logs
                     .send_message(&state.cache_http.http, msg)
                     .await?;
+
+                // The bot is back in the queue, refresh its pre-review for the next reviewer
+                if let Err(e) =
+                    crate::impls::prereview::run(&state.pool, &state.cache_http, target_id).await
+                {
+                    error!("Failed to pre-review {}: {}", target_id, e);
+                }
+
                 Ok(RPCSuccess::NoContent)
             }
             RPCMethod::PremiumAdd {
@@ -1420,8 +1611,16 @@ impl RPCMethod {
                 },
             ],
             RPCMethod::Unclaim { .. } => vec![RPCField::target_id(), RPCField::reason()],
-            RPCMethod::Approve { .. } => vec![RPCField::target_id(), RPCField::reason()],
-            RPCMethod::Deny { .. } => vec![RPCField::target_id(), RPCField::reason()],
+            RPCMethod::Approve { .. } => vec![
+                RPCField::target_id(),
+                RPCField::reason(),
+                RPCField::checklist(),
+            ],
+            RPCMethod::Deny { .. } => vec![
+                RPCField::target_id(),
+                RPCField::reason(),
+                RPCField::checklist(),
+            ],
             RPCMethod::Unverify { .. } => vec![RPCField::target_id(), RPCField::reason()],
             RPCMethod::PremiumAdd { .. } => vec![
                 RPCField::target_id(),
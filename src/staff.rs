@@ -1,9 +1,14 @@
-use crate::{checks, impls::utils::get_user_perms};
-use kittycat::perms;
-use poise::serenity_prelude::CreateEmbed;
-use poise::serenity_prelude::GuildId;
-use poise::serenity_prelude::User;
+use crate::impls::dovewing::DovewingSource;
+use crate::{checks, impls::permissions};
+use futures_util::StreamExt;
+use poise::serenity_prelude::{
+    ButtonStyle, ComponentInteractionDataKind, CreateActionRow, CreateButton, CreateEmbed,
+    CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, EditMessage, GuildId, User,
+};
 use poise::CreateReply;
+use sqlx::types::uuid::Uuid;
+use std::collections::HashMap;
+use std::time::Duration;
 
 type Error = crate::Error;
 type Context<'a> = crate::Context<'a>;
@@ -14,14 +19,44 @@ type Context<'a> = crate::Context<'a>;
     prefix_command,
     slash_command,
     guild_cooldown = 10,
-    subcommands("staff_list", "staff_guildlist", "staff_guildleave", "staff_stats")
+    subcommands(
+        "staff_list",
+        "staff_guildlist",
+        "staff_guildleave",
+        "staff_stats",
+        "staff_history"
+    )
 )]
 pub async fn staff(ctx: Context<'_>) -> Result<(), Error> {
-    ctx.say("Some available options are ``staff list``, ``staff guildlist``, ``staff_guildleave``, ``staff_stats``")
+    ctx.say("Some available options are ``staff list``, ``staff guildlist``, ``staff_guildleave``, ``staff_stats``, ``staff history``")
         .await?;
     Ok(())
 }
 
+/// The number of staff members shown per page of a position
+const STAFF_LIST_PAGE_SIZE: usize = 10;
+
+/// Discord select menus can have at most 25 options, two are kept for moving between pages of positions
+const POSITION_MENU_PAGE_SIZE: usize = 25 - 2;
+
+/// The number of timeline events shown by ``staff history``
+const STAFF_HISTORY_EVENTS: usize = 25;
+
+/// Discord embed descriptions can be at most 4096 characters
+const MAX_EMBED_DESCRIPTION: usize = 4096;
+
+struct ListedPosition {
+    id: Uuid,
+    name: String,
+    index: i32,
+}
+
+struct ListedMember {
+    user_id: String,
+    positions: Vec<Uuid>,
+}
+
+/// Lists the staff members of each position
 #[poise::command(
     rename = "list",
     track_edits,
@@ -29,138 +64,270 @@ pub async fn staff(ctx: Context<'_>) -> Result<(), Error> {
     slash_command,
     check = "checks::staff_server"
 )]
-pub async fn staff_list(_: Context<'_>) -> Result<(), Error> {
-    Err("This command is currently disabled".into())
-
-    /* TODO: FINISH REWRITING
-    // Get list of users with staff flag set to true
+pub async fn staff_list(ctx: Context<'_>) -> Result<(), Error> {
     let data = ctx.data();
 
-    let server_id = match ctx.guild_id() {
-        Some(server_id) => server_id,
-        None => return Err("This command can only be used in a server".into()),
-    };
+    let positions = sqlx::query!("SELECT id, name, index FROM staff_positions ORDER BY index ASC")
+        .fetch_all(&data.pool)
+        .await?
+        .into_iter()
+        .map(|p| ListedPosition {
+            id: p.id,
+            name: p.name,
+            index: p.index,
+        })
+        .collect::<Vec<ListedPosition>>();
+
+    if positions.is_empty() {
+        ctx.say("There are no staff positions!").await?;
+        return Ok(());
+    }
 
-    let positions = sqlx::query!(
-        "SELECT id, name FROM staff_positions ORDER BY index ASC"
+    let members =
+        sqlx::query!("SELECT user_id, positions FROM staff_members ORDER BY created_at ASC")
+            .fetch_all(&data.pool)
+            .await?
+            .into_iter()
+            .map(|m| ListedMember {
+                user_id: m.user_id,
+                positions: m.positions,
+            })
+            .collect::<Vec<ListedMember>>();
+
+    // Disciplinaries are only shown to those who can view the disciplinaries of others
+    let mut disciplinaries = HashMap::new();
+
+    if permissions::has_perm(
+        &data.pool,
+        &ctx.author().id.to_string(),
+        "staff_disciplinaries.view",
     )
-    .fetch_all(&data.pool)
-    .await?;
-
-    let mut select_menus = Vec::<CreateSelectMenuOption>::new();
+    .await?
+    {
+        let active = sqlx::query!(
+            "SELECT d.user_id, d.title, t.name FROM staff_disciplinary d
+            INNER JOIN staff_disciplinary_types t ON t.id = d.type
+            WHERE d.state = 'active' AND (d.expiry IS NULL OR NOW() - d.created_at < d.expiry)
+            ORDER BY d.created_at ASC"
+        )
+        .fetch_all(&data.pool)
+        .await?;
 
-    for position in positions {
-        select_menus.push(
-            CreateSelectMenuOption::new(format!("{} ({})", position.name, position.id), position.id)
-                .description("View staff member's with this position"),
-        );
+        for disc in active {
+            disciplinaries
+                .entry(disc.user_id)
+                .or_insert_with(Vec::new)
+                .push(format!("{} ({})", disc.title, disc.name));
+        }
     }
 
-    // Create select menu
-    let msg = ctx
+    let mut current_position = 0;
+    let mut current_page = 0;
+
+    let mut msg = ctx
         .send(
-            CreateReply::new()
-                .content("**Please select a position to view a list of staff members**")
-                .components(vec![
-                    CreateActionRow::SelectMenu(CreateSelectMenu::new(
-                        "Choose a position",
-                        CreateSelectMenuKind::String {
-                            options: select_menus.clone(),
-                        },
-                    )),
-                    CreateActionRow::Buttons(vec![CreateButton::new("sl:cancel").label("Cancel")]),
-                ]),
+            _staff_list_page(
+                ctx,
+                &positions,
+                &members,
+                &disciplinaries,
+                current_position,
+                current_page,
+            )
+            .await?,
         )
         .await?
         .into_message()
         .await?;
 
-    // Wait for user to select a staff member
-    let interaction = msg
-        .await_component_interactions(ctx.serenity_context())
+    let mut interaction = msg
+        .await_component_interactions(ctx.serenity_context().shard.clone())
         .author_id(ctx.author().id)
-        .timeout(Duration::from_secs(120));
-
-    let mut collect_stream = interaction.stream();
+        .timeout(Duration::from_secs(120))
+        .stream();
 
-    while let Some(item) = collect_stream.next().await {
-        item.defer(&ctx.serenity_context()).await?;
+    while let Some(item) = interaction.next().await {
+        item.defer(&ctx.serenity_context().http).await?;
 
         let id = &item.data.custom_id;
 
         if id == "sl:cancel" {
-            log::info!("Received cancel interaction");
-            item.delete_response(ctx.serenity_context()).await?;
+            item.delete_response(&ctx.serenity_context().http).await?;
             return Ok(());
         }
 
-        // Get select menu value
-        let values = match &item.data.kind {
-            ComponentInteractionDataKind::StringSelect { values } => values,
-            _ => {
-                log::info!("Received interaction of wrong type: {:?}", item.data.kind);
+        if id == "sl:position" {
+            let ComponentInteractionDataKind::StringSelect { values } = &item.data.kind else {
                 continue;
-            }
-        };
+            };
 
-        let id = match values.get(0) {
-            Some(id) => id,
-            None => {
-                log::info!("Failed to get select menu value");
+            let Some(selected) = values.first().and_then(|v| v.parse::<usize>().ok()) else {
                 continue;
-            }
-        };
-
-        log::info!("Received interaction: {}", id);
+            };
 
-        let user_id = match id.parse::<UserId>() {
-            Ok(id) => id,
-            Err(_) => {
-                log::info!("Failed to parse user_id: {}", id);
+            if selected >= positions.len() {
                 continue;
             }
-        };
 
-        let member = {
-            let cache_user = ctx.serenity_context().cache.member(server_id, user_id);
+            current_position = selected;
+            current_page = 0;
+        } else if id == "sl:prev" {
+            current_page = current_page.saturating_sub(1);
+        } else if id == "sl:next" {
+            current_page += 1;
+        }
 
-            match cache_user {
-                Some(user) => user.clone(),
-                None => {
-                    log::error!("Failed to get user from cache: {}", user_id);
-                    continue;
-                }
-            }
-        };
+        msg.edit(
+            ctx,
+            _staff_list_page(
+                ctx,
+                &positions,
+                &members,
+                &disciplinaries,
+                current_position,
+                current_page,
+            )
+            .await?
+            .to_prefix_edit(EditMessage::default()),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Builds a page of the staff members of a position
+async fn _staff_list_page(
+    ctx: Context<'_>,
+    positions: &[ListedPosition],
+    members: &[ListedMember],
+    disciplinaries: &HashMap<String, Vec<String>>,
+    position: usize,
+    page: usize,
+) -> Result<CreateReply, Error> {
+    let pos = &positions[position];
+
+    let in_position = members
+        .iter()
+        .filter(|m| m.positions.contains(&pos.id))
+        .collect::<Vec<&ListedMember>>();
+
+    let total_pages = in_position.len().div_ceil(STAFF_LIST_PAGE_SIZE).max(1);
+    let page = page.min(total_pages - 1);
+
+    let mut embed = CreateEmbed::default()
+        .title(format!("{} [{}/{}]", pos.name, page + 1, total_pages))
+        .description(format!(
+            "There are {} staff members with this position (index {})",
+            in_position.len(),
+            pos.index
+        ));
+
+    for member in in_position
+        .iter()
+        .skip(page * STAFF_LIST_PAGE_SIZE)
+        .take(STAFF_LIST_PAGE_SIZE)
+    {
+        let user = crate::impls::dovewing::get_platform_user(
+            &ctx.data().pool,
+            DovewingSource::Discord(botox::cache::CacheHttpImpl::from_ctx(
+                ctx.serenity_context(),
+            )),
+            &member.user_id,
+        )
+        .await?;
+
+        // Positions are listed in index order as the position list is sorted
+        let member_positions = positions
+            .iter()
+            .filter(|p| member.positions.contains(&p.id))
+            .map(|p| p.name.clone())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let mut value = format!("<@{}>\n**Positions:** {}", member.user_id, member_positions);
 
-        let msg = EditInteractionResponse::new()
-            .content("")
-            .embed(
-                CreateEmbed::default()
-                    .title(format!(
-                        "{}'s [{}] information",
-                        member.user.name,
-                        member.display_name()
-                    ))
-                    .description("This is the information we have on this staff member")
-                    .field("User ID", staff.user_id, true)
-                    .field("Permissions", perms, true),
+        if let Some(discs) = disciplinaries.get(&member.user_id) {
+            value.push_str(&format!("\n**Disciplinaries:** {}", discs.join(", ")));
+        }
+
+        embed = embed.field(
+            format!("{} ({})", user.display_name, member.user_id),
+            value,
+            false,
+        );
+    }
+
+    // The menu shows the page of positions the current position is on, the navigation options
+    // select the first position of the previous or next page
+    let menu_start = position - position % POSITION_MENU_PAGE_SIZE;
+    let menu_end = (menu_start + POSITION_MENU_PAGE_SIZE).min(positions.len());
+
+    let mut options = Vec::new();
+
+    if menu_start > 0 {
+        options.push(
+            CreateSelectMenuOption::new(
+                "« Previous positions",
+                (menu_start - POSITION_MENU_PAGE_SIZE).to_string(),
             )
-            .components(vec![
-                CreateActionRow::SelectMenu(CreateSelectMenu::new(
-                    "Choose a staff member",
-                    CreateSelectMenuKind::String {
-                        options: select_menus.clone(),
-                    },
-                )),
-                CreateActionRow::Buttons(vec![CreateButton::new("sl:cancel").label("Cancel")]),
-            ]);
+            .description(format!(
+                "Positions {} to {}",
+                menu_start - POSITION_MENU_PAGE_SIZE + 1,
+                menu_start
+            )),
+        );
+    }
 
-        item.edit_response(ctx.serenity_context(), msg).await?;
+    options.extend(
+        positions
+            .iter()
+            .enumerate()
+            .skip(menu_start)
+            .take(POSITION_MENU_PAGE_SIZE)
+            .map(|(i, p)| {
+                CreateSelectMenuOption::new(p.name.clone(), i.to_string())
+                    .description("View the staff members with this position")
+                    .default_selection(i == position)
+            }),
+    );
+
+    if menu_end < positions.len() {
+        options.push(
+            CreateSelectMenuOption::new("More positions »", menu_end.to_string()).description(
+                format!(
+                    "Positions {} to {}",
+                    menu_end + 1,
+                    (menu_end + POSITION_MENU_PAGE_SIZE).min(positions.len())
+                ),
+            ),
+        );
     }
 
-    Ok(())
-    */
+    Ok(CreateReply::default().embed(embed).components(vec![
+        CreateActionRow::SelectMenu(
+            CreateSelectMenu::new("sl:position", CreateSelectMenuKind::String { options })
+                .placeholder(format!(
+                    "Choose a position ({}-{} of {})",
+                    menu_start + 1,
+                    menu_end,
+                    positions.len()
+                )),
+        ),
+        CreateActionRow::Buttons(vec![
+            CreateButton::new("sl:prev")
+                .label("Previous")
+                .style(ButtonStyle::Primary)
+                .disabled(page == 0),
+            CreateButton::new("sl:cancel")
+                .label("Cancel")
+                .style(ButtonStyle::Danger),
+            CreateButton::new("sl:next")
+                .label("Next")
+                .style(ButtonStyle::Primary)
+                .disabled(page + 1 >= total_pages),
+        ]),
+    ]))
 }
 
 /// Get guild list, this is intentionally public
@@ -197,11 +364,13 @@ pub async fn staff_guildleave(
     ctx: Context<'_>,
     #[description = "The guild ID to leave"] guild: String,
 ) -> Result<(), Error> {
-    let user_perms = get_user_perms(&ctx.data().pool, &ctx.author().id.to_string())
-        .await?
-        .resolve();
-
-    if !perms::has_perm(&user_perms, &"arcadia.leave_guilds".into()) {
+    if !permissions::has_perm(
+        &ctx.data().pool,
+        &ctx.author().id.to_string(),
+        "arcadia.leave_guilds",
+    )
+    .await?
+    {
         return Err("You do not have permission to use this command".into());
     }
 
@@ -259,3 +428,88 @@ pub async fn staff_stats(
     ctx.send(msg).await?;
     Ok(())
 }
+
+/// Shows the latest changes made to a staff member
+#[poise::command(
+    rename = "history",
+    prefix_command,
+    slash_command,
+    check = "checks::staff_server"
+)]
+pub async fn staff_history(
+    ctx: Context<'_>,
+    #[description = "The staff member to show the history of, defaults to yourself"] user: Option<
+        User,
+    >,
+) -> Result<(), Error> {
+    let data = ctx.data();
+    let user = user.unwrap_or_else(|| ctx.author().clone());
+
+    if user.id != ctx.author().id
+        && !permissions::has_perm(
+            &data.pool,
+            &ctx.author().id.to_string(),
+            "staff_members.history",
+        )
+        .await?
+    {
+        return Err(
+            "You do not have permission to view the history of other staff members (staff_members.history)"
+                .into(),
+        );
+    }
+
+    let timeline = crate::impls::timeline::get_timeline(&data.pool, &user.id.to_string()).await?;
+
+    if timeline.is_empty() {
+        ctx.say("No history recorded for this staff member").await?;
+        return Ok(());
+    }
+
+    // The latest events are picked newest first (stopping before the embed description limit is
+    // hit, leaving room for the note below) and then shown oldest to newest
+    let mut lines = Vec::new();
+    let mut length = 0;
+
+    for event in timeline.iter().rev().take(STAFF_HISTORY_EVENTS) {
+        let line = match event.actor_id {
+            Some(ref actor_id) => format!(
+                "<t:{}:f> {} (by <@{}>)\n",
+                event.created_at.timestamp(),
+                event.summary,
+                actor_id
+            ),
+            None => format!("<t:{}:f> {}\n", event.created_at.timestamp(), event.summary),
+        };
+
+        if length + line.len() > MAX_EMBED_DESCRIPTION - 100 {
+            break;
+        }
+
+        length += line.len();
+        lines.push(line);
+    }
+
+    let mut description = String::new();
+
+    if lines.len() < timeline.len() {
+        description.push_str(&format!(
+            "*Showing the latest {} of {} events, see the panel for the full history*\n\n",
+            lines.len(),
+            timeline.len()
+        ));
+    }
+
+    description.extend(lines.into_iter().rev());
+
+    let embed = CreateEmbed::default()
+        .title(format!("History of {}", user.name))
+        .description(description)
+        .footer(poise::serenity_prelude::CreateEmbedFooter::new(format!(
+            "{} events recorded in total",
+            timeline.len()
+        )));
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
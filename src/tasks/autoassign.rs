@@ -0,0 +1,80 @@
+use log::{error, info};
+use poise::serenity_prelude::{CreateEmbed, CreateMessage};
+
+pub async fn auto_assign(ctx: &serenity::client::Context) -> Result<(), crate::Error> {
+    let data = ctx.data::<crate::Data>();
+    let pool = &data.pool;
+    let cache_http = botox::cache::CacheHttpImpl::from_ctx(ctx);
+
+    // Assignments that were not accepted in time go back to the open queue
+    for expired in crate::impls::assignment::expire(pool).await? {
+        info!(
+            "Assignment of bot {} to {} expired",
+            expired.bot_id, expired.user_id
+        );
+
+        if let Err(e) = crate::config::CONFIG
+            .channels
+            .testing_lounge
+            .send_message(
+                &ctx.http,
+                CreateMessage::default().embed(
+                    CreateEmbed::default()
+                        .title("Assignment Expired")
+                        .description(format!(
+                            "<@{}> did not claim <@{}> in time, it is now back in the open queue",
+                            expired.user_id, expired.bot_id
+                        ))
+                        .color(0xFF0000),
+                ),
+            )
+            .await
+        {
+            error!("Error while sending expired assignment to #lounge: {}", e);
+        }
+    }
+
+    let queue = crate::impls::queue::get_queue(pool, false).await?;
+
+    let unassigned = sqlx::query!(
+        "SELECT bot_id FROM bots b WHERE bot_id = ANY($1) AND claimed_by IS NULL
+        AND NOT EXISTS (SELECT 1 FROM bot_assignments a WHERE a.bot_id = b.bot_id AND a.state = 'pending')",
+        &queue.iter().map(|p| p.bot_id.clone()).collect::<Vec<_>>()
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Error while fetching unassigned bots: {}", e))?
+    .into_iter()
+    .map(|r| r.bot_id)
+    .collect::<Vec<_>>();
+
+    for priority in queue.iter().filter(|p| unassigned.contains(&p.bot_id)) {
+        // Bots whose assignment expired stay in the open queue until they re-enter it
+        let expired = sqlx::query!(
+            "SELECT COUNT(*) FROM bot_assignments WHERE bot_id = $1 AND state = 'expired' AND created_at >= $2",
+            priority.bot_id,
+            priority.queued_at
+        )
+        .fetch_one(pool)
+        .await?
+        .count
+        .unwrap_or(0);
+
+        if expired > 0 {
+            continue;
+        }
+
+        let Some(user_id) =
+            crate::impls::assignment::next_reviewer(pool, &priority.bot_id, priority.queued_at)
+                .await?
+        else {
+            continue;
+        };
+
+        info!("Assigning bot {} to {}", priority.bot_id, user_id);
+
+        crate::impls::assignment::assign(&cache_http, pool, &priority.bot_id, &user_id).await?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,10 @@
 use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter, CreateMessage};
 
-use crate::{config, impls::target_types::TargetType};
+use crate::{
+    config,
+    impls::target_types::TargetType,
+    panelapi::events::{self, PanelEvent},
+};
 
 // Internal struct used to send notifications on unclaimed bots
 struct AutoUnclaimNotification {
@@ -76,6 +80,17 @@ pub async fn auto_unclaim(ctx: &serenity::all::Context) -> Result<(), crate::Err
                 .await
                 .map_err(|e| format!("Error while unclaiming bot {}: {}", bot.bot_id, e))?;
 
+                // Recorded for staff activity tracking
+                sqlx::query!(
+                    "INSERT INTO staff_auto_unclaims (bot_id, user_id, claimed_at) VALUES ($1, $2, $3)",
+                    bot.bot_id,
+                    claimed_by,
+                    last_claimed
+                )
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Error while recording auto-unclaim of {}: {}", bot.bot_id, e))?;
+
                 notifications.push(AutoUnclaimNotification {
                     bot_id: bot.bot_id,
                     claimed_by,
@@ -89,7 +104,26 @@ pub async fn auto_unclaim(ctx: &serenity::all::Context) -> Result<(), crate::Err
         .await
         .map_err(|e| format!("Error while committing transaction: {}", e))?;
 
+    let cache_http = botox::cache::CacheHttpImpl::from_ctx(ctx);
+
     for notification in notifications {
+        if let Err(e) =
+            crate::impls::sandbox::archive(&cache_http, pool, &notification.bot_id, "AutoUnclaim")
+                .await
+        {
+            log::error!(
+                "Failed to archive review channel of {}: {}",
+                notification.bot_id,
+                e
+            );
+        }
+
+        events::publish(PanelEvent::QueueUpdate {
+            bot_id: notification.bot_id.clone(),
+            action: "Unclaim".to_string(),
+            user_id: None,
+        });
+
         // Now send message in #lounge
         let msg = CreateMessage::default()
         .content(format!("<@{}>", notification.claimed_by))
@@ -1,11 +1,16 @@
 pub mod assetcleaner;
+pub mod autoassign;
 pub mod autounclaim;
 pub mod bans;
 pub mod deletedbots;
 pub mod genericcleaner;
 pub mod japiupdate;
+pub mod onboardingreminder;
 pub mod premium;
+pub mod prereview;
+pub mod queuesla;
 pub mod specrolesync;
+pub mod staffactivity;
 pub mod staffresync;
 pub mod teamcleaner;
 pub mod topreviewersync;
@@ -14,8 +19,36 @@ pub mod voterestter;
 use botox::taskman::Task;
 use futures_util::FutureExt;
 
+use crate::panelapi::events::{self, PanelEvent};
+
+/// Wraps a task so that its outcome is published to the panel once it finishes running
+fn publish_outcome(task: Task) -> Task {
+    let name = task.name;
+    let run = task.run;
+
+    Task {
+        name: task.name,
+        description: task.description,
+        enabled: task.enabled,
+        duration: task.duration,
+        run: Box::new(move |ctx| {
+            run(ctx)
+                .map(move |res| {
+                    events::publish(PanelEvent::TaskOutcome {
+                        name: name.to_string(),
+                        success: res.is_ok(),
+                        error: res.as_ref().err().map(|e| e.to_string()),
+                    });
+
+                    res
+                })
+                .boxed()
+        }),
+    }
+}
+
 pub fn tasks() -> Vec<Task> {
-    vec![
+    let tasks = vec![
         Task {
             name: "asset_cleaner",
             description: "Cleaning up orphaned assets",
@@ -23,6 +56,13 @@ pub fn tasks() -> Vec<Task> {
             duration: std::time::Duration::from_secs(450),
             run: Box::new(move |ctx| crate::tasks::assetcleaner::asset_cleaner(ctx).boxed()),
         },
+        Task {
+            name: "auto_assign",
+            description: "Assigning pending bots to opted-in reviewers",
+            enabled: crate::config::CONFIG.assignment.enabled,
+            duration: std::time::Duration::from_secs(60),
+            run: Box::new(move |ctx| crate::tasks::autoassign::auto_assign(ctx).boxed()),
+        },
         Task {
             name: "auto_unclaim",
             description: "Checking for claimed bots greater than 1 hour claim interval",
@@ -51,6 +91,15 @@ pub fn tasks() -> Vec<Task> {
             duration: std::time::Duration::from_secs(400),
             run: Box::new(move |ctx| crate::tasks::genericcleaner::generic_cleaner(ctx).boxed()),
         },
+        Task {
+            name: "onboarding_reminder",
+            description: "Reminding staff of expiring onboardings",
+            enabled: true,
+            duration: std::time::Duration::from_secs(60 * 60),
+            run: Box::new(move |ctx| {
+                crate::tasks::onboardingreminder::onboarding_reminder(ctx).boxed()
+            }),
+        },
         Task {
             name: "premium_remove",
             description: "Removing expired subscriptions",
@@ -58,6 +107,20 @@ pub fn tasks() -> Vec<Task> {
             duration: std::time::Duration::from_secs(75),
             run: Box::new(move |ctx| crate::tasks::premium::premium_remove(ctx).boxed()),
         },
+        Task {
+            name: "pre_review",
+            description: "Running automated pre-review checks on queued bots",
+            enabled: true,
+            duration: std::time::Duration::from_secs(5 * 60),
+            run: Box::new(move |ctx| crate::tasks::prereview::pre_review(ctx).boxed()),
+        },
+        Task {
+            name: "queue_sla",
+            description: "Alerting on bots waiting too long in the queue",
+            enabled: true,
+            duration: std::time::Duration::from_secs(15 * 60),
+            run: Box::new(move |ctx| crate::tasks::queuesla::queue_sla(ctx).boxed()),
+        },
         Task {
             name: "spec_role_sync",
             description: "Syncing special roles",
@@ -65,6 +128,13 @@ pub fn tasks() -> Vec<Task> {
             duration: std::time::Duration::from_secs(50),
             run: Box::new(move |ctx| crate::tasks::specrolesync::spec_role_sync(ctx).boxed()),
         },
+        Task {
+            name: "staff_activity",
+            description: "Reporting staff activity",
+            enabled: true,
+            duration: std::time::Duration::from_secs(60 * 60),
+            run: Box::new(move |ctx| crate::tasks::staffactivity::staff_activity(ctx).boxed()),
+        },
         Task {
             name: "staff_resync",
             description: "Resyncing staff permissions",
@@ -100,5 +170,7 @@ pub fn tasks() -> Vec<Task> {
             duration: std::time::Duration::from_secs(60 * 2),
             run: Box::new(move |ctx| crate::tasks::japiupdate::japi_updater(ctx).boxed()),
         },
-    ]
+    ];
+
+    tasks.into_iter().map(publish_outcome).collect()
 }
@@ -0,0 +1,69 @@
+use poise::serenity_prelude::{CreateEmbed, CreateMessage, UserId};
+
+use crate::panelapi::types::staff_onboardings::StaffOnboardingState;
+
+pub async fn onboarding_reminder(ctx: &serenity::client::Context) -> Result<(), crate::Error> {
+    let data = ctx.data::<crate::Data>();
+    let pool = &data.pool;
+
+    // Onboardings superseded by a later completed onboarding do not need a reminder. Staff on leave
+    // are reminded once they are back if their onboarding has not expired yet
+    let expiring = sqlx::query!(
+        "SELECT o.id, o.user_id, o.expires_at FROM staff_onboardings o
+        INNER JOIN staff_members sm ON sm.user_id = o.user_id
+        WHERE o.state = $1 AND o.void = false AND o.reminded = false
+        AND o.expires_at > NOW() AND o.expires_at < NOW() + make_interval(days => $2)
+        AND NOT EXISTS (
+            SELECT 1 FROM staff_onboardings n WHERE n.user_id = o.user_id AND n.state = $1 AND n.void = false AND n.expires_at > o.expires_at
+        )
+        AND NOT EXISTS (
+            SELECT 1 FROM staff_loas l WHERE l.user_id = o.user_id AND l.state = 'approved' AND l.start_date <= NOW() AND l.end_date > NOW()
+        )",
+        StaffOnboardingState::Completed.to_string(),
+        crate::config::CONFIG.onboarding.reminder_days as i32
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for onboarding in expiring {
+        let Ok(user_id) = onboarding.user_id.parse::<UserId>() else {
+            log::warn!("Invalid user ID on onboarding {}", onboarding.id);
+            continue;
+        };
+
+        let expires_at = onboarding
+            .expires_at
+            .map(|e| format!("<t:{}:R>", e.timestamp()))
+            .unwrap_or_default();
+
+        // Staff members may have DMs closed, the reminder is still marked as sent so we don't retry every run
+        if let Err(e) = user_id
+            .direct_message(
+                &ctx.http,
+                CreateMessage::new().embeds(vec![CreateEmbed::new()
+                    .title("Onboarding Expiring")
+                    .description(format!(
+                        "Your onboarding expires {}. Once it expires you will not be able to approve or deny bots or use RPC until you are onboarded again, use ``/onboarding start`` to start a new onboarding",
+                        expires_at
+                    ))
+                    .field("ID", onboarding.id.hyphenated().to_string(), false)]),
+            )
+            .await
+        {
+            log::warn!(
+                "Failed to send onboarding reminder to {}: {}",
+                onboarding.user_id,
+                e
+            );
+        }
+
+        sqlx::query!(
+            "UPDATE staff_onboardings SET reminded = true WHERE id = $1",
+            onboarding.id
+        )
+        .execute(pool)
+        .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use log::{error, info};
+
+pub async fn pre_review(ctx: &serenity::client::Context) -> Result<(), crate::Error> {
+    let data = ctx.data::<crate::Data>();
+    let pool = &data.pool;
+
+    let queue = crate::impls::queue::get_queue(pool, false).await?;
+
+    let pre_reviews = crate::impls::prereview::get_pre_reviews(
+        pool,
+        &queue.iter().map(|p| p.bot_id.clone()).collect::<Vec<_>>(),
+    )
+    .await?;
+
+    let cache_http = botox::cache::CacheHttpImpl::from_ctx(ctx);
+
+    for priority in queue {
+        // Bots are checked again whenever they re-enter the queue
+        if pre_reviews
+            .get(&priority.bot_id)
+            .is_some_and(|p| p.created_at >= priority.queued_at)
+        {
+            continue;
+        }
+
+        match crate::impls::prereview::run(pool, &cache_http, &priority.bot_id).await {
+            Ok(pre_review) => info!(
+                "Pre-reviewed bot {}: {}/{} checks passed",
+                priority.bot_id,
+                pre_review.checks.iter().filter(|c| c.passed).count(),
+                pre_review.checks.len()
+            ),
+            Err(e) => error!("Error while pre-reviewing bot {}: {}", priority.bot_id, e),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use poise::serenity_prelude::{CreateEmbed, CreateMessage};
+
+use crate::config;
+
+/// The maximum length of the list of overdue bots in the alert
+const MAX_ALERT_LENGTH: usize = 3800;
+
+pub async fn queue_sla(ctx: &serenity::client::Context) -> Result<(), crate::Error> {
+    let data = ctx.data::<crate::Data>();
+    let pool = &data.pool;
+
+    let sla_hours = config::CONFIG.queue.sla_hours;
+
+    // Alerts of bots that left the queue are no longer needed, a bot that comes back is alerted again
+    sqlx::query!(
+        "DELETE FROM bot_queue_sla_alerts a WHERE NOT EXISTS (
+            SELECT 1 FROM bots b WHERE b.bot_id = a.bot_id AND b.type = 'pending'
+        )"
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Error while cleaning up queue SLA alerts: {}", e))?;
+
+    let overdue = crate::impls::queue::get_queue(pool, false)
+        .await?
+        .into_iter()
+        .filter(|p| chrono::Utc::now() - p.queued_at > chrono::Duration::hours(sla_hours))
+        .collect::<Vec<_>>();
+
+    if overdue.is_empty() {
+        return Ok(());
+    }
+
+    let unclaimed = sqlx::query!(
+        "SELECT bot_id FROM bots WHERE bot_id = ANY($1) AND claimed_by IS NULL",
+        &overdue.iter().map(|p| p.bot_id.clone()).collect::<Vec<_>>()
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Error while checking for unclaimed bots: {}", e))?
+    .into_iter()
+    .map(|r| r.bot_id)
+    .collect::<Vec<_>>();
+
+    let mut alert = String::new();
+    let mut alert_count = 0;
+
+    // Alerts are only recorded once the message was sent, a failed send rolls them back so they are retried
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Error while starting transaction: {}", e))?;
+
+    for priority in overdue.iter().filter(|p| unclaimed.contains(&p.bot_id)) {
+        // Each bot is only alerted once per stay in the queue
+        let inserted = sqlx::query!(
+            "INSERT INTO bot_queue_sla_alerts (bot_id, queued_at) VALUES ($1, $2) ON CONFLICT DO NOTHING RETURNING bot_id",
+            priority.bot_id,
+            priority.queued_at
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| format!("Error while recording queue SLA alert: {}", e))?;
+
+        if inserted.is_none() {
+            continue;
+        }
+
+        alert_count += 1;
+
+        let line = format!(
+            "**#{}** <@{}> - waiting {}\n",
+            priority.position,
+            priority.bot_id,
+            crate::impls::queue::format_wait(priority.queued_at)
+        );
+
+        if alert.len() + line.len() > MAX_ALERT_LENGTH {
+            continue;
+        }
+
+        alert.push_str(&line);
+    }
+
+    if alert_count == 0 {
+        return Ok(());
+    }
+
+    if alert_count > alert.lines().count() {
+        alert.push_str(&format!(
+            "*...and {} more, see ``/queue`` for the full queue*",
+            alert_count - alert.lines().count()
+        ));
+    }
+
+    config::CONFIG
+        .channels
+        .testing_lounge
+        .send_message(
+            &ctx.http,
+            CreateMessage::new().embeds(vec![CreateEmbed::new()
+                .title("Queue SLA Exceeded")
+                .description(format!(
+                    "The following bots have been waiting for over {} hours without being claimed:\n\n{}",
+                    sla_hours, alert
+                ))
+                .color(0xFF0000)]),
+        )
+        .await
+        .map_err(|e| format!("Error while sending message in #lounge: {}", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| format!("Error while committing transaction: {}", e))?;
+
+    Ok(())
+}
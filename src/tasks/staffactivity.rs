@@ -0,0 +1,89 @@
+use poise::serenity_prelude::{CreateEmbed, CreateMessage};
+
+use crate::config;
+
+/// The maximum length of the list of flagged staff members in the report
+const MAX_REPORT_LENGTH: usize = 3800;
+
+/// How often the report is sent, the task itself runs more often so restarts do not delay or repeat it
+const REPORT_INTERVAL_DAYS: i32 = 7;
+
+pub async fn staff_activity(ctx: &serenity::client::Context) -> Result<(), crate::Error> {
+    let data = ctx.data::<crate::Data>();
+    let pool = &data.pool;
+
+    let due = sqlx::query!(
+        "SELECT NOT EXISTS (SELECT 1 FROM staff_activity_reports WHERE created_at > NOW() - make_interval(days => $1)) AS \"due!\"",
+        REPORT_INTERVAL_DAYS
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| format!("Error while fetching the last staff activity report: {}", e))?
+    .due;
+
+    if !due {
+        return Ok(());
+    }
+
+    let window_days = config::CONFIG.activity.window_days;
+
+    let activity = crate::impls::activity::get_staff_activity(pool, window_days).await?;
+
+    let total_reviews = activity.iter().map(|a| a.reviews).sum::<i64>();
+    let total_claims = activity.iter().map(|a| a.claims).sum::<i64>();
+    let total_auto_unclaims = activity.iter().map(|a| a.auto_unclaims).sum::<i64>();
+    let on_loa = activity.iter().filter(|a| a.on_loa).count();
+
+    let mut flagged = String::new();
+    let mut flagged_count = 0;
+
+    for member in activity.iter().filter(|a| !a.flags.is_empty()) {
+        flagged_count += 1;
+
+        let line = format!("<@{}>: {}\n", member.user_id, member.flags.join(", "));
+
+        if flagged.len() + line.len() > MAX_REPORT_LENGTH {
+            continue;
+        }
+
+        flagged.push_str(&line);
+    }
+
+    if flagged.is_empty() {
+        flagged = "No staff members were flagged as inactive".to_string();
+    } else if flagged_count > flagged.lines().count() {
+        flagged.push_str(&format!(
+            "*...and {} more, see the panel for the full report*",
+            flagged_count - flagged.lines().count()
+        ));
+    }
+
+    config::CONFIG
+        .channels
+        .staff_logs
+        .send_message(
+            &ctx.http,
+            CreateMessage::new().embeds(vec![CreateEmbed::new()
+                .title(format!("Staff Activity Report (last {} days)", window_days))
+                .description(flagged)
+                .field("Staff Members", activity.len().to_string(), true)
+                .field("Flagged", flagged_count.to_string(), true)
+                .field("On Leave", on_loa.to_string(), true)
+                .field("Reviews", total_reviews.to_string(), true)
+                .field("Claims", total_claims.to_string(), true)
+                .field("Auto-Unclaims", total_auto_unclaims.to_string(), true)]),
+        )
+        .await
+        .map_err(|e| format!("Error while sending staff activity report: {}", e))?;
+
+    sqlx::query!(
+        "INSERT INTO staff_activity_reports (window_days, flagged) VALUES ($1, $2)",
+        window_days,
+        flagged_count as i32
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| format!("Error while recording staff activity report: {}", e))?;
+
+    Ok(())
+}
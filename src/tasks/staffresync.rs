@@ -12,6 +12,9 @@ use serenity::{
 use sqlx::types::Uuid;
 
 use crate::config;
+use crate::impls::timeline;
+use crate::panelapi::events::{self, PanelEvent};
+use crate::panelapi::types::staff_timeline::StaffTimelineEventKind;
 
 #[derive(Clone)]
 struct CachedPosition {
@@ -35,6 +38,98 @@ impl Display for CachedPosition {
     }
 }
 
+/// Returns whether removing a position from a member must be confirmed on the panel first
+fn needs_confirmation(pos: &CachedPosition) -> bool {
+    match config::CONFIG.resync.hold_removals_max_index {
+        Some(max_index) => pos.index <= max_index,
+        None => false,
+    }
+}
+
+/// Returns the names of the given positions for display, unknown positions are shown by ID
+fn position_names<'a>(
+    pos_cache_by_id: &HashMap<Uuid, CachedPosition>,
+    ids: impl Iterator<Item = &'a Uuid>,
+) -> Vec<String> {
+    ids.map(|id| match pos_cache_by_id.get(id) {
+        Some(pos) => pos.name.clone(),
+        None => id.hyphenated().to_string(),
+    })
+    .collect()
+}
+
+/// Holds the removal of a position from a member, returning whether the hold is new
+async fn hold_removal(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: &str,
+    position_id: &Uuid,
+) -> Result<bool, crate::Error> {
+    let new_hold = sqlx::query!(
+        "INSERT INTO staff_resync_holds (user_id, position_id) VALUES ($1, $2) ON CONFLICT (user_id, position_id) DO NOTHING RETURNING id",
+        user_id,
+        position_id
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| format!("Error while holding staff member position removal: {:?}", e))?
+    .is_some();
+
+    Ok(new_hold)
+}
+
+/// The changes made (or held back) by a resync, summarised in staff_logs once committed
+#[derive(Default)]
+struct DriftPlan {
+    added: Vec<String>,
+    removed: Vec<String>,
+    held: Vec<String>,
+    unaccounted: Vec<String>,
+}
+
+impl DriftPlan {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.held.is_empty()
+            && self.unaccounted.is_empty()
+    }
+
+    fn embed(&self) -> CreateEmbed {
+        CreateEmbed::new()
+            .title("Staff Resync Summary")
+            .description("Removals of held positions must be confirmed or rejected on the panel")
+            .field("Added", Self::field_value(&self.added), false)
+            .field("Removed", Self::field_value(&self.removed), false)
+            .field(
+                "Held For Confirmation",
+                Self::field_value(&self.held),
+                false,
+            )
+            .field("Unaccounted", Self::field_value(&self.unaccounted), false)
+    }
+
+    /// Embed field values are limited to 1024 characters
+    fn field_value(lines: &[String]) -> String {
+        if lines.is_empty() {
+            return "None".to_string();
+        }
+
+        let mut value = String::new();
+
+        for (i, line) in lines.iter().enumerate() {
+            if value.len() + line.len() > 950 {
+                value.push_str(&format!("...and {} more", lines.len() - i));
+                break;
+            }
+
+            value.push_str(line);
+            value.push('\n');
+        }
+
+        value
+    }
+}
+
 #[derive(Clone)]
 struct StaffResync {
     /// The user id of the member
@@ -58,13 +153,9 @@ async fn modify_corresponding_roles(
         };
 
         for link in pos.corresponding_roles.iter() {
-            let server_id = match link.name.as_str() {
-                "main" => config::CONFIG.servers.main,
-                "staff" => config::CONFIG.servers.staff,
-                _ => {
-                    log::warn!("Unknown corresponding server: {}", link.name);
-                    continue;
-                }
+            let Some(server_id) = config::CONFIG.servers.get_by_alias(&link.name) else {
+                log::warn!("Unknown corresponding server: {}", link.name);
+                continue;
             };
 
             let role_id = link.value.parse::<serenity::all::RoleId>()?;
@@ -81,13 +172,9 @@ async fn modify_corresponding_roles(
         };
 
         for link in pos.corresponding_roles.iter() {
-            let server_id = match link.name.as_str() {
-                "main" => config::CONFIG.servers.main,
-                "staff" => config::CONFIG.servers.staff,
-                _ => {
-                    log::warn!("Unknown corresponding server: {}", link.name);
-                    continue;
-                }
+            let Some(server_id) = config::CONFIG.servers.get_by_alias(&link.name) else {
+                log::warn!("Unknown corresponding server: {}", link.name);
+                continue;
             };
 
             let role_id = link.value.parse::<serenity::all::RoleId>()?;
@@ -300,6 +387,11 @@ pub async fn staff_resync(ctx: &serenity::client::Context) -> Result<(), crate::
         member_pos_cache
     };
 
+    // Events to publish to the panel once the transaction is committed
+    let mut panel_events = Vec::new();
+
+    let mut plan = DriftPlan::default();
+
     for user in staff_resync {
         // Skip if the user is in the noautosync list
         if staff_noautosync.contains(&user.user_id.to_string()) {
@@ -359,6 +451,39 @@ pub async fn staff_resync(ctx: &serenity::client::Context) -> Result<(), crate::
             }
         }
 
+        // Removals of positions high up in the hierarchy are held until confirmed on the panel
+        let held = user_positions_db
+            .difference(&user_positions)
+            .filter(|p| {
+                pos_cache_by_id
+                    .get(p)
+                    .map(needs_confirmation)
+                    .unwrap_or(false)
+            })
+            .copied()
+            .collect::<Vec<Uuid>>();
+
+        for pos in held.iter() {
+            if hold_removal(&mut tx, &user.user_id.to_string(), pos).await? {
+                if let Some(pos) = pos_cache_by_id.get(pos) {
+                    plan.held
+                        .push(format!("<@{}> ``{}``", user.user_id, pos.name));
+                }
+            }
+
+            user_positions.insert(*pos);
+        }
+
+        // Holds for positions the member has the role for again are no longer needed
+        sqlx::query!(
+            "DELETE FROM staff_resync_holds WHERE user_id = $1 AND NOT (position_id = ANY($2))",
+            user.user_id.to_string(),
+            &held
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Error while clearing staff resync holds: {:?}", e))?;
+
         // Compare user_positions_db and user_positions
         if user_positions
             .symmetric_difference(&user_positions_db)
@@ -393,6 +518,34 @@ pub async fn staff_resync(ctx: &serenity::client::Context) -> Result<(), crate::
                 })?;
             }
 
+            let old_names = position_names(&pos_cache_by_id, user_positions_db.iter());
+            let new_names = position_names(&pos_cache_by_id, user_positions.iter());
+
+            timeline::record(
+                &mut *tx,
+                &user.user_id.to_string(),
+                if is_on_db {
+                    StaffTimelineEventKind::Positions
+                } else {
+                    StaffTimelineEventKind::Membership
+                },
+                None,
+                if is_on_db {
+                    format!(
+                        "Staff resync changed positions from [{}] to [{}]",
+                        old_names.join(", "),
+                        new_names.join(", ")
+                    )
+                } else {
+                    format!(
+                        "Added by staff resync with positions [{}]",
+                        new_names.join(", ")
+                    )
+                },
+                serde_json::json!({ "old": old_names, "new": new_names }),
+            )
+            .await?;
+
             // Get the position with the highest index
             let mut lowest_index = i32::MAX;
 
@@ -571,6 +724,32 @@ pub async fn staff_resync(ctx: &serenity::client::Context) -> Result<(), crate::
                 user_positions.clone(),
             )
             .await?;
+
+            for pos in user_positions.difference(&user_positions_db) {
+                if let Some(pos) = pos_cache_by_id.get(pos) {
+                    plan.added
+                        .push(format!("<@{}> ``{}``", user.user_id, pos.name));
+                }
+            }
+
+            for pos in user_positions_db.difference(&user_positions) {
+                if let Some(pos) = pos_cache_by_id.get(pos) {
+                    plan.removed
+                        .push(format!("<@{}> ``{}``", user.user_id, pos.name));
+                }
+            }
+
+            panel_events.push(PanelEvent::StaffResync {
+                user_id: user.user_id.to_string(),
+                old_positions: user_positions_db
+                    .iter()
+                    .map(|p| p.hyphenated().to_string())
+                    .collect(),
+                new_positions: user_positions
+                    .iter()
+                    .map(|p| p.hyphenated().to_string())
+                    .collect(),
+            });
         }
 
         unaccounted_user_ids.remove(&user.user_id.to_string());
@@ -583,6 +762,37 @@ pub async fn staff_resync(ctx: &serenity::client::Context) -> Result<(), crate::
             continue;
         }
 
+        // Hold the removal of the whole member if they have any positions needing confirmation
+        let held = member_pos_cache
+            .get(&user_id)
+            .unwrap()
+            .iter()
+            .filter(|p| {
+                pos_cache_by_id
+                    .get(p)
+                    .map(needs_confirmation)
+                    .unwrap_or(false)
+            })
+            .copied()
+            .collect::<Vec<Uuid>>();
+
+        if !held.is_empty() {
+            for pos in held.iter() {
+                if hold_removal(&mut tx, &user_id, pos).await? {
+                    if let Some(pos) = pos_cache_by_id.get(pos) {
+                        plan.held.push(format!(
+                            "<@{}> ``{}`` (left the staff server)",
+                            user_id, pos.name
+                        ));
+                    }
+                }
+            }
+
+            continue;
+        }
+
+        plan.unaccounted.push(format!("<@{}>", user_id));
+
         let delete = if let Some(p) = staff_override_perms.get(&user_id) {
             p.is_empty()
         } else {
@@ -604,6 +814,42 @@ pub async fn staff_resync(ctx: &serenity::client::Context) -> Result<(), crate::
             .map_err(|e| format!("Error while updating unaccounted staff member: {:?}", e))?;
         }
 
+        let old_names = position_names(
+            &pos_cache_by_id,
+            member_pos_cache.get(&user_id).unwrap().iter(),
+        );
+
+        timeline::record(
+            &mut *tx,
+            &user_id,
+            StaffTimelineEventKind::Membership,
+            None,
+            if delete {
+                format!(
+                    "Removed by staff resync after leaving the staff server, had positions [{}]",
+                    old_names.join(", ")
+                )
+            } else {
+                format!(
+                    "Marked unaccounted by staff resync after leaving the staff server, had positions [{}]",
+                    old_names.join(", ")
+                )
+            },
+            serde_json::json!({ "old": old_names, "new": Vec::<String>::new(), "deleted": delete }),
+        )
+        .await?;
+
+        panel_events.push(PanelEvent::StaffResync {
+            user_id: user_id.clone(),
+            old_positions: member_pos_cache
+                .get(&user_id)
+                .unwrap()
+                .iter()
+                .map(|p| p.hyphenated().to_string())
+                .collect(),
+            new_positions: Vec::new(),
+        });
+
         let mut old_sp = perms::StaffPermissions {
             user_positions: vec![],
             perm_overrides: vec![],
@@ -765,5 +1011,30 @@ pub async fn staff_resync(ctx: &serenity::client::Context) -> Result<(), crate::
         .await
         .map_err(|e| format!("Error while committing transaction: {:?}", e))?;
 
+    // Only the staff members whose positions changed need their cached permissions dropped
+    for event in &panel_events {
+        if let PanelEvent::StaffResync { user_id, .. } = event {
+            crate::impls::permissions::invalidate(user_id).await;
+        }
+    }
+
+    if !plan.is_empty() {
+        if let Err(e) = crate::config::CONFIG
+            .channels
+            .staff_logs
+            .send_message(
+                &cache_http.http,
+                CreateMessage::new().embeds(vec![plan.embed()]),
+            )
+            .await
+        {
+            log::error!("Error while sending staff resync summary: {:?}", e);
+        }
+    }
+
+    for event in panel_events {
+        events::publish(event);
+    }
+
     Ok(())
 }
@@ -1,11 +1,18 @@
 use crate::impls::dovewing::DovewingSource;
 use crate::impls::target_types::TargetType;
+use crate::panelapi::types::bot_queue::QueuePriority;
+use crate::panelapi::types::pre_review::PreReview;
+use crate::panelapi::types::review_history::ReviewHistory;
 use crate::{checks, config};
 use futures_util::StreamExt;
 use log::info;
-use poise::serenity_prelude::{CreateActionRow, CreateButton, CreateEmbed, User};
+use poise::serenity_prelude::{
+    CreateActionRow, CreateButton, CreateEmbed, CreateInputText, CreateQuickModal, InputTextStyle,
+    User,
+};
 use poise::{serenity_prelude as serenity, CreateReply};
 use serde_json::json;
+use std::collections::HashMap;
 use std::time::Duration;
 
 type Error = crate::Error;
@@ -20,13 +27,20 @@ pub async fn invite_db(
     let data = ctx.data();
 
     let invite_data = sqlx::query!(
-        "SELECT invite FROM bots WHERE bot_id = $1 ORDER BY created_at DESC LIMIT 1",
+        "SELECT invite, client_id FROM bots WHERE bot_id = $1 ORDER BY created_at DESC LIMIT 1",
         bot
     )
     .fetch_one(&data.pool)
     .await?;
 
-    ctx.say(&format!("Invite: {}", invite_data.invite)).await?;
+    let analysis = crate::impls::invite::analyze(&invite_data.invite);
+
+    ctx.say(&format!(
+        "Invite: {}\n{}",
+        invite_data.invite,
+        crate::impls::invite::format_analysis(&analysis, &invite_data.client_id)
+    ))
+    .await?;
     Ok(())
 }
 
@@ -42,13 +56,11 @@ pub async fn invite(
         .fetch_one(&data.pool)
         .await?;
 
-    ctx.say(
-        format!(
-            "https://discord.com/api/v10/oauth2/authorize?client_id={client_id}&permissions=0&scope=bot%20applications.commands&guild_id={guild_id}", 
-            client_id = invite_data.client_id,
-            guild_id = crate::config::CONFIG.servers.testing
-        )
-    ).await?;
+    ctx.say(crate::impls::invite::safe_invite(
+        &invite_data.client_id,
+        crate::config::CONFIG.servers.testing,
+    ))
+    .await?;
     Ok(())
 }
 
@@ -81,11 +93,50 @@ struct InternalQueueBot {
     short: String,
     owner: String,
     invite: String,
+    queued_at: chrono::DateTime<chrono::Utc>,
+    score: f64,
+    history: ReviewHistory,
+    pre_review: Option<PreReview>,
+}
+
+/// Shows the automated pre-review checks of a bot as a checklist
+fn _pre_review_checks(pre_review: &Option<PreReview>) -> String {
+    match pre_review {
+        Some(pre_review) => crate::impls::prereview::format_checks(&pre_review.checks),
+        None => "*The pre-review checks have not run yet*".to_string(),
+    }
+}
+
+/// Summarizes the latest decision on a bot so reviewers can check the earlier issues were fixed
+fn _previous_review(history: &ReviewHistory) -> String {
+    let summary = match history
+        .entries
+        .iter()
+        .find(|e| e.action != "Claim" && e.action != "Unclaim")
+    {
+        Some(e) => format!(
+            "{} by <@{}> <t:{}:R>: {}",
+            e.action,
+            e.user_id,
+            e.created_at.timestamp(),
+            e.reason.as_deref().unwrap_or("No reason given")
+        ),
+        None => match history.previous_reviewer {
+            Some(ref user_id) => format!("Last reviewed by <@{}>", user_id),
+            None => "*This bot has not been reviewed before*".to_string(),
+        },
+    };
+
+    // Embed fields can be at most 1024 characters
+    summary.chars().take(1024).collect()
 }
 
 fn _queue_bot<'a>(qb: InternalQueueBot) -> CreateReply<'a> {
+    let invite_analysis = crate::impls::invite::analyze(&qb.invite);
+    let claimed = qb.claimed_by.is_some();
+
     let reply = if qb.text_msg {
-        let text_msg = format!("**{name} [{c_bot}/{bot_len}]**\n**ID:** {id}\n**Claimed by:** {claimed_by}\n**Approval note:** {approve_note}\n**Short:** {short}\n**Owner:** {owner}\n**Invite:** {invite}", 
+        let text_msg = format!("**{name} [{c_bot}/{bot_len}]**\n**ID:** {id}\n**Claimed by:** {claimed_by}\n**Approval note:** {approve_note}\n**Short:** {short}\n**Owner:** {owner}\n**Invite:** {invite}\n**Waiting:** {wait} (priority {score:.1})\n**Prior denials:** {denials}\n**Previous review:** {previous_review}\n**Pre-review:**\n{pre_review}\n**Invite analysis:**\n{invite_analysis}", 
             name = qb.queue_name,
             c_bot = qb.index + 1,
             bot_len = qb.total_bots,
@@ -94,7 +145,13 @@ fn _queue_bot<'a>(qb: InternalQueueBot) -> CreateReply<'a> {
             approve_note = qb.approval_note,
             short = qb.short,
             owner = qb.owner,
-            invite = qb.invite
+            invite = qb.invite,
+            wait = crate::impls::queue::format_wait(qb.queued_at),
+            score = qb.score,
+            denials = qb.history.denials,
+            previous_review = _previous_review(&qb.history),
+            pre_review = _pre_review_checks(&qb.pre_review),
+            invite_analysis = crate::impls::invite::format_analysis(&invite_analysis, &qb.client_id)
         );
 
         CreateReply::default().content(text_msg)
@@ -117,7 +174,25 @@ fn _queue_bot<'a>(qb: InternalQueueBot) -> CreateReply<'a> {
                 false,
             )
             .field("Approval note", qb.approval_note, true)
-            .field("Invite", format!("[Invite Bot]({})", qb.invite), true);
+            .field("Invite", format!("[Invite Bot]({})", qb.invite), true)
+            .field(
+                "Waiting",
+                format!(
+                    "{} (<t:{}:R>)",
+                    crate::impls::queue::format_wait(qb.queued_at),
+                    qb.queued_at.timestamp()
+                ),
+                true,
+            )
+            .field("Priority", format!("{:.1}", qb.score), true)
+            .field("Prior Denials", qb.history.denials.to_string(), true)
+            .field("Previous Review", _previous_review(&qb.history), false)
+            .field("Pre-Review", _pre_review_checks(&qb.pre_review), false)
+            .field(
+                "Invite Analysis",
+                crate::impls::invite::format_analysis(&invite_analysis, &qb.client_id),
+                false,
+            );
 
         CreateReply::default().embed(embed)
     };
@@ -137,76 +212,282 @@ fn _queue_bot<'a>(qb: InternalQueueBot) -> CreateReply<'a> {
                 .disabled(qb.index >= qb.total_bots - 1),
         ]),
         CreateActionRow::Buttons(vec![
-            CreateButton::new_link(
-                format!(
-                    "https://discord.com/api/v10/oauth2/authorize?client_id={client_id}&permissions=0&scope=bot%20applications.commands&guild_id={guild_id}", 
-                    client_id = qb.client_id,
-                    guild_id = crate::config::CONFIG.servers.testing
-                )
-            ).label("Invite"),
+            CreateButton::new_link(crate::impls::invite::safe_invite(
+                &qb.client_id,
+                crate::config::CONFIG.servers.testing,
+            ))
+            .label("Invite"),
             CreateButton::new_link(qb.invite).label("Invite (DB, Unsafe)"),
             CreateButton::new_link(
                 config::CONFIG.frontend_url.get().clone() + "/bots/" + &qb.bot_id,
             )
             .label("View Page"),
         ]),
+        CreateActionRow::Buttons(vec![
+            CreateButton::new("q:claim")
+                .label("Claim")
+                .style(serenity::ButtonStyle::Primary)
+                .disabled(claimed),
+            CreateButton::new("q:unclaim")
+                .label("Unclaim")
+                .style(serenity::ButtonStyle::Secondary)
+                .disabled(!claimed),
+            CreateButton::new("q:approve")
+                .label("Approve")
+                .style(serenity::ButtonStyle::Success)
+                .disabled(!claimed),
+            CreateButton::new("q:deny")
+                .label("Deny")
+                .style(serenity::ButtonStyle::Danger)
+                .disabled(!claimed),
+        ]),
     ])
 }
 
+/// Which bots the queue command shows
+#[derive(poise::ChoiceParameter)]
+pub enum QueueFilter {
+    #[name = "All"]
+    All,
+    #[name = "Unclaimed"]
+    Unclaimed,
+    #[name = "Claimed"]
+    Claimed,
+    #[name = "Claimed by me"]
+    ClaimedByMe,
+}
+
+/// A bot in the queue along with how it was prioritized
+struct QueueEntry {
+    bot_id: String,
+    client_id: String,
+    claimed_by: Option<String>,
+    approval_note: String,
+    short: String,
+    invite: String,
+    priority: QueuePriority,
+}
+
+/// Fetches the bots in the queue matching the filter, highest priority first
+async fn _fetch_queue(
+    pool: &sqlx::PgPool,
+    filter: &QueueFilter,
+    user_id: &str,
+) -> Result<Vec<QueueEntry>, Error> {
+    let priorities = crate::impls::queue::get_queue(pool, false).await?;
+
+    let bot_ids = priorities
+        .iter()
+        .map(|p| p.bot_id.clone())
+        .collect::<Vec<_>>();
+
+    let mut rows = sqlx::query!(
+        "SELECT claimed_by, bot_id, approval_note, short, invite, client_id FROM bots WHERE bot_id = ANY($1)",
+        &bot_ids
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|bot| (bot.bot_id.clone(), bot))
+    .collect::<HashMap<_, _>>();
+
+    Ok(priorities
+        .into_iter()
+        .filter_map(|priority| {
+            let bot = rows.remove(&priority.bot_id)?;
+            let claimed_by = bot.claimed_by.filter(|c| !c.is_empty());
+
+            let matches = match filter {
+                QueueFilter::All => true,
+                QueueFilter::Unclaimed => claimed_by.is_none(),
+                QueueFilter::Claimed => claimed_by.is_some(),
+                QueueFilter::ClaimedByMe => claimed_by.as_deref() == Some(user_id),
+            };
+
+            matches.then_some(QueueEntry {
+                bot_id: bot.bot_id,
+                client_id: bot.client_id,
+                claimed_by,
+                approval_note: bot.approval_note,
+                short: bot.short,
+                invite: bot.invite,
+                priority,
+            })
+        })
+        .collect())
+}
+
+/// Builds the queue message for the bot at the given index
+async fn _queue_message<'a>(
+    ctx: Context<'_>,
+    entries: &[QueueEntry],
+    index: usize,
+    embed: bool,
+) -> Result<CreateReply<'a>, Error> {
+    let data = ctx.data();
+    let entry = &entries[index];
+
+    let owners =
+        crate::impls::utils::get_entity_managers(TargetType::Bot, &entry.bot_id, &data.pool)
+            .await?;
+
+    let bot_partial = crate::impls::dovewing::get_platform_user(
+        &data.pool,
+        DovewingSource::Discord(botox::cache::CacheHttpImpl::from_ctx(
+            ctx.serenity_context(),
+        )),
+        &entry.bot_id,
+    )
+    .await?;
+
+    let bot_ids = [entry.bot_id.clone()];
+    let mut history = crate::impls::review::get_review_history(&data.pool, &bot_ids).await?;
+    let mut pre_reviews = crate::impls::prereview::get_pre_reviews(&data.pool, &bot_ids).await?;
+
+    Ok(_queue_bot(InternalQueueBot {
+        index,
+        total_bots: entries.len(),
+        bot_id: entry.bot_id.clone(),
+        client_id: entry.client_id.clone(),
+        queue_name: bot_partial.display_name,
+        text_msg: !embed,
+        claimed_by: entry.claimed_by.clone(),
+        approval_note: entry.approval_note.clone(),
+        short: entry.short.clone(),
+        owner: owners.mention_users(),
+        invite: entry.invite.clone(),
+        queued_at: entry.priority.queued_at,
+        score: entry.priority.score,
+        history: history.remove(&entry.bot_id).unwrap_or_default(),
+        pre_review: pre_reviews.remove(&entry.bot_id),
+    }))
+}
+
+/// Runs the checks of the claim, unclaim, approve and deny commands for the queue buttons
+async fn _queue_action_checks(ctx: Context<'_>) -> Result<(), Error> {
+    if !checks::testing_server(ctx).await? {
+        return Err("You are not in the testing server".into());
+    }
+
+    checks::is_staff(ctx).await?;
+    checks::needs_onboarding(ctx).await?;
+
+    Ok(())
+}
+
+/// Handles the claim, unclaim, approve and deny buttons of the queue, returning None if the action was cancelled
+async fn _queue_action(
+    ctx: Context<'_>,
+    item: &serenity::ComponentInteraction,
+    bot_id: &str,
+) -> Result<Option<String>, Error> {
+    let http = &ctx.serenity_context().http;
+    let id = item.data.custom_id.as_str();
+
+    if let Err(e) = _queue_action_checks(ctx).await {
+        item.defer(http).await?;
+        return Err(e);
+    }
+
+    let method = if id == "q:claim" {
+        item.defer(http).await?;
+
+        crate::rpc::core::RPCMethod::Claim {
+            target_id: bot_id.to_string(),
+            force: false,
+        }
+    } else {
+        let title = match id {
+            "q:unclaim" => "Unclaim Bot",
+            "q:approve" => "Approve Bot",
+            _ => "Deny Bot",
+        };
+
+        let qm = CreateQuickModal::new(title)
+            .timeout(Duration::from_secs(300))
+            .field(CreateInputText::new(
+                InputTextStyle::Paragraph,
+                "Reason",
+                "reason",
+            ));
+
+        let Some(resp) = item.quick_modal(ctx.serenity_context(), qm).await? else {
+            return Ok(None);
+        };
+
+        resp.interaction.defer(http).await?;
+
+        let reason = resp.inputs.into_iter().next().unwrap_or_default();
+
+        match id {
+            "q:unclaim" => crate::rpc::core::RPCMethod::Unclaim {
+                target_id: bot_id.to_string(),
+                reason,
+            },
+            _ => {
+                let approve = id == "q:approve";
+
+                let Some(checklist) = _review_checklist(ctx, approve).await? else {
+                    return Ok(None);
+                };
+
+                if approve {
+                    crate::rpc::core::RPCMethod::Approve {
+                        target_id: bot_id.to_string(),
+                        reason,
+                        checklist,
+                    }
+                } else {
+                    crate::rpc::core::RPCMethod::Deny {
+                        target_id: bot_id.to_string(),
+                        reason,
+                        checklist,
+                    }
+                }
+            }
+        }
+    };
+
+    let res = method
+        .handle(crate::rpc::core::RPCHandle {
+            pool: ctx.data().pool.clone(),
+            cache_http: botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+            user_id: ctx.author().id.to_string(),
+            target_type: TargetType::Bot,
+        })
+        .await?;
+
+    Ok(Some(match res.content() {
+        Some(content) => format!("Done! <@{}>\n{}", bot_id, content),
+        None => format!("Done! <@{}>", bot_id),
+    }))
+}
+
 /// Checks the bot queue
 #[poise::command(prefix_command, slash_command, user_cooldown = 3, category = "Testing")]
 pub async fn queue(
     ctx: Context<'_>,
     #[description = "Whether to embed or not"] embed: Option<bool>,
+    #[description = "Which bots to show"] filter: Option<QueueFilter>,
 ) -> Result<(), Error> {
     let embed = embed.unwrap_or(true);
+    let filter = filter.unwrap_or(QueueFilter::All);
+    let user_id = ctx.author().id.to_string();
 
     let data = ctx.data();
 
-    let bots = sqlx::query!(
-        "SELECT claimed_by, bot_id, approval_note, short, invite, client_id FROM bots WHERE type = 'pending' ORDER BY created_at ASC",
-    )
-    .fetch_all(&data.pool)
-    .await?;
+    let mut entries = _fetch_queue(&data.pool, &filter, &user_id).await?;
 
-    if bots.is_empty() {
+    if entries.is_empty() {
         ctx.say("There are no bots in the queue!").await?;
         return Ok(());
     }
 
     let mut current_bot = 0;
-    let bot_len = bots.len();
 
     // Send message with buttons
-    let bot = &bots[current_bot];
-
-    let owners =
-        crate::impls::utils::get_entity_managers(TargetType::Bot, &bot.bot_id, &data.pool).await?;
-
-    let bot_partial: crate::impls::dovewing::PlatformUser =
-        crate::impls::dovewing::get_platform_user(
-            &data.pool,
-            DovewingSource::Discord(botox::cache::CacheHttpImpl::from_ctx(
-                ctx.serenity_context(),
-            )),
-            &bot.bot_id,
-        )
-        .await?;
-
     let mut msg = ctx
-        .send(_queue_bot(InternalQueueBot {
-            index: current_bot,
-            total_bots: bot_len,
-            bot_id: bot.bot_id.clone(),
-            client_id: bot.client_id.clone(),
-            queue_name: bot_partial.display_name,
-            text_msg: !embed,
-            claimed_by: bot.claimed_by.clone(),
-            approval_note: bot.approval_note.clone(),
-            short: bot.short.clone(),
-            owner: owners.mention_users(),
-            invite: bot.invite.clone(),
-        }))
+        .send(_queue_message(ctx, &entries, current_bot, embed).await?)
         .await?
         .into_message()
         .await?;
@@ -214,66 +495,73 @@ pub async fn queue(
     let mut interaction = msg
         .await_component_interactions(ctx.serenity_context().shard.clone())
         .author_id(ctx.author().id)
-        .timeout(Duration::from_secs(120))
+        .timeout(Duration::from_secs(300))
         .stream();
 
     while let Some(item) = interaction.next().await {
-        item.defer(&ctx.serenity_context().http).await?;
-
-        let id = &item.data.custom_id;
+        let id = item.data.custom_id.as_str();
 
         info!("Received interaction: {}", id);
 
-        if id == "q:cancel" {
-            item.delete_response(&ctx.serenity_context().http).await?;
-            return Ok(());
-        }
-
-        if id == "q:prev" {
-            if current_bot == 0 {
-                current_bot = 0
+        match id {
+            "q:cancel" => {
+                item.defer(&ctx.serenity_context().http).await?;
+                item.delete_response(&ctx.serenity_context().http).await?;
+                return Ok(());
             }
-
-            current_bot -= 1;
-        } else if id == "q:next" {
-            if current_bot >= bot_len - 1 {
-                current_bot = bot_len - 1
+            "q:prev" => {
+                item.defer(&ctx.serenity_context().http).await?;
+                current_bot = current_bot.saturating_sub(1);
             }
+            "q:next" => {
+                item.defer(&ctx.serenity_context().http).await?;
+                current_bot = (current_bot + 1).min(entries.len() - 1);
+            }
+            "q:claim" | "q:unclaim" | "q:approve" | "q:deny" => {
+                let bot_id = entries[current_bot].bot_id.clone();
+
+                match _queue_action(ctx, &item, &bot_id).await {
+                    Ok(Some(content)) => {
+                        ctx.send(CreateReply::default().content(content).ephemeral(true))
+                            .await?;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        ctx.send(
+                            CreateReply::default()
+                                .content(format!("Could not update <@{}>: {}", bot_id, e))
+                                .ephemeral(true),
+                        )
+                        .await?;
+                    }
+                }
+
+                // The bot may have left the queue or been claimed by someone else
+                entries = _fetch_queue(&data.pool, &filter, &user_id).await?;
+
+                if entries.is_empty() {
+                    msg.edit(
+                        ctx,
+                        serenity::EditMessage::default()
+                            .content("There are no bots left in the queue!")
+                            .embeds(vec![])
+                            .components(vec![]),
+                    )
+                    .await?;
 
-            current_bot += 1
-        }
-
-        let bot = &bots[current_bot];
-
-        let owners =
-            crate::impls::utils::get_entity_managers(TargetType::Bot, &bot.bot_id, &data.pool)
-                .await?;
+                    return Ok(());
+                }
 
-        let bot_partial = crate::impls::dovewing::get_platform_user(
-            &data.pool,
-            DovewingSource::Discord(botox::cache::CacheHttpImpl::from_ctx(
-                ctx.serenity_context(),
-            )),
-            &bot.bot_id,
-        )
-        .await?;
+                current_bot = current_bot.min(entries.len() - 1);
+            }
+            _ => continue,
+        }
 
         msg.edit(
             ctx,
-            _queue_bot(InternalQueueBot {
-                index: current_bot,
-                total_bots: bot_len,
-                bot_id: bot.bot_id.clone(),
-                client_id: bot.client_id.clone(),
-                queue_name: bot_partial.display_name,
-                text_msg: !embed,
-                claimed_by: bot.claimed_by.clone(),
-                approval_note: bot.approval_note.clone(),
-                short: bot.short.clone(),
-                owner: owners.mention_users(),
-                invite: bot.invite.clone(),
-            })
-            .to_prefix_edit(poise::serenity_prelude::EditMessage::default()),
+            _queue_message(ctx, &entries, current_bot, embed)
+                .await?
+                .to_prefix_edit(poise::serenity_prelude::EditMessage::default()),
         )
         .await?;
     }
@@ -281,6 +569,44 @@ pub async fn queue(
     Ok(())
 }
 
+/// Runs the automated pre-review checks on a bot
+#[poise::command(
+    prefix_command,
+    slash_command,
+    user_cooldown = 10,
+    category = "Testing",
+    check = "checks::is_staff"
+)]
+pub async fn prereview(
+    ctx: Context<'_>,
+    #[description = "The bot to check"] bot: User,
+) -> Result<(), Error> {
+    let data = ctx.data();
+
+    ctx.defer().await?;
+
+    let pre_review = crate::impls::prereview::run(
+        &data.pool,
+        &botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        &bot.id.to_string(),
+    )
+    .await?;
+
+    let passed = pre_review.checks.iter().all(|c| c.passed);
+
+    ctx.send(
+        CreateReply::default().embed(
+            CreateEmbed::default()
+                .title(format!("Pre-Review: {}", bot.name))
+                .description(crate::impls::prereview::format_checks(&pre_review.checks))
+                .color(if passed { 0x00ff00 } else { 0xFF0000 }),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
 /// Claims a bot
 #[poise::command(
     prefix_command,
@@ -435,6 +761,102 @@ pub async fn unclaim(
     Ok(())
 }
 
+/// Asks the reviewer to fill in the review checklist, returning None if it was cancelled or timed out
+async fn _review_checklist(
+    ctx: Context<'_>,
+    approve: bool,
+) -> Result<Option<HashMap<String, bool>>, Error> {
+    let items = &config::CONFIG.review.checklist;
+
+    if items.is_empty() {
+        return Ok(Some(HashMap::new()));
+    }
+
+    // Discord select menus can have at most 25 options
+    let options = items
+        .iter()
+        .take(25)
+        .map(|item| {
+            let mut option = serenity::CreateSelectMenuOption::new(
+                item.label.chars().take(100).collect::<String>(),
+                item.id.clone(),
+            );
+
+            if approve && item.required_for_approval {
+                option = option.description("Must pass to approve");
+            }
+
+            option
+        })
+        .collect::<Vec<_>>();
+
+    let msg = ctx
+        .send(
+            CreateReply::default()
+                .content("Select every review checklist item the bot passed, then press ``Submit``")
+                .components(vec![
+                    CreateActionRow::SelectMenu(
+                        serenity::CreateSelectMenu::new(
+                            "rc:items",
+                            serenity::CreateSelectMenuKind::String { options },
+                        )
+                        .min_values(0)
+                        .max_values(items.len().min(25) as u8),
+                    ),
+                    CreateActionRow::Buttons(vec![
+                        CreateButton::new("rc:submit")
+                            .label("Submit")
+                            .style(serenity::ButtonStyle::Success),
+                        CreateButton::new("rc:cancel")
+                            .label("Cancel")
+                            .style(serenity::ButtonStyle::Danger),
+                    ]),
+                ]),
+        )
+        .await?
+        .into_message()
+        .await?;
+
+    let mut passed = Vec::new();
+
+    let mut interaction = msg
+        .await_component_interactions(ctx.serenity_context().shard.clone())
+        .author_id(ctx.author().id)
+        .timeout(Duration::from_secs(300))
+        .stream();
+
+    while let Some(item) = interaction.next().await {
+        item.defer(&ctx.serenity_context().http).await?;
+
+        match item.data.custom_id.as_str() {
+            "rc:items" => {
+                if let serenity::ComponentInteractionDataKind::StringSelect { values } =
+                    &item.data.kind
+                {
+                    passed = values.to_vec();
+                }
+            }
+            "rc:submit" => {
+                item.delete_response(&ctx.serenity_context().http).await?;
+
+                return Ok(Some(
+                    items
+                        .iter()
+                        .map(|i| (i.id.clone(), passed.contains(&i.id)))
+                        .collect(),
+                ));
+            }
+            "rc:cancel" => {
+                item.delete_response(&ctx.serenity_context().http).await?;
+                return Ok(None);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(None)
+}
+
 /// Approves a bot
 #[poise::command(
     prefix_command,
@@ -455,12 +877,17 @@ pub async fn approve(
 
     let data = ctx.data();
 
+    let Some(checklist) = _review_checklist(ctx, true).await? else {
+        return Err("The review checklist was not submitted, the bot has not been approved".into());
+    };
+
     ctx.defer_or_broadcast().await?;
 
     // Create a rpc call
     let res = crate::rpc::core::RPCMethod::Approve {
         target_id: bot.user.id.to_string(),
         reason: reason.clone(),
+        checklist,
     }
     .handle(crate::rpc::core::RPCHandle {
         pool: data.pool.clone(),
@@ -493,7 +920,10 @@ pub async fn approve(
 pub async fn deny(
     ctx: Context<'_>,
     #[description = "The bot you wish to deny"] bot: serenity::User,
-    #[description = "The reason for denial"] reason: String,
+    #[description = "The reason for denial, added after any templates"] reason: Option<String>,
+    #[description = "Reason templates to use, separated by commas"]
+    #[autocomplete = "deny_template_autocomplete"]
+    templates: Option<String>,
 ) -> Result<(), Error> {
     if !checks::testing_server(ctx).await? {
         return Err("You are not in the testing server".into());
@@ -501,11 +931,18 @@ pub async fn deny(
 
     let data = ctx.data();
 
+    let reason = _resolve_reason(ctx, "Deny", &bot.id.to_string(), templates, reason).await?;
+
+    let Some(checklist) = _review_checklist(ctx, false).await? else {
+        return Err("The review checklist was not submitted, the bot has not been denied".into());
+    };
+
     ctx.defer_or_broadcast().await?;
 
     crate::rpc::core::RPCMethod::Deny {
         target_id: bot.id.to_string(),
-        reason: reason.clone(),
+        reason,
+        checklist,
     }
     .handle(crate::rpc::core::RPCHandle {
         pool: data.pool.clone(),
@@ -519,3 +956,141 @@ pub async fn deny(
 
     Ok(())
 }
+
+/// Unverifies a bot, sending it back to the queue
+#[poise::command(
+    prefix_command,
+    slash_command,
+    user_cooldown = 10,
+    category = "Testing",
+    check = "checks::is_staff",
+    check = "checks::needs_onboarding"
+)]
+pub async fn unverify(
+    ctx: Context<'_>,
+    #[description = "The bot you wish to unverify"] bot: serenity::User,
+    #[description = "The reason for unverifying, added after any templates"] reason: Option<String>,
+    #[description = "Reason templates to use, separated by commas"]
+    #[autocomplete = "unverify_template_autocomplete"]
+    templates: Option<String>,
+) -> Result<(), Error> {
+    let data = ctx.data();
+
+    let reason = _resolve_reason(ctx, "Unverify", &bot.id.to_string(), templates, reason).await?;
+
+    ctx.defer_or_broadcast().await?;
+
+    crate::rpc::core::RPCMethod::Unverify {
+        target_id: bot.id.to_string(),
+        reason,
+    }
+    .handle(crate::rpc::core::RPCHandle {
+        pool: data.pool.clone(),
+        cache_http: botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        user_id: ctx.author().id.to_string(),
+        target_type: TargetType::Bot,
+    })
+    .await?;
+
+    ctx.say("Okay! The bot has been unverified.").await?;
+
+    Ok(())
+}
+
+async fn deny_template_autocomplete<'a>(
+    ctx: Context<'_>,
+    partial: &str,
+) -> Vec<serenity::AutocompleteChoice<'a>> {
+    _reason_template_autocomplete(ctx, "Deny", partial).await
+}
+
+async fn unverify_template_autocomplete<'a>(
+    ctx: Context<'_>,
+    partial: &str,
+) -> Vec<serenity::AutocompleteChoice<'a>> {
+    _reason_template_autocomplete(ctx, "Unverify", partial).await
+}
+
+/// Autocompletes the reason templates of an RPC method, completing only the name after the last comma
+/// so several templates can be combined
+async fn _reason_template_autocomplete<'a>(
+    ctx: Context<'_>,
+    method: &str,
+    partial: &str,
+) -> Vec<serenity::AutocompleteChoice<'a>> {
+    let Ok(templates) =
+        crate::impls::review::get_reason_templates(&ctx.data().pool, Some(method)).await
+    else {
+        return Vec::new();
+    };
+
+    let (chosen, current) = match partial.rsplit_once(',') {
+        Some((chosen, current)) => (
+            chosen
+                .split(',')
+                .map(|n| n.trim().to_lowercase())
+                .collect::<Vec<_>>(),
+            current.trim().to_lowercase(),
+        ),
+        None => (Vec::new(), partial.trim().to_lowercase()),
+    };
+
+    let prefix = partial
+        .rsplit_once(',')
+        .map(|(p, _)| format!("{}, ", p.trim()))
+        .unwrap_or_default();
+
+    templates
+        .into_iter()
+        .filter(|t| {
+            let name = t.name.to_lowercase();
+            !chosen.contains(&name) && (current.is_empty() || name.contains(&current))
+        })
+        .map(|t| format!("{}{}", prefix, t.name))
+        // Autocomplete values can be at most 100 characters
+        .filter(|v| v.len() <= 100)
+        .take(25)
+        .map(|v| serenity::AutocompleteChoice::new(v.clone(), v))
+        .collect()
+}
+
+/// Combines the comma separated reason templates picked and the custom reason into the reason to
+/// submit with the RPC method
+async fn _resolve_reason(
+    ctx: Context<'_>,
+    method: &str,
+    bot_id: &str,
+    templates: Option<String>,
+    reason: Option<String>,
+) -> Result<String, Error> {
+    let mut picked = Vec::new();
+
+    if let Some(templates) = templates {
+        let available =
+            crate::impls::review::get_reason_templates(&ctx.data().pool, Some(method)).await?;
+
+        for name in templates
+            .split(',')
+            .map(|n| n.trim())
+            .filter(|n| !n.is_empty())
+        {
+            let Some(template) = available
+                .iter()
+                .find(|t| t.name.to_lowercase() == name.to_lowercase())
+            else {
+                return Err(format!("Unknown reason template: {}", name).into());
+            };
+
+            picked.push(template.clone());
+        }
+    }
+
+    crate::impls::review::render_reason(
+        &ctx.data().pool,
+        &botox::cache::CacheHttpImpl::from_ctx(ctx.serenity_context()),
+        &picked,
+        bot_id,
+        reason.as_deref(),
+    )
+    .await
+}